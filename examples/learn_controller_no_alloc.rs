@@ -4,7 +4,7 @@
 
 use std::collections::HashMap;
 
-use clap::{command, Parser};
+use clap::Parser;
 use common::MySocket;
 use embedded_bacnet::{
     application_protocol::{
@@ -70,7 +70,7 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
                 | ObjectType::ObjectAnalogValue
                 | ObjectType::ObjectSchedule
                 | ObjectType::ObjectTrendlog => {
-                    let list = map.entry(item.object_type.clone() as u32).or_insert(vec![]);
+                    let list = map.entry(u32::from(item.object_type)).or_insert(vec![]);
                     list.push(item);
                 }
                 _ => {}
@@ -84,7 +84,7 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
             ObjectType::ObjectBinaryInput
             | ObjectType::ObjectBinaryOutput
             | ObjectType::ObjectBinaryValue => {
-                for chunk in ids.as_slice().chunks(10).into_iter() {
+                for chunk in ids.as_slice().chunks(10) {
                     let _values = get_multi_binary(&mut bacnet, &mut buf, chunk).await?;
                     println!("{:?}", _values);
                 }
@@ -92,7 +92,7 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
             ObjectType::ObjectAnalogInput
             | ObjectType::ObjectAnalogOutput
             | ObjectType::ObjectAnalogValue => {
-                for chunk in ids.as_slice().chunks(10).into_iter() {
+                for chunk in ids.as_slice().chunks(10) {
                     let _values = get_multi_analog(&mut bacnet, &mut buf, chunk).await?;
                     println!("{:?}", _values);
                 }
@@ -104,7 +104,7 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
                 }
             }
             ObjectType::ObjectTrendlog => {
-                for chunk in ids.as_slice().chunks(10).into_iter() {
+                for chunk in ids.as_slice().chunks(10) {
                     let values = get_multi_trend_log(&mut bacnet, &mut buf, chunk).await?;
                     println!("{:?}", values);
                 }
@@ -167,7 +167,7 @@ async fn get_multi_binary(
     ];
     let items: Vec<ReadPropertyMultipleObject> = object_ids
         .iter()
-        .map(|x| ReadPropertyMultipleObject::new(x.clone(), &property_ids))
+        .map(|x| ReadPropertyMultipleObject::new(*x, &property_ids))
         .collect();
     let request = ReadPropertyMultiple::new(&items);
     let result = bacnet.read_property_multiple(buf, request).await?;
@@ -177,12 +177,12 @@ async fn get_multi_binary(
         let obj = obj?;
         let mut x = obj.property_results.into_iter();
         let name = x.next().unwrap()?.value.to_string();
-        let value = match x.next().unwrap()?.value {
+        let value = matches!(
+            x.next().unwrap()?.value,
             PropertyValue::PropValue(ApplicationDataValue::Enumerated(Enumerated::Binary(
                 Binary::On,
-            ))) => true,
-            _ => false,
-        };
+            )))
+        );
         let status = match x.next().unwrap()?.value {
             PropertyValue::PropValue(ApplicationDataValue::BitString(BitString::Status(x))) => x,
             _ => unreachable!(),
@@ -196,7 +196,7 @@ async fn get_multi_binary(
         });
     }
 
-    return Ok(items);
+    Ok(items)
 }
 
 #[cfg(not(feature = "alloc"))]
@@ -214,7 +214,7 @@ async fn get_multi_analog(
 
     let items: Vec<ReadPropertyMultipleObject> = object_ids
         .iter()
-        .map(|x| ReadPropertyMultipleObject::new(x.clone(), &property_ids))
+        .map(|x| ReadPropertyMultipleObject::new(*x, &property_ids))
         .collect();
 
     let request = ReadPropertyMultiple::new(&items);
@@ -249,7 +249,7 @@ async fn get_multi_analog(
         })
     }
 
-    return Ok(items);
+    Ok(items)
 }
 
 #[cfg(not(feature = "alloc"))]
@@ -262,7 +262,7 @@ async fn get_multi_trend_log(
 
     let items: Vec<ReadPropertyMultipleObject> = object_ids
         .iter()
-        .map(|x| ReadPropertyMultipleObject::new(x.clone(), &property_ids))
+        .map(|x| ReadPropertyMultipleObject::new(*x, &property_ids))
         .collect();
 
     let request = ReadPropertyMultiple::new(&items);
@@ -286,7 +286,7 @@ async fn get_multi_trend_log(
         })
     }
 
-    return Ok(items);
+    Ok(items)
 }
 
 #[cfg(not(feature = "alloc"))]
@@ -296,10 +296,7 @@ async fn get_multi_schedule(
     object_id: &ObjectId,
 ) -> Result<Vec<ScheduleValue>, BacnetError<MySocket>> {
     let property_ids = [PropertyId::PropObjectName, PropertyId::PropWeeklySchedule];
-    let objects = [ReadPropertyMultipleObject::new(
-        object_id.clone(),
-        &property_ids,
-    )];
+    let objects = [ReadPropertyMultipleObject::new(*object_id, &property_ids)];
     let request = ReadPropertyMultiple::new(&objects);
     let result = bacnet.read_property_multiple(buf, request).await?;
 
@@ -327,5 +324,5 @@ async fn get_multi_schedule(
         });
     }
 
-    return Ok(items);
+    Ok(items)
 }
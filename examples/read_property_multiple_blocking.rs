@@ -1,6 +1,6 @@
 // cargo run --example read_property_multiple_blocking --features="is_sync,alloc" -- --addr "192.168.1.249:47808"
 
-use clap::{command, Parser};
+use clap::Parser;
 use embedded_bacnet::{
     application_protocol::services::read_property_multiple::{
         ReadPropertyMultiple, ReadPropertyMultipleObject,
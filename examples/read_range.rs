@@ -7,7 +7,7 @@
 use core::ops::Range;
 
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
-use clap::{command, Parser};
+use clap::Parser;
 use common::MySocket;
 use embedded_bacnet::{
     application_protocol::{
@@ -43,19 +43,13 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
 
     // fetch record count
     let object_id = ObjectId::new(ObjectType::ObjectTrendlog, 1);
-    let record_count = get_record_count(&mut bacnet, &mut buf, object_id.clone()).await?;
+    let record_count = get_record_count(&mut bacnet, &mut buf, object_id).await?;
     println!("Record count {record_count}");
 
     // fetch records in batches and print
     const MAX_LOG_COUNT_PER_REQ: usize = 55;
     for row in (1..=record_count).step_by(MAX_LOG_COUNT_PER_REQ) {
-        get_items_for_range(
-            &mut bacnet,
-            &mut buf,
-            object_id.clone(),
-            row..MAX_LOG_COUNT_PER_REQ,
-        )
-        .await?;
+        get_items_for_range(&mut bacnet, &mut buf, object_id, row..MAX_LOG_COUNT_PER_REQ).await?;
     }
 
     Ok(())
@@ -87,7 +81,7 @@ async fn get_items_for_range(
 ) -> Result<(), BacnetError<MySocket>> {
     let request_type = ReadRangeRequestType::ByPosition(ReadRangeByPosition {
         index: range.start as u32,
-        count: range.end as u32,
+        count: range.end as i32,
     });
     let request = ReadRange::new(object_id, PropertyId::PropLogBuffer, request_type);
     let result = bacnet.read_range(buf, request).await?;
@@ -123,7 +117,7 @@ async fn get_items_for_range(
 ) -> Result<(), BacnetError<MySocket>> {
     let request_type = ReadRangeRequestType::ByPosition(ReadRangeByPosition {
         index: range.start as u32,
-        count: range.end as u32,
+        count: range.end as i32,
     });
     let request = ReadRange::new(object_id, PropertyId::PropLogBuffer, request_type);
     let result = bacnet.read_range(buf, request).await?;
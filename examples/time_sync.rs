@@ -2,7 +2,7 @@
 
 #![allow(unused_imports)]
 use chrono::{Datelike, Local, Timelike};
-use clap::{command, Parser};
+use clap::Parser;
 use common::MySocket;
 use embedded_bacnet::{
     application_protocol::{
@@ -2,7 +2,7 @@
 
 #![allow(unused_imports)]
 
-use clap::{command, Parser};
+use clap::Parser;
 use common::MySocket;
 use embedded_bacnet::{
     application_protocol::{
@@ -60,8 +60,8 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
         ApplicationDataValueWrite::WeeklySchedule(weekly_schedule),
     );
 
-    let ack = bacnet.write_property(&mut buf, request).await?;
-    println!("Write ack: {:?}", ack);
+    bacnet.write_property(&mut buf, request).await?;
+    println!("Write ack: {:?}", ());
 
     Ok(())
 }
@@ -7,7 +7,7 @@ mod common;
 fn main() {}
 
 use crate::common::{get_bacnet_socket, MySocket};
-use clap::{command, Parser};
+use clap::Parser;
 use embedded_bacnet::{
     application_protocol::{
         primitives::data_value::{ApplicationDataValue, ApplicationDataValueWrite},
@@ -40,7 +40,7 @@ struct Args {
 async fn main() -> Result<(), BacnetError<MySocket>> {
     // setup
     let args = Args::parse();
-    let mut bacnet = get_bacnet_socket(&args.addr).await?;
+    let bacnet = get_bacnet_socket(&args.addr).await?;
     let mut buf = vec![0; 1500];
 
     // fetch
@@ -127,8 +127,8 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
         ApplicationDataValueWrite::WeeklySchedule(weekly_schedule),
     );
 
-    let ack = bacnet.write_property(&mut buf, request).await?;
-    println!("Write ack: {:?}", ack);
+    bacnet.write_property(&mut buf, request).await?;
+    println!("Write ack: {:?}", ());
 
     Ok(())
 }
@@ -1,7 +1,7 @@
 // cargo run --example who_is_broadcast
 // cargo run --example who_is_broadcast -- --addr "192.168.1.249:47808"
 
-use std::{io::Error, net::UdpSocket};
+use std::net::UdpSocket;
 
 use clap::Parser;
 use embedded_bacnet::{
@@ -47,13 +47,13 @@ struct Args {
 // NOTE: this example works with broadcast UDP packets which may be blocked by your network
 // You can get around this by sending the who_is directly to a known IP aaddress
 // Since sending who_is requests are somewhat network specific (you need to know who the peer is) we don't have a conveneince function for it as this would be messy.
-fn main() -> Result<(), Error> {
+fn main() -> Result<(), MainError> {
     simple_logger::init().unwrap();
     let args = Args::parse();
     let socket = UdpSocket::bind(format!("0.0.0.0:{}", 0xBAC1))?;
     socket.set_broadcast(true)?;
 
-    let who_is = WhoIs {};
+    let who_is = WhoIs::new();
     let apdu = ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::WhoIs(who_is));
     let dst = Some(DestinationAddress::new(0xffff, None));
     let message = NetworkMessage::Apdu(apdu);
@@ -65,7 +65,7 @@ fn main() -> Result<(), Error> {
     {
         let mut buffer = Writer::new(&mut buffer);
         data_link.encode(&mut buffer);
-        let buf = buffer.to_bytes();
+        let buf = buffer.try_to_bytes()?;
         socket.send_to(buf, &args.addr)?;
         println!("Sent:     {:02x?} to {}\n", buf, &args.addr);
     }
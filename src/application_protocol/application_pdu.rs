@@ -1,6 +1,9 @@
 use crate::{
     application_protocol::{
-        confirmed::{ComplexAck, ConfirmedBacnetError, ConfirmedRequest, SegmentAck, SimpleAck},
+        confirmed::{
+            Abort, ComplexAck, ConfirmedBacnetError, ConfirmedRequest, Reject, SegmentAck,
+            SimpleAck,
+        },
         segment::Segment,
         unconfirmed::UnconfirmedRequest,
     },
@@ -21,7 +24,8 @@ pub enum ApplicationPdu<'a> {
     Error(ConfirmedBacnetError),
     Segment(Segment<'a>),
     SegmentAck(SegmentAck),
-    // add more here (see ApduType)
+    Reject(Reject),
+    Abort(Abort),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -88,6 +92,22 @@ impl From<u8> for MaxSegments {
     }
 }
 
+impl MaxSegments {
+    // the largest number of segments, when the limit is unspecified (0) or more than 64
+    // the number of segments is only limited by the 8 bit sequence number
+    pub fn segments(&self) -> usize {
+        match self {
+            Self::_0 | Self::_65 => 256,
+            Self::_2 => 2,
+            Self::_4 => 4,
+            Self::_8 => 8,
+            Self::_16 => 16,
+            Self::_32 => 32,
+            Self::_64 => 64,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
@@ -114,7 +134,22 @@ impl From<u8> for MaxAdpu {
     }
 }
 
+impl MaxAdpu {
+    // the largest apdu size in octets (a value of 0 means up to 50 octets)
+    pub fn octets(&self) -> usize {
+        match self {
+            Self::_0 => 50,
+            Self::_128 => 128,
+            Self::_206 => 206,
+            Self::_480 => 480,
+            Self::_1024 => 1024,
+            Self::_1476 => 1476,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum PduFlags {
     Server = 0b0001,
     SegmentedResponseAccepted = 0b0010,
@@ -131,7 +166,9 @@ impl<'a> ApplicationPdu<'a> {
             Self::SimpleAck(ack) => ack.encode(writer),
             Self::SegmentAck(ack) => ack.encode(writer),
             Self::Segment(segment) => segment.encode(writer),
-            Self::Error(_) => todo!(),
+            Self::Reject(reject) => reject.encode(writer),
+            Self::Abort(abort) => abort.encode(writer),
+            Self::Error(error) => error.encode(writer),
         };
     }
 
@@ -142,6 +179,7 @@ impl<'a> ApplicationPdu<'a> {
         let pdu_flags = byte0 & 0x0F;
         let segmented_message = (pdu_flags & PduFlags::SegmentedMessage as u8) > 0;
         let more_follows = (pdu_flags & PduFlags::MoreFollows as u8) > 0;
+        let server = (pdu_flags & PduFlags::Server as u8) > 0;
         let _segmented_response_accepted =
             (pdu_flags & PduFlags::SegmentedResponseAccepted as u8) > 0;
 
@@ -168,14 +206,22 @@ impl<'a> ApplicationPdu<'a> {
                 Ok(Self::SimpleAck(adpu))
             }
             ApduType::SegmentAck => {
-                let adpu = SegmentAck::decode(reader, buf)?;
+                let negative_ack = (pdu_flags & SegmentAck::NEGATIVE_ACK_FLAG) > 0;
+                let adpu = SegmentAck::decode(negative_ack, server, reader, buf)?;
                 Ok(Self::SegmentAck(adpu))
             }
             ApduType::Error => {
                 let apdu = ConfirmedBacnetError::decode(reader, buf)?;
                 Ok(Self::Error(apdu))
             }
-            apdu_type => Err(Error::ApduTypeNotSupported(apdu_type)),
+            ApduType::Reject => {
+                let apdu = Reject::decode(reader, buf)?;
+                Ok(Self::Reject(apdu))
+            }
+            ApduType::Abort => {
+                let apdu = Abort::decode(server, reader, buf)?;
+                Ok(Self::Abort(apdu))
+            }
         }
     }
 }
@@ -1,17 +1,32 @@
 use crate::{
     application_protocol::{
         application_pdu::{ApduType, ApplicationPdu, MaxAdpu, MaxSegments, PduFlags},
+        primitives::object_reference::ObjectPropertyReference,
         services::{
+            acknowledge_alarm::AcknowledgeAlarm,
+            atomic_read_file::{AtomicReadFile, AtomicReadFileAck},
+            atomic_write_file::{AtomicWriteFile, AtomicWriteFileAck},
             change_of_value::SubscribeCov,
+            create_object::{CreateObject, CreateObjectAck},
+            device_communication_control::DeviceCommunicationControl,
+            event_notification::EventNotification,
+            get_alarm_summary::GetAlarmSummaryAck,
+            get_event_information::{GetEventInformation, GetEventInformationAck},
+            list_element::ListElementRequest,
             read_property::{ReadProperty, ReadPropertyAck},
             read_property_multiple::{ReadPropertyMultiple, ReadPropertyMultipleAck},
             read_range::{ReadRange, ReadRangeAck},
+            reinitialize_device::ReinitializeDevice,
             write_property::WriteProperty,
+            write_property_multiple::WritePropertyMultiple,
         },
     },
     common::{
         error::{Error, Unimplemented},
-        helper::decode_unsigned,
+        helper::{
+            decode_unsigned, encode_application_enumerated, encode_closing_tag,
+            encode_context_unsigned, encode_opening_tag, get_tagged_body_for_tag,
+        },
         io::{Reader, Writer},
         spec::{ErrorClass, ErrorCode},
         tag::{ApplicationTagNumber, Tag, TagNumber},
@@ -71,10 +86,57 @@ impl<'a> ConfirmedRequest<'a> {
                 writer.push(ConfirmedServiceChoice::WriteProperty as u8);
                 service.encode(writer)
             }
+            ConfirmedRequestService::WritePropertyMultiple(service) => {
+                writer.push(ConfirmedServiceChoice::WritePropMultiple as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::CreateObject(service) => {
+                writer.push(ConfirmedServiceChoice::CreateObject as u8);
+                service.encode(writer)
+            }
             ConfirmedRequestService::ReadRange(service) => {
                 writer.push(ConfirmedServiceChoice::ReadRange as u8);
                 service.encode(writer)
             }
+            ConfirmedRequestService::AtomicReadFile(service) => {
+                writer.push(ConfirmedServiceChoice::AtomicReadFile as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::AtomicWriteFile(service) => {
+                writer.push(ConfirmedServiceChoice::AtomicWriteFile as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::ReinitializeDevice(service) => {
+                writer.push(ConfirmedServiceChoice::ReinitializeDevice as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::DeviceCommunicationControl(service) => {
+                writer.push(ConfirmedServiceChoice::DeviceCommunicationControl as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::EventNotification(service) => {
+                writer.push(ConfirmedServiceChoice::EventNotification as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::AcknowledgeAlarm(service) => {
+                writer.push(ConfirmedServiceChoice::AcknowledgeAlarm as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::GetEventInformation(service) => {
+                writer.push(ConfirmedServiceChoice::GetEventInformation as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::GetAlarmSummary => {
+                writer.push(ConfirmedServiceChoice::GetAlarmSummary as u8);
+            }
+            ConfirmedRequestService::AddListElement(service) => {
+                writer.push(ConfirmedServiceChoice::AddListElement as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::RemoveListElement(service) => {
+                writer.push(ConfirmedServiceChoice::RemoveListElement as u8);
+                service.encode(writer)
+            }
         };
     }
 
@@ -169,6 +231,37 @@ pub enum ConfirmedServiceChoice {
     MaxBacnetConfirmedService = 34,
 }
 
+// the kind of acknowledgement a server returns when a confirmed service succeeds
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AckKind {
+    Simple,
+    Complex,
+}
+
+impl ConfirmedServiceChoice {
+    pub fn ack_kind(&self) -> AckKind {
+        match self {
+            Self::AtomicReadFile
+            | Self::AtomicWriteFile
+            | Self::CreateObject
+            | Self::ReadProperty
+            | Self::ReadPropConditional
+            | Self::ReadPropMultiple
+            | Self::ReadRange
+            | Self::GetAlarmSummary
+            | Self::GetEnrollmentSummary
+            | Self::GetEventInformation
+            | Self::PrivateTransfer
+            | Self::Authenticate
+            | Self::VtOpen
+            | Self::VtData
+            | Self::AuditLogQuery => AckKind::Complex,
+            _ => AckKind::Simple,
+        }
+    }
+}
+
 impl TryFrom<u8> for ConfirmedServiceChoice {
     type Error = u8;
 
@@ -225,7 +318,7 @@ impl<'a> TryFrom<DataLink<'a>> for SimpleAck {
     type Error = Error;
 
     fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
-        match value.npdu {
+        match value.into_npdu() {
             Some(x) => match x.network_message {
                 NetworkMessage::Apdu(ApplicationPdu::SimpleAck(ack)) => Ok(ack),
                 _ => Err(Error::ConvertDataLink(
@@ -267,9 +360,94 @@ pub struct ConfirmedBacnetError {
     pub service_choice: ConfirmedServiceChoice,
     pub error_class: ErrorClass,
     pub error_code: ErrorCode,
+    // the (1 based) index of the first element that could not be processed
+    // required for CreateObject, AddListElement and RemoveListElement errors
+    pub first_failed_element: Option<u32>,
+    // the first write that could not be processed, required for WritePropertyMultiple errors
+    pub first_failed_write: Option<ObjectPropertyReference>,
+}
+
+impl<'a> TryFrom<DataLink<'a>> for ConfirmedBacnetError {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        match value.into_npdu() {
+            Some(x) => match x.network_message {
+                NetworkMessage::Apdu(ApplicationPdu::Error(error)) => Ok(error),
+                _ => Err(Error::ConvertDataLink("npdu message is not an apdu error")),
+            },
+            _ => Err(Error::ConvertDataLink("no npdu defined in message")),
+        }
+    }
 }
 
 impl ConfirmedBacnetError {
+    const ERROR_TYPE_TAG: u8 = 0;
+    const FIRST_FAILED_ELEMENT_TAG: u8 = 1;
+
+    // a missing first_failed_element or first_failed_write for a service that requires it
+    // is reported by writer.try_to_bytes
+    pub fn encode(&self, writer: &mut Writer) {
+        let control = (ApduType::Error as u8) << 4;
+        writer.push(control);
+        writer.push(self.invoke_id);
+        writer.push(self.service_choice.clone() as u8);
+
+        let has_first_failed_element = Self::has_first_failed_element(&self.service_choice);
+        let is_write_property_multiple = matches!(
+            self.service_choice,
+            ConfirmedServiceChoice::WritePropMultiple
+        );
+        let is_wrapped = has_first_failed_element || is_write_property_multiple;
+
+        if is_wrapped {
+            encode_opening_tag(writer, Self::ERROR_TYPE_TAG);
+        }
+
+        encode_application_enumerated(writer, u32::from(&self.error_class));
+        encode_application_enumerated(writer, u32::from(&self.error_code));
+
+        if is_wrapped {
+            encode_closing_tag(writer, Self::ERROR_TYPE_TAG);
+        }
+
+        if has_first_failed_element {
+            match self.first_failed_element {
+                Some(first_failed_element) => encode_context_unsigned(
+                    writer,
+                    Self::FIRST_FAILED_ELEMENT_TAG,
+                    first_failed_element,
+                ),
+                None => writer.fail(Error::InvalidValue(
+                    "ConfirmedBacnetError first_failed_element is required for this service",
+                )),
+            }
+        }
+
+        if is_write_property_multiple {
+            match &self.first_failed_write {
+                Some(first_failed_write) => {
+                    encode_opening_tag(writer, Self::FIRST_FAILED_ELEMENT_TAG);
+                    first_failed_write.encode(writer);
+                    encode_closing_tag(writer, Self::FIRST_FAILED_ELEMENT_TAG);
+                }
+                None => writer.fail(Error::InvalidValue(
+                    "ConfirmedBacnetError first_failed_write is required for WritePropertyMultiple",
+                )),
+            }
+        }
+    }
+
+    // these services wrap the error in context tag 0 and follow it with the failed element number
+    fn has_first_failed_element(service_choice: &ConfirmedServiceChoice) -> bool {
+        matches!(
+            service_choice,
+            ConfirmedServiceChoice::CreateObject
+                | ConfirmedServiceChoice::AddListElement
+                | ConfirmedServiceChoice::RemoveListElement
+        )
+    }
+
     pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
         let invoke_id = reader.read_byte(buf)?;
         let service_choice: ConfirmedServiceChoice =
@@ -280,6 +458,74 @@ impl ConfirmedBacnetError {
                 ))
             })?;
 
+        let has_first_failed_element = Self::has_first_failed_element(&service_choice);
+        // WritePropertyMultiple also wraps the error but follows it with the first failed property
+        let is_write_property_multiple =
+            matches!(service_choice, ConfirmedServiceChoice::WritePropMultiple);
+        let is_wrapped = has_first_failed_element || is_write_property_multiple;
+
+        if is_wrapped {
+            Tag::decode_expected(
+                reader,
+                buf,
+                TagNumber::ContextSpecificOpening(Self::ERROR_TYPE_TAG),
+                "ConfirmedBacnetError error type opening",
+            )?;
+        }
+
+        let (error_class, error_code) = Self::decode_class_and_code(reader, buf)?;
+
+        if is_wrapped {
+            Tag::decode_expected(
+                reader,
+                buf,
+                TagNumber::ContextSpecificClosing(Self::ERROR_TYPE_TAG),
+                "ConfirmedBacnetError error type closing",
+            )?;
+        }
+
+        let first_failed_element = if has_first_failed_element {
+            let tag = Tag::decode_expected(
+                reader,
+                buf,
+                TagNumber::ContextSpecific(Self::FIRST_FAILED_ELEMENT_TAG),
+                "ConfirmedBacnetError first failed element",
+            )?;
+            Some(decode_unsigned(tag.value, reader, buf)? as u32)
+        } else {
+            None
+        };
+
+        let first_failed_write = if is_write_property_multiple {
+            let inner_buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::FIRST_FAILED_ELEMENT_TAG,
+                "ConfirmedBacnetError first failed write",
+            )?;
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            Some(ObjectPropertyReference::decode(
+                &mut inner_reader,
+                inner_buf,
+            )?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            invoke_id,
+            service_choice,
+            error_class,
+            error_code,
+            first_failed_element,
+            first_failed_write,
+        })
+    }
+
+    fn decode_class_and_code(
+        reader: &mut Reader,
+        buf: &[u8],
+    ) -> Result<(ErrorClass, ErrorCode), Error> {
         let tag = Tag::decode_expected(
             reader,
             buf,
@@ -300,11 +546,211 @@ impl ConfirmedBacnetError {
         let error_code =
             ErrorCode::try_from(value).map_err(|e| Error::InvalidVariant(("ErrorCode", e)))?;
 
+        Ok((error_class, error_code))
+    }
+}
+
+// sent by either peer to terminate a transaction
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Abort {
+    pub server: bool, // true if the abort was sent by the server
+    pub invoke_id: u8,
+    pub reason: AbortReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AbortReason {
+    Other,
+    BufferOverflow,
+    InvalidApduInThisState,
+    PreemptedByHigherPriorityTask,
+    SegmentationNotSupported,
+    SecurityError,
+    InsufficientSecurity,
+    WindowSizeOutOfRange,
+    ApplicationExceededReplyTime,
+    OutOfResources,
+    TsmTimeout,
+    ApduTooLong,
+    Proprietary(u8), // 64 to 255
+    Reserved(u8),
+}
+
+impl From<u8> for AbortReason {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Other,
+            1 => Self::BufferOverflow,
+            2 => Self::InvalidApduInThisState,
+            3 => Self::PreemptedByHigherPriorityTask,
+            4 => Self::SegmentationNotSupported,
+            5 => Self::SecurityError,
+            6 => Self::InsufficientSecurity,
+            7 => Self::WindowSizeOutOfRange,
+            8 => Self::ApplicationExceededReplyTime,
+            9 => Self::OutOfResources,
+            10 => Self::TsmTimeout,
+            11 => Self::ApduTooLong,
+            x if x >= 64 => Self::Proprietary(x),
+            x => Self::Reserved(x),
+        }
+    }
+}
+
+impl From<&AbortReason> for u8 {
+    fn from(value: &AbortReason) -> Self {
+        match value {
+            AbortReason::Other => 0,
+            AbortReason::BufferOverflow => 1,
+            AbortReason::InvalidApduInThisState => 2,
+            AbortReason::PreemptedByHigherPriorityTask => 3,
+            AbortReason::SegmentationNotSupported => 4,
+            AbortReason::SecurityError => 5,
+            AbortReason::InsufficientSecurity => 6,
+            AbortReason::WindowSizeOutOfRange => 7,
+            AbortReason::ApplicationExceededReplyTime => 8,
+            AbortReason::OutOfResources => 9,
+            AbortReason::TsmTimeout => 10,
+            AbortReason::ApduTooLong => 11,
+            AbortReason::Proprietary(x) => *x,
+            AbortReason::Reserved(x) => *x,
+        }
+    }
+}
+
+// sent by the server when a confirmed request is malformed or cannot be understood
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Reject {
+    pub invoke_id: u8,
+    pub reason: RejectReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RejectReason {
+    Other,
+    BufferOverflow,
+    InconsistentParameters,
+    InvalidParameterDataType,
+    InvalidTag,
+    MissingRequiredParameter,
+    ParameterOutOfRange,
+    TooManyArguments,
+    UndefinedEnumeration,
+    UnrecognizedService,
+    InvalidDataEncoding,
+    Proprietary(u8), // 64 to 255
+    Reserved(u8),
+}
+
+impl From<u8> for RejectReason {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Other,
+            1 => Self::BufferOverflow,
+            2 => Self::InconsistentParameters,
+            3 => Self::InvalidParameterDataType,
+            4 => Self::InvalidTag,
+            5 => Self::MissingRequiredParameter,
+            6 => Self::ParameterOutOfRange,
+            7 => Self::TooManyArguments,
+            8 => Self::UndefinedEnumeration,
+            9 => Self::UnrecognizedService,
+            10 => Self::InvalidDataEncoding,
+            x if x >= 64 => Self::Proprietary(x),
+            x => Self::Reserved(x),
+        }
+    }
+}
+
+impl From<&RejectReason> for u8 {
+    fn from(value: &RejectReason) -> Self {
+        match value {
+            RejectReason::Other => 0,
+            RejectReason::BufferOverflow => 1,
+            RejectReason::InconsistentParameters => 2,
+            RejectReason::InvalidParameterDataType => 3,
+            RejectReason::InvalidTag => 4,
+            RejectReason::MissingRequiredParameter => 5,
+            RejectReason::ParameterOutOfRange => 6,
+            RejectReason::TooManyArguments => 7,
+            RejectReason::UndefinedEnumeration => 8,
+            RejectReason::UnrecognizedService => 9,
+            RejectReason::InvalidDataEncoding => 10,
+            RejectReason::Proprietary(x) => *x,
+            RejectReason::Reserved(x) => *x,
+        }
+    }
+}
+
+impl<'a> TryFrom<DataLink<'a>> for Reject {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        match value.into_npdu() {
+            Some(x) => match x.network_message {
+                NetworkMessage::Apdu(ApplicationPdu::Reject(reject)) => Ok(reject),
+                _ => Err(Error::ConvertDataLink("npdu message is not an apdu reject")),
+            },
+            _ => Err(Error::ConvertDataLink("no npdu defined in message")),
+        }
+    }
+}
+
+impl Reject {
+    pub fn encode(&self, writer: &mut Writer) {
+        let control = (ApduType::Reject as u8) << 4;
+        writer.push(control);
+        writer.push(self.invoke_id);
+        writer.push((&self.reason).into());
+    }
+
+    // the control byte has already been read
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let invoke_id = reader.read_byte(buf)?;
+        let reason = reader.read_byte(buf)?.into();
+
+        Ok(Self { invoke_id, reason })
+    }
+}
+
+impl<'a> TryFrom<DataLink<'a>> for Abort {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        match value.into_npdu() {
+            Some(x) => match x.network_message {
+                NetworkMessage::Apdu(ApplicationPdu::Abort(abort)) => Ok(abort),
+                _ => Err(Error::ConvertDataLink("npdu message is not an apdu abort")),
+            },
+            _ => Err(Error::ConvertDataLink("no npdu defined in message")),
+        }
+    }
+}
+
+impl Abort {
+    pub fn encode(&self, writer: &mut Writer) {
+        let mut control = (ApduType::Abort as u8) << 4;
+        if self.server {
+            control |= PduFlags::Server as u8;
+        }
+        writer.push(control);
+        writer.push(self.invoke_id);
+        writer.push((&self.reason).into());
+    }
+
+    // the control byte has already been read
+    pub fn decode(server: bool, reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let invoke_id = reader.read_byte(buf)?;
+        let reason = reader.read_byte(buf)?.into();
+
         Ok(Self {
+            server,
             invoke_id,
-            service_choice,
-            error_class,
-            error_code,
+            reason,
         })
     }
 }
@@ -320,7 +766,7 @@ impl<'a> TryFrom<DataLink<'a>> for ComplexAck<'a> {
     type Error = Error;
 
     fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
-        match value.npdu {
+        match value.into_npdu() {
             Some(x) => match x.network_message {
                 NetworkMessage::Apdu(ApplicationPdu::ComplexAck(ack)) => Ok(ack),
                 _ => Err(Error::ConvertDataLink(
@@ -342,6 +788,11 @@ impl<'a> ComplexAck<'a> {
             ComplexAckService::ReadProperty(service) => service.encode(writer),
             ComplexAckService::ReadPropertyMultiple(service) => service.encode(writer),
             ComplexAckService::ReadRange(service) => service.encode(writer),
+            ComplexAckService::AtomicReadFile(service) => service.encode(writer),
+            ComplexAckService::AtomicWriteFile(service) => service.encode(writer),
+            ComplexAckService::CreateObject(service) => service.encode(writer),
+            ComplexAckService::GetEventInformation(service) => service.encode(writer),
+            ComplexAckService::GetAlarmSummary(service) => service.encode(writer),
         }
     }
 
@@ -363,10 +814,30 @@ pub enum ComplexAckService<'a> {
     ReadProperty(ReadPropertyAck<'a>),
     ReadPropertyMultiple(ReadPropertyMultipleAck<'a>),
     ReadRange(ReadRangeAck<'a>),
+    AtomicReadFile(AtomicReadFileAck<'a>),
+    AtomicWriteFile(AtomicWriteFileAck),
+    CreateObject(CreateObjectAck),
+    GetEventInformation(GetEventInformationAck<'a>),
+    GetAlarmSummary(GetAlarmSummaryAck<'a>),
     // add more here
 }
 
 impl<'a> ComplexAckService<'a> {
+    pub fn choice(&self) -> ConfirmedServiceChoice {
+        match self {
+            Self::ReadProperty(_) => ConfirmedServiceChoice::ReadProperty,
+            Self::ReadPropertyMultiple(_) => ConfirmedServiceChoice::ReadPropMultiple,
+            Self::ReadRange(_) => ConfirmedServiceChoice::ReadRange,
+            Self::AtomicReadFile(_) => ConfirmedServiceChoice::AtomicReadFile,
+            Self::AtomicWriteFile(_) => ConfirmedServiceChoice::AtomicWriteFile,
+            Self::CreateObject(_) => ConfirmedServiceChoice::CreateObject,
+            Self::GetEventInformation(_) => ConfirmedServiceChoice::GetEventInformation,
+            Self::GetAlarmSummary(_) => ConfirmedServiceChoice::GetAlarmSummary,
+        }
+    }
+
+    // decodes the service body only, the choice is supplied by the caller
+    // (usually read from the byte following the invoke_id but it may also be known up front)
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
     pub fn decode(
         choice: ConfirmedServiceChoice,
@@ -386,6 +857,26 @@ impl<'a> ComplexAckService<'a> {
                 let service = ReadRangeAck::decode(reader, buf)?;
                 Ok(ComplexAckService::ReadRange(service))
             }
+            ConfirmedServiceChoice::AtomicReadFile => {
+                let service = AtomicReadFileAck::decode(reader, buf)?;
+                Ok(ComplexAckService::AtomicReadFile(service))
+            }
+            ConfirmedServiceChoice::AtomicWriteFile => {
+                let service = AtomicWriteFileAck::decode(reader, buf)?;
+                Ok(ComplexAckService::AtomicWriteFile(service))
+            }
+            ConfirmedServiceChoice::CreateObject => {
+                let service = CreateObjectAck::decode(reader, buf)?;
+                Ok(ComplexAckService::CreateObject(service))
+            }
+            ConfirmedServiceChoice::GetEventInformation => {
+                let service = GetEventInformationAck::decode(reader, buf)?;
+                Ok(ComplexAckService::GetEventInformation(service))
+            }
+            ConfirmedServiceChoice::GetAlarmSummary => {
+                let service = GetAlarmSummaryAck::decode(reader, buf)?;
+                Ok(ComplexAckService::GetAlarmSummary(service))
+            }
             s => Err(Error::Unimplemented(Unimplemented::ConfirmedServiceChoice(
                 s,
             ))),
@@ -400,8 +891,20 @@ pub enum ConfirmedRequestService<'a> {
     ReadPropertyMultiple(ReadPropertyMultiple<'a>),
     SubscribeCov(SubscribeCov),
     WriteProperty(WriteProperty<'a>),
+    WritePropertyMultiple(WritePropertyMultiple<'a>), // encode only
+    CreateObject(CreateObject<'a>),                   // encode only
     ReadRange(ReadRange),
-    // add more here (see ConfirmedServiceChoice enum)
+    AtomicReadFile(AtomicReadFile),
+    AtomicWriteFile(AtomicWriteFile<'a>),       // encode only
+    ReinitializeDevice(ReinitializeDevice<'a>), // encode only
+    DeviceCommunicationControl(DeviceCommunicationControl<'a>), // encode only
+    EventNotification(EventNotification<'a>),   // encode and decode
+    AcknowledgeAlarm(AcknowledgeAlarm<'a>),     // encode only
+    GetEventInformation(GetEventInformation),   // encode only
+    GetAlarmSummary,                            // encode only (there are no request parameters)
+    AddListElement(ListElementRequest<'a>),     // encode only
+    RemoveListElement(ListElementRequest<'a>),  // encode only
+                                                // add more here (see ConfirmedServiceChoice enum)
 }
 
 impl<'a> ConfirmedRequestService<'a> {
@@ -428,6 +931,14 @@ impl<'a> ConfirmedRequestService<'a> {
                 let service = WriteProperty::decode(reader, buf)?;
                 Ok(ConfirmedRequestService::WriteProperty(service))
             }
+            ConfirmedServiceChoice::AtomicReadFile => {
+                let service = AtomicReadFile::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::AtomicReadFile(service))
+            }
+            ConfirmedServiceChoice::EventNotification => {
+                let service = EventNotification::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::EventNotification(service))
+            }
             s => Err(Error::Unimplemented(Unimplemented::ConfirmedServiceChoice(
                 s,
             ))),
@@ -438,20 +949,22 @@ impl<'a> ConfirmedRequestService<'a> {
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct SegmentAck {
+    pub negative_ack: bool, // true to request retransmission of the segments after sequence_num
+    pub server: bool,       // true if the ack was sent by the server
     pub invoke_id: u8,
-    pub sequence_num: u8,
-    pub proposed_window_size: u8,
+    pub sequence_num: u8,       // the last segment received in order
+    pub actual_window_size: u8, // the window size chosen by the receiver
 }
 
 impl<'a> TryFrom<DataLink<'a>> for SegmentAck {
     type Error = Error;
 
     fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
-        match value.npdu {
+        match value.into_npdu() {
             Some(x) => match x.network_message {
                 NetworkMessage::Apdu(ApplicationPdu::SegmentAck(ack)) => Ok(ack),
                 _ => Err(Error::ConvertDataLink(
-                    "npdu message is not an apdu simple ack",
+                    "npdu message is not an apdu segment ack",
                 )),
             },
             _ => Err(Error::ConvertDataLink("no npdu defined in message")),
@@ -460,23 +973,374 @@ impl<'a> TryFrom<DataLink<'a>> for SegmentAck {
 }
 
 impl SegmentAck {
+    // shares its bit with PduFlags::SegmentedResponseAccepted
+    pub const NEGATIVE_ACK_FLAG: u8 = 0b0010;
+
     pub fn encode(&self, writer: &mut Writer) {
-        let control = (ApduType::SegmentAck as u8) << 4;
+        let mut control = (ApduType::SegmentAck as u8) << 4;
+        if self.negative_ack {
+            control |= Self::NEGATIVE_ACK_FLAG;
+        }
+        if self.server {
+            control |= PduFlags::Server as u8;
+        }
         writer.push(control);
         writer.push(self.invoke_id);
         writer.push(self.sequence_num);
-        writer.push(self.proposed_window_size);
+        writer.push(self.actual_window_size);
     }
 
-    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+    // the control byte has already been read
+    pub fn decode(
+        negative_ack: bool,
+        server: bool,
+        reader: &mut Reader,
+        buf: &[u8],
+    ) -> Result<Self, Error> {
         let invoke_id = reader.read_byte(buf)?;
         let sequence_num = reader.read_byte(buf)?;
-        let proposed_window_size = reader.read_byte(buf)?;
+        let actual_window_size = reader.read_byte(buf)?;
 
         Ok(Self {
+            negative_ack,
+            server,
             invoke_id,
             sequence_num,
-            proposed_window_size,
+            actual_window_size,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            primitives::{
+                data_value::ApplicationDataValue, object_reference::ObjectPropertyReference,
+            },
+            services::read_property::{ReadPropertyAck, ReadPropertyValue},
+        },
+        common::{
+            error::Error,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{ErrorClass, ErrorCode, BACNET_ARRAY_ALL},
+        },
+        network_protocol::data_link::DataLink,
+    };
+
+    use super::{
+        AckKind, ComplexAckService, ConfirmedBacnetError, ConfirmedServiceChoice, Reject,
+        RejectReason, SegmentAck, SimpleAck,
+    };
+
+    #[test]
+    fn ack_kind() {
+        assert_eq!(
+            ConfirmedServiceChoice::WriteProperty.ack_kind(),
+            AckKind::Simple
+        );
+        assert_eq!(
+            ConfirmedServiceChoice::SubscribeCov.ack_kind(),
+            AckKind::Simple
+        );
+        assert_eq!(
+            ConfirmedServiceChoice::ReinitializeDevice.ack_kind(),
+            AckKind::Simple
+        );
+        assert_eq!(
+            ConfirmedServiceChoice::ReadProperty.ack_kind(),
+            AckKind::Complex
+        );
+        assert_eq!(
+            ConfirmedServiceChoice::ReadPropMultiple.ack_kind(),
+            AckKind::Complex
+        );
+        assert_eq!(
+            ConfirmedServiceChoice::ReadRange.ack_kind(),
+            AckKind::Complex
+        );
+        assert_eq!(
+            ConfirmedServiceChoice::AtomicReadFile.ack_kind(),
+            AckKind::Complex
+        );
+    }
+
+    #[test]
+    fn decode_service_body_with_known_choice() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 3);
+        let ack = ReadPropertyAck {
+            object_id,
+            property_id: PropertyId::PropPresentValue,
+            array_index: BACNET_ARRAY_ALL,
+            property_value: ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::Real(
+                21.5,
+            )),
+        };
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        ack.encode(&mut writer);
+
+        // skip the leading choice byte, we already know it
+        let buf = &writer.to_bytes()[1..];
+        let mut reader = Reader::new_with_len(buf.len());
+        let service =
+            ComplexAckService::decode(ConfirmedServiceChoice::ReadProperty, &mut reader, buf)
+                .unwrap();
+        assert!(matches!(
+            service.choice(),
+            ConfirmedServiceChoice::ReadProperty
+        ));
+
+        let ComplexAckService::ReadProperty(ack) = service else {
+            panic!("expected a ReadPropertyAck");
+        };
+        assert_eq!(ack.object_id, object_id);
+        assert_eq!(ack.property_id, PropertyId::PropPresentValue);
+        assert!(matches!(
+            ack.property_value,
+            ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::Real(x)) if x == 21.5
+        ));
+    }
+
+    #[test]
+    fn decode_create_object_error_first_failed_element() {
+        // invoke_id 3, CreateObject, [0] { property (2), value_out_of_range (37) }, [1] 2
+        let buf = [0x03, 0x0a, 0x0e, 0x91, 0x02, 0x91, 0x25, 0x0f, 0x19, 0x02];
+        let mut reader = Reader::new_with_len(buf.len());
+        let error = ConfirmedBacnetError::decode(&mut reader, &buf).unwrap();
+
+        assert_eq!(error.invoke_id, 3);
+        assert!(matches!(
+            error.service_choice,
+            ConfirmedServiceChoice::CreateObject
+        ));
+        assert!(matches!(error.error_class, ErrorClass::Property));
+        assert!(matches!(error.error_code, ErrorCode::ValueOutOfRange));
+        assert_eq!(error.first_failed_element, Some(2));
+        assert!(reader.eof());
+    }
+
+    #[test]
+    fn encode_error_round_trip() {
+        let error =
+            |service_choice, first_failed_element, first_failed_write| ConfirmedBacnetError {
+                invoke_id: 3,
+                service_choice,
+                error_class: ErrorClass::Property,
+                error_code: ErrorCode::ValueOutOfRange,
+                first_failed_element,
+                first_failed_write,
+            };
+        let first_failed_write = ObjectPropertyReference::new(
+            ObjectId::new(ObjectType::ObjectAnalogValue, 1),
+            PropertyId::PropPresentValue,
+        );
+        let errors = [
+            error(ConfirmedServiceChoice::CreateObject, Some(2), None),
+            error(ConfirmedServiceChoice::AddListElement, Some(1), None),
+            error(ConfirmedServiceChoice::RemoveListElement, Some(3), None),
+            error(
+                ConfirmedServiceChoice::WritePropMultiple,
+                None,
+                Some(first_failed_write),
+            ),
+            error(ConfirmedServiceChoice::DeleteObject, None, None),
+        ];
+
+        for error in errors {
+            let mut buf = [0; 32];
+            let mut writer = Writer::new(&mut buf);
+            error.encode(&mut writer);
+            let buf = writer.try_to_bytes().unwrap();
+            assert_eq!(buf[0], 0x50);
+
+            let mut reader = Reader::new_with_len(buf.len());
+            reader.index = 1;
+            let decoded = ConfirmedBacnetError::decode(&mut reader, buf).unwrap();
+            assert!(reader.eof());
+            assert_eq!(decoded.invoke_id, error.invoke_id);
+            assert_eq!(decoded.service_choice as u8, error.service_choice as u8);
+            assert_eq!(decoded.error_code, error.error_code);
+            assert_eq!(decoded.first_failed_element, error.first_failed_element);
+            assert_eq!(decoded.first_failed_write, error.first_failed_write);
+        }
+
+        // the exact bytes of a CreateObject error from a device
+        let create_object = [0x03, 0x0a, 0x0e, 0x91, 0x02, 0x91, 0x25, 0x0f, 0x19, 0x02];
+        let mut reader = Reader::new_with_len(create_object.len());
+        let decoded = ConfirmedBacnetError::decode(&mut reader, &create_object).unwrap();
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        decoded.encode(&mut writer);
+        assert_eq!(&writer.try_to_bytes().unwrap()[1..], create_object);
+    }
+
+    #[test]
+    fn encode_error_missing_failed_element_is_error() {
+        for service_choice in [
+            ConfirmedServiceChoice::CreateObject,
+            ConfirmedServiceChoice::WritePropMultiple,
+        ] {
+            let error = ConfirmedBacnetError {
+                invoke_id: 3,
+                service_choice,
+                error_class: ErrorClass::Property,
+                error_code: ErrorCode::ValueOutOfRange,
+                first_failed_element: None,
+                first_failed_write: None,
+            };
+            let mut buf = [0; 32];
+            let mut writer = Writer::new(&mut buf);
+            error.encode(&mut writer);
+            assert!(matches!(writer.try_to_bytes(), Err(Error::InvalidValue(_))));
+        }
+    }
+
+    #[test]
+    fn decode_write_property_multiple_error() {
+        // invoke_id 4, WritePropertyMultiple, [0] { property (2), write_access_denied (40) },
+        // [1] { AnalogValue:1 Present_Value }
+        let buf = [
+            0x04, 0x10, 0x0e, 0x91, 0x02, 0x91, 0x28, 0x0f, 0x1e, 0x0c, 0x00, 0x80, 0x00, 0x01,
+            0x19, 0x55, 0x1f,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let error = ConfirmedBacnetError::decode(&mut reader, &buf).unwrap();
+
+        assert!(matches!(error.error_code, ErrorCode::WriteAccessDenied));
+        assert_eq!(error.first_failed_element, None);
+        assert_eq!(
+            error.first_failed_write,
+            Some(ObjectPropertyReference::new(
+                ObjectId::new(ObjectType::ObjectAnalogValue, 1),
+                PropertyId::PropPresentValue
+            ))
+        );
+        assert!(reader.eof());
+    }
+
+    #[test]
+    fn decode_plain_error_has_no_first_failed_element() {
+        // invoke_id 3, DeleteObject, object (1), unknown_object (31)
+        let buf = [0x03, 0x0b, 0x91, 0x01, 0x91, 0x1f];
+        let mut reader = Reader::new_with_len(buf.len());
+        let error = ConfirmedBacnetError::decode(&mut reader, &buf).unwrap();
+
+        assert!(matches!(error.error_code, ErrorCode::UnknownObject));
+        assert_eq!(error.first_failed_element, None);
+    }
+
+    #[test]
+    fn convert_data_link_to_error() {
+        // Error PDU for ReadProperty: object / unknown-object
+        let buf = [
+            0x81, 0x0a, 0x00, 0x0d, 0x01, 0x00, 0x50, 0x02, 0x0c, 0x91, 0x01, 0x91, 0x1f,
+        ];
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, &buf).unwrap();
+        let error: ConfirmedBacnetError = message.try_into().unwrap();
+        assert_eq!(error.invoke_id, 2);
+        assert!(matches!(
+            error.service_choice,
+            ConfirmedServiceChoice::ReadProperty
+        ));
+        assert_eq!(error.error_class, ErrorClass::Object);
+        assert_eq!(error.error_code, ErrorCode::UnknownObject);
+
+        // a complex ack is not an error
+        let buf = [
+            0x81, 0x0a, 0x00, 0x19, 0x01, 0x00, 0x30, 0x00, 0x0c, 0x0c, 0x02, 0x00, 0x4e, 0x78,
+            0x19, 0x4c, 0x29, 0x01, 0x3e, 0xc4, 0x02, 0x00, 0x4e, 0x78, 0x3f,
+        ];
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, &buf).unwrap();
+        let result: Result<ConfirmedBacnetError, _> = message.try_into();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn convert_data_link_to_simple_ack() {
+        // SimpleAck for WriteProperty with invoke_id 1
+        let buf = [0x81, 0x0a, 0x00, 0x09, 0x01, 0x00, 0x20, 0x01, 0x0f];
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, &buf).unwrap();
+        let ack: SimpleAck = message.try_into().unwrap();
+        assert_eq!(ack.invoke_id, 1);
+        assert!(matches!(
+            ack.service_choice,
+            ConfirmedServiceChoice::WriteProperty
+        ));
+
+        let mut out = [0; 3];
+        let mut writer = Writer::new(&mut out);
+        ack.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &buf[6..]);
+
+        // an error is not a simple ack
+        let buf = [
+            0x81, 0x0a, 0x00, 0x0d, 0x01, 0x00, 0x50, 0x01, 0x0f, 0x91, 0x02, 0x91, 0x28,
+        ];
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, &buf).unwrap();
+        let result: Result<SimpleAck, _> = message.try_into();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn decode_reject() {
+        // Reject for invoke_id 3: unrecognized-service
+        let buf = [0x81, 0x0a, 0x00, 0x09, 0x01, 0x00, 0x60, 0x03, 0x09];
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, &buf).unwrap();
+        let reject: Reject = message.try_into().unwrap();
+        assert_eq!(reject.invoke_id, 3);
+        assert_eq!(reject.reason, RejectReason::UnrecognizedService);
+
+        let mut out = [0; 3];
+        let mut writer = Writer::new(&mut out);
+        reject.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &buf[6..]);
+
+        assert_eq!(RejectReason::from(70), RejectReason::Proprietary(70));
+        assert_eq!(RejectReason::from(11), RejectReason::Reserved(11));
+    }
+
+    #[test]
+    fn segment_ack_encode_decode() {
+        // negative ack from the client: segments after 5 of invoke_id 9 are missing, window size 4
+        let frame = [0x81, 0x0a, 0x00, 0x0a, 0x01, 0x00, 0x42, 0x09, 0x05, 0x04];
+        let ack = SegmentAck {
+            negative_ack: true,
+            server: false,
+            invoke_id: 9,
+            sequence_num: 5,
+            actual_window_size: 4,
+        };
+        let mut out = [0; 4];
+        let mut writer = Writer::new(&mut out);
+        ack.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &frame[6..]);
+
+        // positive ack from the server
+        let ack = SegmentAck {
+            negative_ack: false,
+            server: true,
+            ..ack
+        };
+        let mut out = [0; 4];
+        let mut writer = Writer::new(&mut out);
+        ack.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), [0x41, 0x09, 0x05, 0x04]);
+
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, &frame).unwrap();
+        let ack: SegmentAck = message.try_into().unwrap();
+        assert!(ack.negative_ack);
+        assert!(!ack.server);
+        assert_eq!(ack.invoke_id, 9);
+        assert_eq!(ack.sequence_num, 5);
+        assert_eq!(ack.actual_window_size, 4);
+    }
+}
@@ -1,46 +1,78 @@
 use core::{fmt::Display, str::from_utf8};
 
-use crate::common::{
-    daily_schedule::WeeklySchedule,
-    error::Error,
-    helper::{decode_unsigned, encode_application_enumerated},
-    io::{Reader, Writer},
-    object_id::{ObjectId, ObjectType},
-    property_id::PropertyId,
-    spec::{
-        Binary, EngineeringUnits, EventState, LogBufferResult, LoggingType, NotifyType, Status,
+use crate::{
+    application_protocol::primitives::time_stamp::TimeStamp,
+    common::{
+        daily_schedule::WeeklySchedule,
+        error::Error,
+        helper::{
+            decode_signed, decode_unsigned, encode_application_enumerated,
+            encode_application_signed, encode_application_unsigned, get_tagged_body_for_tag,
+        },
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+        property_id::PropertyId,
+        spec::{
+            Binary, EngineeringUnits, EventState, EventTransitionBits, LogBufferResult,
+            LoggingType, NotifyType, Status,
+        },
+        tag::{ApplicationTagNumber, Tag, TagNumber},
     },
-    tag::{ApplicationTagNumber, Tag, TagNumber},
 };
 
 #[cfg(feature = "alloc")]
 use {
-    crate::common::spooky::Phantom,
+    crate::common::{spooky::Phantom, time_value::TimeValue},
     alloc::{string::String, vec::Vec},
 };
 
+#[cfg(not(feature = "alloc"))]
+use crate::common::daily_schedule::TimeValueList;
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ApplicationDataValue<'a> {
+    Null, // e.g. a relinquished slot in a priority array
     Boolean(bool),
     Real(f32),
     Double(f64),
     Date(Date),
     Time(Time),
     ObjectId(ObjectId),
+    #[cfg_attr(all(feature = "serde", not(feature = "alloc")), serde(borrow))]
     CharacterString(CharacterString<'a>),
+    #[cfg_attr(all(feature = "serde", not(feature = "alloc")), serde(borrow))]
+    OctetString(OctetString<'a>),
     Enumerated(Enumerated),
+    #[cfg_attr(all(feature = "serde", not(feature = "alloc")), serde(borrow))]
     BitString(BitString<'a>),
     UnsignedInt(u32),
+    SignedInt(i32),
+    // without alloc the schedule borrows the decode buffer so it can only be serialized
+    #[cfg_attr(
+        all(feature = "serde", not(feature = "alloc")),
+        serde(skip_deserializing)
+    )]
     WeeklySchedule(WeeklySchedule<'a>),
+    EventTimeStamps([TimeStamp; 3]),
+    // a single element of an array property, e.g. Event_Time_Stamps[1] or Weekly_Schedule[1]
+    TimeStamp(TimeStamp),
+    #[cfg(not(feature = "alloc"))]
+    #[cfg_attr(feature = "serde", serde(skip_deserializing))]
+    DailySchedule(TimeValueList<'a>),
+    #[cfg(feature = "alloc")]
+    DailySchedule(Vec<TimeValue>),
 }
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ApplicationDataValueWrite<'a> {
+    Null, // relinquishes the command at the priority written to
     Boolean(bool),
     Enumerated(Enumerated),
     Real(f32),
+    CharacterString(CharacterString<'a>), // e.g. renaming an object
     WeeklySchedule(WeeklySchedule<'a>),
 }
 
@@ -57,22 +89,61 @@ pub enum Enumerated {
     Unknown(u32),
 }
 
+impl From<EngineeringUnits> for Enumerated {
+    fn from(value: EngineeringUnits) -> Self {
+        Self::Units(value)
+    }
+}
+
+impl From<Binary> for Enumerated {
+    fn from(value: Binary) -> Self {
+        Self::Binary(value)
+    }
+}
+
+impl From<ObjectType> for Enumerated {
+    fn from(value: ObjectType) -> Self {
+        Self::ObjectType(value)
+    }
+}
+
+impl From<EventState> for Enumerated {
+    fn from(value: EventState) -> Self {
+        Self::EventState(value)
+    }
+}
+
+impl From<NotifyType> for Enumerated {
+    fn from(value: NotifyType) -> Self {
+        Self::NotifyType(value)
+    }
+}
+
+impl From<LoggingType> for Enumerated {
+    fn from(value: LoggingType) -> Self {
+        Self::LoggingType(value)
+    }
+}
+
 impl Enumerated {
-    pub fn encode(&self, writer: &mut Writer) {
-        let value = match self {
+    pub fn as_u32(&self) -> u32 {
+        match self {
             Self::Units(x) => x.clone() as u32,
             Self::Binary(x) => x.clone() as u32,
-            Self::ObjectType(x) => *x as u32,
+            Self::ObjectType(x) => u32::from(*x),
             Self::EventState(x) => x.clone() as u32,
             Self::NotifyType(x) => x.clone() as u32,
             Self::LoggingType(x) => x.clone() as u32,
             Self::Unknown(x) => *x,
-        };
-        encode_application_enumerated(writer, value);
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_enumerated(writer, self.as_u32());
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Date {
@@ -85,6 +156,52 @@ pub struct Date {
 impl Date {
     pub const LEN: u32 = 4; // 4 bytes
 
+    // 0xFF in any field means unspecified (any) e.g. in a schedule or calendar entry
+    pub const WILDCARD: u8 = 0xFF;
+    pub const WILDCARD_YEAR: u16 = 1900 + Self::WILDCARD as u16;
+    pub const ODD_MONTHS: u8 = 13;
+    pub const EVEN_MONTHS: u8 = 14;
+    pub const LAST_DAY_OF_MONTH: u8 = 32;
+
+    // matches every date
+    pub const fn any() -> Self {
+        Self {
+            year: Self::WILDCARD_YEAR,
+            month: Self::WILDCARD,
+            day: Self::WILDCARD,
+            wday: Self::WILDCARD,
+        }
+    }
+
+    // true if any field is unspecified or a special value (so this is a pattern and not a single date)
+    pub fn is_wildcard(&self) -> bool {
+        self.year == Self::WILDCARD_YEAR
+            || self.month == Self::WILDCARD
+            || self.month == Self::ODD_MONTHS
+            || self.month == Self::EVEN_MONTHS
+            || self.day == Self::WILDCARD
+            || self.day == Self::LAST_DAY_OF_MONTH
+            || self.wday == Self::WILDCARD
+    }
+
+    // checks if a specific date (e.g. today) falls within this date pattern
+    pub fn matches(&self, date: &Date) -> bool {
+        let year = self.year == Self::WILDCARD_YEAR || self.year == date.year;
+        let month = match self.month {
+            Self::WILDCARD => true,
+            Self::ODD_MONTHS => date.month % 2 == 1,
+            Self::EVEN_MONTHS => date.month.is_multiple_of(2),
+            x => x == date.month,
+        };
+        let day = match self.day {
+            Self::WILDCARD => true,
+            Self::LAST_DAY_OF_MONTH => date.day == days_in_month(date.year, date.month),
+            x => x == date.day,
+        };
+        let wday = self.wday == Self::WILDCARD || self.wday == date.wday;
+        year && month && day && wday
+    }
+
     //  year = years since 1900, wildcard=1900+255
     //  month 1=Jan
     //  day = day of month
@@ -120,9 +237,29 @@ impl Date {
         writer.push(self.day);
         writer.push(self.wday);
     }
+
+    pub fn encode_application(&self, writer: &mut Writer) {
+        Tag::new(
+            TagNumber::Application(ApplicationTagNumber::Date),
+            Self::LEN,
+        )
+        .encode(writer);
+        self.encode(writer);
+    }
 }
 
-#[derive(Debug, Clone)]
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Time {
@@ -135,6 +272,36 @@ pub struct Time {
 impl Time {
     pub const LEN: u32 = 4; // 4 bytes
 
+    // 0xFF in any field means unspecified (any)
+    pub const WILDCARD: u8 = 0xFF;
+
+    // matches every time
+    pub const fn any() -> Self {
+        Self {
+            hour: Self::WILDCARD,
+            minute: Self::WILDCARD,
+            second: Self::WILDCARD,
+            hundredths: Self::WILDCARD,
+        }
+    }
+
+    // true if any field is unspecified
+    pub fn is_wildcard(&self) -> bool {
+        self.hour == Self::WILDCARD
+            || self.minute == Self::WILDCARD
+            || self.second == Self::WILDCARD
+            || self.hundredths == Self::WILDCARD
+    }
+
+    // checks if a specific time falls within this time pattern
+    pub fn matches(&self, time: &Time) -> bool {
+        let field = |pattern: u8, value: u8| pattern == Self::WILDCARD || pattern == value;
+        field(self.hour, time.hour)
+            && field(self.minute, time.minute)
+            && field(self.second, time.second)
+            && field(self.hundredths, time.hundredths)
+    }
+
     // assuming that this comes from a Time tag
     pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
         let hour = reader.read_byte(buf)?;
@@ -155,6 +322,57 @@ impl Time {
         writer.push(self.second);
         writer.push(self.hundredths);
     }
+
+    pub fn encode_application(&self, writer: &mut Writer) {
+        Tag::new(
+            TagNumber::Application(ApplicationTagNumber::Time),
+            Self::LEN,
+        )
+        .encode(writer);
+        self.encode(writer);
+    }
+}
+
+// BACnetDateTime (an application tagged date followed by an application tagged time)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+}
+
+impl DateTime {
+    pub fn new(date: Date, time: Time) -> Self {
+        Self { date, time }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        self.date.encode_application(writer);
+        self.time.encode_application(writer);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Date),
+            "DateTime decode date",
+        )?;
+        tag.expect_value("DateTime decode date", Date::LEN)?;
+        let date = Date::decode(reader, buf)?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Time),
+            "DateTime decode time",
+        )?;
+        tag.expect_value("DateTime decode time", Time::LEN)?;
+        let time = Time::decode(reader, buf)?;
+
+        Ok(Self { date, time })
+    }
 }
 
 #[cfg(not(feature = "alloc"))]
@@ -175,6 +393,24 @@ pub struct CharacterString<'a> {
     _phantom: &'a Phantom,
 }
 
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OctetString<'a> {
+    pub inner: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OctetString<'a> {
+    pub inner: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    _phantom: &'a Phantom,
+}
+
 impl<'a> Display for ApplicationDataValue<'a> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
@@ -188,22 +424,19 @@ impl<'a> Display for ApplicationDataValue<'a> {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BitString<'a> {
     Status(Status),
     LogBufferResult(LogBufferResult),
+    #[cfg_attr(all(feature = "serde", not(feature = "alloc")), serde(borrow))]
     Custom(CustomBitStream<'a>),
 }
 
-#[cfg(feature = "defmt")]
-impl<'a> defmt::Format for BitString<'a> {
-    fn format(&self, _fmt: defmt::Formatter) {
-        // do nothing for now because it is too complicated due to StatusFlags
-    }
-}
-
 #[cfg(not(feature = "alloc"))]
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CustomBitStream<'a> {
     pub unused_bits: u8,
     pub bits: &'a [u8],
@@ -212,9 +445,11 @@ pub struct CustomBitStream<'a> {
 #[cfg(feature = "alloc")]
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CustomBitStream<'a> {
     pub unused_bits: u8,
     pub bits: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
     _phantom: &'a Phantom,
 }
 
@@ -234,19 +469,158 @@ impl<'a> CustomBitStream<'a> {
             _phantom: &PHANTOM,
         }
     }
+
+    // the number of meaningful bits (trailing unused bits in the last octet are excluded)
+    pub fn bit_len(&self) -> usize {
+        (self.bits.len() * 8).saturating_sub(self.unused_bits as usize)
+    }
+
+    // bit 0 is the most significant bit of the first octet
+    pub fn is_set(&self, index: usize) -> bool {
+        if index >= self.bit_len() {
+            return false;
+        }
+        self.bits[index / 8] & (0x80 >> (index % 8)) != 0
+    }
+
+    // indices of all the set bits, ignoring any set bits in the unused trailing portion
+    pub fn iter_set_bits(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.bit_len()).filter(move |i| self.is_set(*i))
+    }
+}
+
+// builds a bit string one bit at a time (e.g. status flags in a server response)
+// bit 0 is the most significant bit of the first octet
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BitStringBuilder {
+    bytes: [u8; BitStringBuilder::MAX_LEN],
+    bit_len: usize,
+}
+
+impl BitStringBuilder {
+    pub const MAX_LEN: usize = 32; // octets
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_bits(bits: &[bool]) -> Result<Self, Error> {
+        let mut builder = Self::new();
+        for bit in bits {
+            builder.push(*bit)?;
+        }
+        Ok(builder)
+    }
+
+    pub fn push(&mut self, bit: bool) -> Result<(), Error> {
+        if self.bit_len == Self::MAX_LEN * 8 {
+            return Err(Error::Length((
+                "bit string builder is full",
+                self.bit_len as u32,
+            )));
+        }
+        if bit {
+            self.bytes[self.bit_len / 8] |= 0x80 >> (self.bit_len % 8);
+        }
+        self.bit_len += 1;
+        Ok(())
+    }
+
+    pub fn bit_len(&self) -> usize {
+        self.bit_len
+    }
+
+    pub fn unused_bits(&self) -> u8 {
+        ((8 - self.bit_len % 8) % 8) as u8
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes[..self.bit_len.div_ceil(8)]
+    }
+
+    pub fn build(&self) -> BitString<'_> {
+        BitString::Custom(CustomBitStream::new(self.unused_bits(), self.bytes()))
+    }
+}
+
+// the 3 event transition bits (to-offnormal, to-fault and to-normal)
+impl From<&EventTransitionBits> for BitStringBuilder {
+    fn from(value: &EventTransitionBits) -> Self {
+        let mut builder = Self::new();
+        builder.bytes[0] = value.inner & 0b1110_0000;
+        builder.bit_len = 3;
+        builder
+    }
+}
+
+impl<'a> From<Status> for BitString<'a> {
+    fn from(value: Status) -> Self {
+        Self::Status(value)
+    }
 }
 
 impl<'a> BitString<'a> {
+    // interprets the bits as Status_Flags
+    // values decoded without knowing the property (e.g. in a COV notification) are Custom bits
+    pub fn status_flags(&self) -> Option<Status> {
+        match self {
+            Self::Status(x) => Some(x.clone()),
+            Self::Custom(x) if x.bit_len() == 4 => Some(Status::new(x.bits[0] & 0xf0)),
+            _ => None,
+        }
+    }
+
+    // the number of meaningful bits e.g. 4 for status flags
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Status(_) => 4,
+            Self::LogBufferResult(_) => 3,
+            Self::Custom(x) => x.bit_len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // bit 0 is the most significant bit of the first octet (e.g. in-alarm for status flags)
+    // bits past the end are never set
+    pub fn get(&self, bit: usize) -> bool {
+        match self {
+            Self::Status(x) => bit < self.len() && x.inner & (0x80 >> bit) != 0,
+            Self::LogBufferResult(x) => bit < self.len() && x.inner & (0x80 >> bit) != 0,
+            Self::Custom(x) => x.is_set(bit),
+        }
+    }
+
+    // indices of all the set bits
+    pub fn iter_set_bits(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len()).filter(move |i| self.get(*i))
+    }
+
+    // interprets the bits as a BACnetEventTransitionBits (e.g. Acked_Transitions or Event_Enable)
+    pub fn event_transition_bits(&self) -> Result<EventTransitionBits, Error> {
+        if self.len() != 3 {
+            return Err(Error::Length((
+                "event transition bits should be a bit string of 3 bits",
+                self.len() as u32,
+            )));
+        }
+        let inner = self.iter_set_bits().fold(0, |acc, bit| acc | (0x80 >> bit));
+        Ok(EventTransitionBits::new(inner))
+    }
+
     pub fn encode_application(&self, writer: &mut Writer) {
         match self {
             Self::Status(x) => {
                 Tag::new(TagNumber::Application(ApplicationTagNumber::BitString), 2).encode(writer);
-                writer.push(0); // no unused bits
+                writer.push(4); // 4 status flags
                 writer.push(x.inner);
             }
             Self::LogBufferResult(x) => {
                 Tag::new(TagNumber::Application(ApplicationTagNumber::BitString), 2).encode(writer);
-                writer.push(0); // no unused bits
+                writer.push(5); // 3 log buffer result flags
                 writer.push(x.inner);
             }
             Self::Custom(x) => {
@@ -255,8 +629,11 @@ impl<'a> BitString<'a> {
                     x.bits.len() as u32 + 1,
                 )
                 .encode(writer);
-                writer.push(0); // no unused bits
+                writer.push(x.unused_bits);
+                #[cfg(feature = "alloc")]
                 writer.extend_from_slice(&x.bits);
+                #[cfg(not(feature = "alloc"))]
+                writer.extend_from_slice(x.bits);
             }
         }
     }
@@ -265,19 +642,22 @@ impl<'a> BitString<'a> {
         match self {
             Self::Status(x) => {
                 Tag::new(TagNumber::ContextSpecific(tag_num), 2).encode(writer);
-                writer.push(0); // no unused bits
+                writer.push(4); // 4 status flags
                 writer.push(x.inner);
             }
             Self::LogBufferResult(x) => {
                 Tag::new(TagNumber::ContextSpecific(tag_num), 2).encode(writer);
-                writer.push(0); // no unused bits
+                writer.push(5); // 3 log buffer result flags
                 writer.push(x.inner);
             }
             Self::Custom(x) => {
                 Tag::new(TagNumber::ContextSpecific(tag_num), x.bits.len() as u32 + 1)
                     .encode(writer);
-                writer.push(0); // no unused bits
+                writer.push(x.unused_bits);
+                #[cfg(feature = "alloc")]
                 writer.extend_from_slice(&x.bits);
+                #[cfg(not(feature = "alloc"))]
+                writer.extend_from_slice(x.bits);
             }
         }
     }
@@ -289,23 +669,54 @@ impl<'a> BitString<'a> {
         reader: &mut Reader,
         buf: &'a [u8],
     ) -> Result<Self, Error> {
-        let unused_bits = reader.read_byte(buf)?;
+        // only a single octet of bits can be interpreted as flags, anything else is kept as is
         match property_id {
-            PropertyId::PropStatusFlags => {
+            PropertyId::PropStatusFlags if len == 2 => {
+                Self::decode_unused_bits(len, reader, buf)?;
                 let status_flags = Status::new(reader.read_byte(buf)?);
                 Ok(Self::Status(status_flags))
             }
-            PropertyId::PropLogBuffer => {
+            PropertyId::PropLogBuffer if len == 2 => {
+                Self::decode_unused_bits(len, reader, buf)?;
                 let flags = LogBufferResult::new(reader.read_byte(buf)?);
                 Ok(Self::LogBufferResult(flags))
             }
-            _ => {
-                let len = (len - 1) as usize; // we have already read a byte
-                let bits = reader.read_slice(len, buf)?;
-                Ok(Self::Custom(CustomBitStream::new(unused_bits, bits)))
-            }
+            _ => Self::decode_uninterpreted(len, reader, buf),
+        }
+    }
+
+    // decodes the bits purely from the tag length without a property to interpret them
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode_uninterpreted(
+        len: u32,
+        reader: &mut Reader,
+        buf: &'a [u8],
+    ) -> Result<Self, Error> {
+        let unused_bits = Self::decode_unused_bits(len, reader, buf)?;
+        let len = (len - 1) as usize; // we have already read a byte
+        let bits = reader.read_slice(len, buf)?;
+        Ok(Self::Custom(CustomBitStream::new(unused_bits, bits)))
+    }
+
+    // the status flags of a notification where there is no property to interpret the bits
+    pub fn decode_status_flags(len: u32, reader: &mut Reader, buf: &[u8]) -> Result<Status, Error> {
+        match BitString::decode(&PropertyId::PropStatusFlags, len, reader, buf)? {
+            BitString::Status(status_flags) => Ok(status_flags),
+            _ => Err(Error::InvalidValue("status flags must be a single octet")),
         }
     }
+
+    // the first content octet is the number of unused bits in the last octet
+    fn decode_unused_bits(len: u32, reader: &mut Reader, buf: &[u8]) -> Result<u8, Error> {
+        if len == 0 {
+            return Err(Error::InvalidValue("bit string length must be at least 1"));
+        }
+        let unused_bits = reader.read_byte(buf)?;
+        if unused_bits > 7 || (len == 1 && unused_bits != 0) {
+            return Err(Error::InvalidValue("bit string unused bits out of range"));
+        }
+        Ok(unused_bits)
+    }
 }
 
 impl<'a> CharacterString<'a> {
@@ -324,11 +735,28 @@ impl<'a> CharacterString<'a> {
         }
     }
 
+    #[cfg(not(feature = "alloc"))]
+    pub fn as_str(&self) -> &'a str {
+        self.inner
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn as_str(&self) -> &str {
+        &self.inner
+    }
+
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
     pub fn decode(len: u32, reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        if len == 0 {
+            return Err(Error::InvalidValue(
+                "CharacterString length must include the character set",
+            ));
+        }
         let character_set = reader.read_byte(buf)?;
         if character_set != 0 {
-            unimplemented!("non-utf8 characterset not supported")
+            return Err(Error::InvalidValue(
+                "non-utf8 CharacterString character set not supported",
+            ));
         }
         let slice = reader.read_slice(len as usize - 1, buf)?;
         let inner = from_utf8(slice).map_err(|_| {
@@ -337,6 +765,61 @@ impl<'a> CharacterString<'a> {
 
         Ok(CharacterString::new(inner))
     }
+
+    pub fn encode_application(&self, writer: &mut Writer) {
+        self.encode_with_tag(
+            TagNumber::Application(ApplicationTagNumber::CharacterString),
+            writer,
+        );
+    }
+
+    pub fn encode_context(&self, tag_num: u8, writer: &mut Writer) {
+        self.encode_with_tag(TagNumber::ContextSpecific(tag_num), writer);
+    }
+
+    fn encode_with_tag(&self, tag_number: TagNumber, writer: &mut Writer) {
+        let utf8_encoded = self.inner.as_bytes(); // strings in rust are utf8 encoded already
+        Tag::new(
+            tag_number,
+            utf8_encoded.len() as u32 + 1, // keep space for encoding byte
+        )
+        .encode(writer);
+        writer.push(0); // utf8 encoding
+        writer.extend_from_slice(utf8_encoded);
+    }
+}
+
+impl<'a> OctetString<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(inner: &'a [u8]) -> Self {
+        Self { inner }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(inner: &[u8]) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            inner: inner.into(),
+            _phantom: &PHANTOM,
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.inner
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.inner
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(len: u32, reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let inner = reader.read_slice(len as usize, buf)?;
+        Ok(OctetString::new(inner))
+    }
 }
 
 impl<'a> ApplicationDataValueWrite<'a> {
@@ -355,6 +838,7 @@ impl<'a> ApplicationDataValueWrite<'a> {
             _ => {
                 let tag = Tag::decode(reader, buf)?;
                 match tag.number {
+                    TagNumber::Application(ApplicationTagNumber::Null) => Ok(Self::Null),
                     TagNumber::Application(ApplicationTagNumber::Boolean) => {
                         Ok(Self::Boolean(tag.value > 0))
                     }
@@ -372,6 +856,10 @@ impl<'a> ApplicationDataValueWrite<'a> {
                         let value = decode_enumerated(object_id, property_id, &tag, reader, buf)?;
                         Ok(Self::Enumerated(value))
                     }
+                    TagNumber::Application(ApplicationTagNumber::CharacterString) => {
+                        let value = CharacterString::decode(tag.value, reader, buf)?;
+                        Ok(Self::CharacterString(value))
+                    }
                     tag_number => Err(Error::TagNotSupported((
                         "ApplicationDataValueWrite decode",
                         tag_number,
@@ -383,12 +871,14 @@ impl<'a> ApplicationDataValueWrite<'a> {
 
     pub fn encode(&self, writer: &mut Writer) {
         match self {
+            Self::Null => {
+                Tag::new(TagNumber::Application(ApplicationTagNumber::Null), 0).encode(writer);
+            }
             Self::Boolean(x) => {
-                let len = 1;
-                let tag = Tag::new(TagNumber::Application(ApplicationTagNumber::Boolean), len);
+                // an application tagged boolean has no content, the value is the tag's length field
+                let value = if *x { 1 } else { 0 };
+                let tag = Tag::new(TagNumber::Application(ApplicationTagNumber::Boolean), value);
                 tag.encode(writer);
-                let value = if *x { 1_u8 } else { 0_u8 };
-                writer.push(value)
             }
             Self::Real(x) => {
                 let len = 4;
@@ -399,14 +889,55 @@ impl<'a> ApplicationDataValueWrite<'a> {
             Self::Enumerated(x) => {
                 x.encode(writer);
             }
+            Self::CharacterString(x) => x.encode_application(writer),
             Self::WeeklySchedule(x) => x.encode(writer),
         }
     }
 }
 
 impl<'a> ApplicationDataValue<'a> {
+    // only returns a value for character strings
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::CharacterString(x) => Some(x.as_str()),
+            _ => None,
+        }
+    }
+
+    // only returns a value for reals
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            Self::Real(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    // coerces any numeric value to f32 because some devices return integers where a real is expected
+    // large integers and doubles may lose precision
+    pub fn as_number_f32(&self) -> Option<f32> {
+        match self {
+            Self::Real(x) => Some(*x),
+            Self::Double(x) => Some(*x as f32),
+            Self::UnsignedInt(x) => Some(*x as f32),
+            Self::SignedInt(x) => Some(*x as f32),
+            Self::Enumerated(x) => Some(x.as_u32() as f32),
+            _ => None,
+        }
+    }
+
+    // only returns a value for octet strings
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::OctetString(x) => Some(x.as_bytes()),
+            _ => None,
+        }
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
         match self {
+            ApplicationDataValue::Null => {
+                Tag::new(TagNumber::Application(ApplicationTagNumber::Null), 0).encode(writer)
+            }
             ApplicationDataValue::Boolean(x) => Tag::new(
                 TagNumber::Application(ApplicationTagNumber::Boolean),
                 if *x { 1 } else { 0 },
@@ -416,22 +947,12 @@ impl<'a> ApplicationDataValue<'a> {
                 Tag::new(TagNumber::Application(ApplicationTagNumber::Real), 4).encode(writer);
                 writer.extend_from_slice(&x.to_be_bytes());
             }
-            ApplicationDataValue::Date(x) => {
-                Tag::new(
-                    TagNumber::Application(ApplicationTagNumber::Date),
-                    Date::LEN,
-                )
-                .encode(writer);
-                x.encode(writer);
-            }
-            ApplicationDataValue::Time(x) => {
-                Tag::new(
-                    TagNumber::Application(ApplicationTagNumber::Time),
-                    Time::LEN,
-                )
-                .encode(writer);
-                x.encode(writer);
+            ApplicationDataValue::Double(x) => {
+                Tag::new(TagNumber::Application(ApplicationTagNumber::Double), 8).encode(writer);
+                writer.extend_from_slice(&x.to_be_bytes());
             }
+            ApplicationDataValue::Date(x) => x.encode_application(writer),
+            ApplicationDataValue::Time(x) => x.encode_application(writer),
             ApplicationDataValue::ObjectId(x) => {
                 Tag::new(
                     TagNumber::Application(ApplicationTagNumber::ObjectId),
@@ -440,15 +961,14 @@ impl<'a> ApplicationDataValue<'a> {
                 .encode(writer);
                 x.encode(writer);
             }
-            ApplicationDataValue::CharacterString(x) => {
-                let utf8_encoded = x.inner.as_bytes(); // strings in rust are utf8 encoded already
+            ApplicationDataValue::CharacterString(x) => x.encode_application(writer),
+            ApplicationDataValue::OctetString(x) => {
                 Tag::new(
-                    TagNumber::Application(ApplicationTagNumber::CharacterString),
-                    utf8_encoded.len() as u32 + 1, // keep space for encoding byte
+                    TagNumber::Application(ApplicationTagNumber::OctetString),
+                    x.inner.len() as u32,
                 )
                 .encode(writer);
-                writer.push(0); // utf8 encoding
-                writer.extend_from_slice(utf8_encoded);
+                writer.extend_from_slice(x.as_bytes());
             }
             ApplicationDataValue::Enumerated(x) => {
                 x.encode(writer);
@@ -457,19 +977,64 @@ impl<'a> ApplicationDataValue<'a> {
                 x.encode_application(writer);
             }
             ApplicationDataValue::UnsignedInt(x) => {
-                Tag::new(TagNumber::Application(ApplicationTagNumber::UnsignedInt), 4)
-                    .encode(writer);
-                writer.extend_from_slice(&x.to_be_bytes());
+                encode_application_unsigned(writer, *x as u64);
+            }
+            ApplicationDataValue::SignedInt(x) => {
+                encode_application_signed(writer, *x);
             }
             ApplicationDataValue::WeeklySchedule(x) => {
                 // no application tag required for weekly schedule
                 x.encode(writer);
             }
-
-            x => todo!("{:?}", x),
+            ApplicationDataValue::EventTimeStamps(x) => {
+                // each time stamp is context tagged
+                for time_stamp in x.iter() {
+                    time_stamp.encode(writer);
+                }
+            }
+            ApplicationDataValue::TimeStamp(x) => x.encode(writer),
+            ApplicationDataValue::DailySchedule(x) => WeeklySchedule::encode_day(writer, x),
         };
     }
 
+    // strips the context opening and closing tags wrapping a single value (e.g. tag 2 in a
+    // CovNotification property value) and decodes the value inside
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode_wrapped(
+        expected_tag_number: u8,
+        object_id: &ObjectId,
+        property_id: &PropertyId,
+        reader: &mut Reader,
+        buf: &'a [u8],
+    ) -> Result<Self, Error> {
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            expected_tag_number,
+            "ApplicationDataValue decode_wrapped",
+        )?;
+        let mut inner_reader = Reader::new_with_len(inner_buf.len());
+        let value = Self::decode_inner(object_id, property_id, &mut inner_reader, inner_buf)?;
+        if !inner_reader.eof() {
+            return Err(Error::InvalidValue(
+                "ApplicationDataValue unexpected data after the wrapped value",
+            ));
+        }
+        Ok(value)
+    }
+
+    // decodes an application tagged value from a body whose context wrapper has already been stripped
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode_inner(
+        object_id: &ObjectId,
+        property_id: &PropertyId,
+        reader: &mut Reader,
+        buf: &'a [u8],
+    ) -> Result<Self, Error> {
+        let tag = Tag::decode(reader, buf)?;
+        Self::decode(&tag, object_id, property_id, reader, buf)
+    }
+
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
     pub fn decode(
         tag: &Tag,
@@ -489,6 +1054,7 @@ impl<'a> ApplicationDataValue<'a> {
         };
 
         match tag_num {
+            ApplicationTagNumber::Null => Ok(ApplicationDataValue::Null),
             ApplicationTagNumber::Real => {
                 if tag.value != 4 {
                     return Err(Error::Length((
@@ -520,6 +1086,10 @@ impl<'a> ApplicationDataValue<'a> {
                 let text = CharacterString::decode(tag.value, reader, buf)?;
                 Ok(ApplicationDataValue::CharacterString(text))
             }
+            ApplicationTagNumber::OctetString => {
+                let bytes = OctetString::decode(tag.value, reader, buf)?;
+                Ok(ApplicationDataValue::OctetString(bytes))
+            }
             ApplicationTagNumber::Enumerated => {
                 let value = decode_enumerated(object_id, property_id, tag, reader, buf)?;
                 Ok(ApplicationDataValue::Enumerated(value))
@@ -536,6 +1106,10 @@ impl<'a> ApplicationDataValue<'a> {
                 let value = decode_unsigned(tag.value, reader, buf)? as u32;
                 Ok(ApplicationDataValue::UnsignedInt(value))
             }
+            ApplicationTagNumber::SignedInt => {
+                let value = decode_signed(tag.value, reader, buf)?;
+                Ok(ApplicationDataValue::SignedInt(value))
+            }
             ApplicationTagNumber::Time => {
                 if tag.value != 4 {
                     return Err(Error::Length((
@@ -610,3 +1184,503 @@ fn decode_enumerated(
         _ => Ok(Enumerated::Unknown(value)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        error::Error,
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+        property_id::PropertyId,
+        spec::Status,
+        tag::Tag,
+    };
+
+    use super::{
+        ApplicationDataValue, BitString, BitStringBuilder, CharacterString, Date, DateTime,
+        Enumerated, OctetString, Time,
+    };
+
+    #[test]
+    fn bit_string_invalid_unused_bits() {
+        let buf = [9, 0xff];
+        let mut reader = Reader::new_with_len(buf.len());
+        let result = BitString::decode(&PropertyId::PropEventEnable, 2, &mut reader, &buf);
+        assert!(matches!(result, Err(Error::InvalidValue(_))));
+    }
+
+    #[test]
+    fn bit_string_zero_length() {
+        let buf = [0];
+        let mut reader = Reader::new_with_len(buf.len());
+        let result = BitString::decode(&PropertyId::PropEventEnable, 0, &mut reader, &buf);
+        assert!(matches!(result, Err(Error::InvalidValue(_))));
+    }
+
+    #[test]
+    fn bit_string_unused_bits_masked() {
+        // 3 bits long, the trailing unused bits are set and should be ignored
+        let buf = [5, 0b1011_1111];
+        let mut reader = Reader::new_with_len(buf.len());
+        let bit_string =
+            BitString::decode(&PropertyId::PropEventEnable, 2, &mut reader, &buf).unwrap();
+        let BitString::Custom(bits) = bit_string else {
+            panic!("expected a custom bit string");
+        };
+        assert_eq!(bits.bit_len(), 3);
+        let mut set = bits.iter_set_bits();
+        assert_eq!(set.next(), Some(0));
+        assert_eq!(set.next(), Some(2));
+        assert_eq!(set.next(), None);
+    }
+
+    #[test]
+    fn bit_string_builder_status_flags() {
+        // in_alarm and overridden set
+        let builder = BitStringBuilder::from_bits(&[true, false, true, false]).unwrap();
+        assert_eq!(builder.unused_bits(), 4);
+        assert_eq!(builder.bytes(), &[0b1010_0000]);
+
+        let mut buf = [0; 8];
+        let mut writer = Writer::new(&mut buf);
+        builder.build().encode_application(&mut writer);
+        let buf = writer.to_bytes();
+        assert_eq!(buf, &[0x82, 4, 0b1010_0000]);
+
+        let mut reader = Reader::new_with_len(buf.len());
+        let tag = Tag::decode(&mut reader, buf).unwrap();
+        let bit_string =
+            BitString::decode(&PropertyId::PropStatusFlags, tag.value, &mut reader, buf).unwrap();
+        let BitString::Status(status) = bit_string else {
+            panic!("expected status flags");
+        };
+        assert!(status.in_alarm());
+        assert!(!status.fault());
+        assert!(status.overridden());
+        assert!(!status.out_of_service());
+    }
+
+    #[test]
+    fn bit_string_accessors() {
+        // fault and out_of_service set, the unused low bits are ignored
+        let status = BitString::Status(Status::new(0b0101_1111));
+        assert_eq!(status.len(), 4);
+        assert!(!status.get(0));
+        assert!(status.get(1));
+        assert!(status.get(3));
+        assert!(!status.get(4));
+        assert!(status.iter_set_bits().eq([1, 3]));
+
+        let builder = BitStringBuilder::from_bits(&[false; 9]).unwrap();
+        let custom = builder.build();
+        assert_eq!(custom.len(), 9);
+        assert_eq!(custom.iter_set_bits().count(), 0);
+
+        let mut builder = BitStringBuilder::new();
+        for i in 0..10 {
+            builder.push(i % 3 == 0).unwrap();
+        }
+        let custom = builder.build();
+        assert!(custom.get(9));
+        assert!(custom.iter_set_bits().eq([0, 3, 6, 9]));
+    }
+
+    #[test]
+    fn status_flags_conversion() {
+        let status = Status::from_flags(true, false, false, true);
+        assert!(status.in_alarm() && status.out_of_service());
+        assert!(!status.fault() && !status.overridden());
+
+        let bit_string: BitString = status.clone().into();
+        assert!(bit_string.iter_set_bits().eq([0, 3]));
+        assert_eq!(bit_string.status_flags(), Some(status.clone()));
+
+        // the same flags decoded without the property id
+        let builder = BitStringBuilder::from_bits(&[true, false, false, true]).unwrap();
+        assert_eq!(builder.build().status_flags(), Some(status));
+
+        let builder = BitStringBuilder::from_bits(&[true, false, false]).unwrap();
+        assert_eq!(builder.build().status_flags(), None);
+    }
+
+    #[test]
+    fn bit_string_builder_spans_octets() {
+        let mut builder = BitStringBuilder::new();
+        for i in 0..10 {
+            builder.push(i % 3 == 0).unwrap();
+        }
+        assert_eq!(builder.bit_len(), 10);
+        assert_eq!(builder.unused_bits(), 6);
+        assert_eq!(builder.bytes(), &[0b1001_0010, 0b0100_0000]);
+
+        let BitString::Custom(bits) = builder.build() else {
+            panic!("expected a custom bit string");
+        };
+        assert_eq!(bits.bit_len(), 10);
+        assert!(bits.is_set(9));
+        assert!(!bits.is_set(8));
+    }
+
+    #[test]
+    fn double_round_trip() {
+        // Present_Value of an AnalogValue presented as a Double
+        let value = ApplicationDataValue::Double(1234.5678);
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        value.encode(&mut writer);
+        let buf = writer.to_bytes();
+        assert_eq!(buf[..2], [0x55, 0x08]);
+        assert_eq!(buf[2..], 1234.5678f64.to_be_bytes());
+
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogValue, 1);
+        let mut reader = Reader::new_with_len(buf.len());
+        let decoded = ApplicationDataValue::decode_inner(
+            &object_id,
+            &PropertyId::PropPresentValue,
+            &mut reader,
+            buf,
+        )
+        .unwrap();
+        assert!(matches!(decoded, ApplicationDataValue::Double(x) if x == 1234.5678));
+
+        // a double must be exactly 8 bytes
+        let buf = [0x54, 0, 0, 0, 0];
+        let mut reader = Reader::new_with_len(buf.len());
+        let result = ApplicationDataValue::decode_inner(
+            &object_id,
+            &PropertyId::PropPresentValue,
+            &mut reader,
+            &buf,
+        );
+        assert!(matches!(result, Err(Error::Length(_))));
+    }
+
+    #[test]
+    fn octet_string_round_trip() {
+        let object_id = ObjectId::new(ObjectType::ObjectNetworkPort, 1);
+        let cases: [(PropertyId, &[u8], &[u8]); 2] = [
+            // IP_Address 192.168.1.20
+            (
+                PropertyId::PropIpAddress,
+                &[192, 168, 1, 20],
+                &[0x64, 192, 168, 1, 20],
+            ),
+            // MAC_Address of a BACnet/IP port (6 bytes needs an extended length)
+            (
+                PropertyId::PropMacAddress,
+                &[192, 168, 1, 20, 0xba, 0xc0],
+                &[0x65, 0x06, 192, 168, 1, 20, 0xba, 0xc0],
+            ),
+        ];
+
+        for (property_id, bytes, expected) in cases {
+            let value = ApplicationDataValue::OctetString(OctetString::new(bytes));
+            let mut buf = [0; 16];
+            let mut writer = Writer::new(&mut buf);
+            value.encode(&mut writer);
+            let buf = writer.to_bytes();
+            assert_eq!(buf, expected);
+
+            let mut reader = Reader::new_with_len(buf.len());
+            let decoded =
+                ApplicationDataValue::decode_inner(&object_id, &property_id, &mut reader, buf)
+                    .unwrap();
+            let ApplicationDataValue::OctetString(x) = decoded else {
+                panic!("expected an octet string");
+            };
+            assert_eq!(x.as_bytes(), bytes);
+        }
+    }
+
+    #[test]
+    fn date_wildcards() {
+        // every Friday, encoded as is
+        let fridays = Date {
+            year: Date::WILDCARD_YEAR,
+            month: Date::WILDCARD,
+            day: Date::WILDCARD,
+            wday: 5,
+        };
+        let mut buf = [0; 4];
+        let mut writer = Writer::new(&mut buf);
+        fridays.encode(&mut writer);
+        assert_eq!(buf, [0xff, 0xff, 0xff, 0x05]);
+        let mut reader = Reader::new_with_len(buf.len());
+        assert_eq!(Date::decode(&mut reader, &buf).unwrap(), fridays);
+        assert!(fridays.is_wildcard());
+
+        let leap_day = Date {
+            year: 2024,
+            month: 2,
+            day: 29,
+            wday: 4,
+        };
+        assert!(!leap_day.is_wildcard());
+        assert!(Date::any().matches(&leap_day));
+        assert!(!fridays.matches(&leap_day));
+
+        // the last day of every even month
+        let pattern = Date {
+            month: Date::EVEN_MONTHS,
+            day: Date::LAST_DAY_OF_MONTH,
+            ..Date::any()
+        };
+        assert!(pattern.matches(&leap_day));
+        let day = Date {
+            day: 28,
+            ..leap_day.clone()
+        };
+        assert!(!pattern.matches(&day));
+        let day = Date {
+            year: 2023,
+            day: 28,
+            ..leap_day
+        };
+        assert!(pattern.matches(&day));
+        let pattern = Date {
+            month: Date::ODD_MONTHS,
+            ..pattern
+        };
+        assert!(!pattern.matches(&day));
+    }
+
+    #[test]
+    fn time_wildcards() {
+        // every minute past 7am
+        let pattern = Time {
+            hour: 7,
+            ..Time::any()
+        };
+        let mut buf = [0; 4];
+        let mut writer = Writer::new(&mut buf);
+        pattern.encode(&mut writer);
+        assert_eq!(buf, [0x07, 0xff, 0xff, 0xff]);
+        let mut reader = Reader::new_with_len(buf.len());
+        assert_eq!(Time::decode(&mut reader, &buf).unwrap(), pattern);
+        assert!(pattern.is_wildcard());
+
+        let time = Time {
+            hour: 7,
+            minute: 30,
+            second: 0,
+            hundredths: 0,
+        };
+        assert!(!time.is_wildcard());
+        assert!(pattern.matches(&time));
+        assert!(Time::any().matches(&time));
+        let time = Time { hour: 8, ..time };
+        assert!(!pattern.matches(&time));
+    }
+
+    #[test]
+    fn date_time_round_trip() {
+        let date_time = DateTime::new(
+            Date {
+                year: 2024,
+                month: 3,
+                day: 15,
+                wday: 5,
+            },
+            Time {
+                hour: 13,
+                minute: 45,
+                second: 30,
+                hundredths: 0,
+            },
+        );
+        let mut buf = [0; 10];
+        let mut writer = Writer::new(&mut buf);
+        date_time.encode(&mut writer);
+        assert_eq!(buf, [0xa4, 124, 3, 15, 5, 0xb4, 13, 45, 30, 0]);
+
+        let mut reader = Reader::new_with_len(buf.len());
+        assert_eq!(DateTime::decode(&mut reader, &buf).unwrap(), date_time);
+
+        // the time must be 4 bytes long
+        let buf = [0xa4, 124, 3, 15, 5, 0xb3, 13, 45, 30];
+        let mut reader = Reader::new_with_len(buf.len());
+        assert!(DateTime::decode(&mut reader, &buf).is_err());
+    }
+
+    #[test]
+    fn as_number_f32_coerces_numeric_values() {
+        assert_eq!(ApplicationDataValue::Real(1.5).as_number_f32(), Some(1.5));
+        assert_eq!(ApplicationDataValue::Double(2.5).as_number_f32(), Some(2.5));
+        assert_eq!(
+            ApplicationDataValue::UnsignedInt(3).as_number_f32(),
+            Some(3.0)
+        );
+        assert_eq!(
+            ApplicationDataValue::SignedInt(-4).as_number_f32(),
+            Some(-4.0)
+        );
+        assert_eq!(
+            ApplicationDataValue::Enumerated(Enumerated::Unknown(5)).as_number_f32(),
+            Some(5.0)
+        );
+        assert_eq!(
+            ApplicationDataValue::CharacterString(CharacterString::new("6")).as_number_f32(),
+            None
+        );
+
+        // only reals are returned without coercion
+        assert_eq!(ApplicationDataValue::UnsignedInt(3).as_f32(), None);
+    }
+
+    #[test]
+    fn decode_negative_signed() {
+        for value in [-1, -200, -40000, -9000000, i32::MIN, 127, 40000] {
+            let original = ApplicationDataValue::SignedInt(value);
+            let mut buf = [0; 8];
+            let mut writer = Writer::new(&mut buf);
+            original.encode(&mut writer);
+            let buf = writer.to_bytes();
+
+            let mut reader = Reader::new_with_len(buf.len());
+            let tag = Tag::decode(&mut reader, buf).unwrap();
+            let object_id = ObjectId::new(ObjectType::ObjectAnalogValue, 1);
+            let decoded = ApplicationDataValue::decode(
+                &tag,
+                &object_id,
+                &PropertyId::PropPresentValue,
+                &mut reader,
+                buf,
+            )
+            .unwrap();
+            assert!(matches!(decoded, ApplicationDataValue::SignedInt(x) if x == value));
+        }
+    }
+
+    #[test]
+    fn decode_real_wrapped_in_context_tag() {
+        let buf = [0x4e, 0x44, 0x42, 0x28, 0x00, 0x00, 0x4f];
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let property_id = PropertyId::PropPresentValue;
+
+        let mut reader = Reader::new_with_len(buf.len());
+        let value =
+            ApplicationDataValue::decode_wrapped(4, &object_id, &property_id, &mut reader, &buf)
+                .unwrap();
+        assert!(matches!(value, ApplicationDataValue::Real(x) if x == 42.0));
+        assert!(reader.eof());
+
+        // only a single value is allowed inside the wrapper
+        let buf = [0x4e, 0x21, 0x01, 0x21, 0x02, 0x4f];
+        let mut reader = Reader::new_with_len(buf.len());
+        let result =
+            ApplicationDataValue::decode_wrapped(4, &object_id, &property_id, &mut reader, &buf);
+        assert!(matches!(result, Err(Error::InvalidValue(_))));
+
+        // the wrapper itself must not be mistaken for the value
+        let mut reader = Reader::new_with_len(buf.len());
+        let tag = Tag::decode(&mut reader, &buf).unwrap();
+        let result =
+            ApplicationDataValue::decode(&tag, &object_id, &property_id, &mut reader, &buf);
+        assert!(matches!(result, Err(Error::TagNotSupported(_))));
+    }
+
+    #[test]
+    fn decode_generic_application_bit_string() {
+        // application bit string of 13 bits (3 unused) for a property with no special meaning
+        let buf = [0x83, 0x03, 0xa5, 0xe0];
+        let object_id = ObjectId::new(ObjectType::ObjectDevice, 1);
+        let property_id = PropertyId::PropProtocolServicesSupported;
+
+        let mut reader = Reader::new_with_len(buf.len());
+        let tag = Tag::decode(&mut reader, &buf).unwrap();
+        let value = ApplicationDataValue::decode(&tag, &object_id, &property_id, &mut reader, &buf)
+            .unwrap();
+        let bits = match value {
+            ApplicationDataValue::BitString(BitString::Custom(x)) => x,
+            x => panic!("expected custom bit string: {:?}", x),
+        };
+        assert_eq!(bits.bit_len(), 13);
+        assert!(bits.iter_set_bits().eq([0, 2, 5, 7, 8, 9, 10]));
+        assert!(reader.eof());
+
+        // a status flags property with more than one octet cannot be interpreted as flags
+        let mut reader = Reader::new_with_len(buf.len());
+        reader.index = 1;
+        let value = BitString::decode(&PropertyId::PropStatusFlags, 3, &mut reader, &buf).unwrap();
+        assert!(matches!(value, BitString::Custom(_)));
+    }
+
+    #[test]
+    fn character_string_invalid_is_error() {
+        // zero length (no character set) and an unsupported character set (ucs-2)
+        let buf = [0x00, 0x04, 0x00, b'a'];
+        let mut reader = Reader::new_with_len(buf.len());
+        let result = CharacterString::decode(0, &mut reader, &buf);
+        assert!(matches!(result, Err(Error::InvalidValue(_))));
+        reader.index = 1;
+        let result = CharacterString::decode(3, &mut reader, &buf);
+        assert!(matches!(result, Err(Error::InvalidValue(_))));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_json_round_trip() {
+        use crate::common::spec::EngineeringUnits;
+
+        let values = [
+            ApplicationDataValue::Null,
+            ApplicationDataValue::Boolean(true),
+            ApplicationDataValue::Real(21.5),
+            ApplicationDataValue::UnsignedInt(7),
+            ApplicationDataValue::ObjectId(ObjectId::new(ObjectType::ObjectAnalogInput, 3)),
+            ApplicationDataValue::CharacterString(CharacterString::new("AHU-1")),
+            ApplicationDataValue::Enumerated(Enumerated::Units(EngineeringUnits::DegreesCelsius)),
+        ];
+
+        for value in values {
+            let json = serde_json::to_string(&value).unwrap();
+            let decoded: ApplicationDataValue = serde_json::from_str(&json).unwrap();
+            assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+        }
+
+        // engineering units are written as their numeric value
+        let value =
+            ApplicationDataValue::Enumerated(Enumerated::Units(EngineeringUnits::DegreesCelsius));
+        let json = serde_json::to_value(&value).unwrap();
+        assert_eq!(json["Enumerated"]["Units"], 62);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_weekly_schedule() {
+        use crate::common::daily_schedule::WeeklySchedule;
+
+        // on at 08:00 on monday, nothing scheduled for the rest of the week
+        #[rustfmt::skip]
+        let buf = [
+            0x0e, 0xb4, 8, 0, 0, 0, 0x91, 0x01, 0x0f,
+            0x0e, 0x0f, 0x0e, 0x0f, 0x0e, 0x0f, 0x0e, 0x0f, 0x0e, 0x0f, 0x0e, 0x0f,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let schedule = WeeklySchedule::decode(&mut reader, &buf).unwrap();
+        let value = ApplicationDataValue::WeeklySchedule(schedule);
+
+        // the json is the same with or without alloc
+        let json = serde_json::to_value(&value).unwrap();
+        let monday = &json["WeeklySchedule"]["monday"];
+        assert_eq!(monday.as_array().unwrap().len(), 1);
+        assert_eq!(monday[0]["time"]["hour"], 8);
+        assert!(json["WeeklySchedule"]["sunday"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+
+        #[cfg(feature = "alloc")]
+        {
+            let decoded: ApplicationDataValue = serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(serde_json::to_value(&decoded).unwrap(), json);
+        }
+
+        // the borrowed schedule cannot be deserialized without an allocator
+        #[cfg(not(feature = "alloc"))]
+        {
+            let json = serde_json::to_string(&json).unwrap();
+            assert!(serde_json::from_str::<ApplicationDataValue>(&json).is_err());
+        }
+    }
+}
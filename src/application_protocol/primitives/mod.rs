@@ -1 +1,5 @@
 pub mod data_value;
+pub mod object_reference;
+pub mod priority_array;
+pub mod recipient;
+pub mod time_stamp;
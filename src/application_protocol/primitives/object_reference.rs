@@ -0,0 +1,181 @@
+use crate::common::{
+    error::Error,
+    helper::{
+        decode_context_object_id, decode_context_property_id, decode_optional_context_tag,
+        decode_unsigned, encode_context_enumerated, encode_context_object_id,
+        encode_context_unsigned,
+    },
+    io::{Reader, Writer},
+    object_id::ObjectId,
+    property_id::PropertyId,
+};
+
+// BACnetDeviceObjectReference (e.g. an entry in a Subordinate_List)
+// the device_id is omitted when the object is in the local device
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeviceObjectReference {
+    pub device_id: Option<ObjectId>,
+    pub object_id: ObjectId,
+}
+
+impl DeviceObjectReference {
+    const TAG_DEVICE_ID: u8 = 0;
+    const TAG_OBJECT_ID: u8 = 1;
+
+    pub fn new(device_id: Option<ObjectId>, object_id: ObjectId) -> Self {
+        Self {
+            device_id,
+            object_id,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        if let Some(device_id) = &self.device_id {
+            encode_context_object_id(writer, Self::TAG_DEVICE_ID, device_id);
+        }
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let device_id = match decode_optional_context_tag(reader, buf, Self::TAG_DEVICE_ID)? {
+            Some(tag) => Some(ObjectId::decode(tag.value, reader, buf)?),
+            None => None,
+        };
+        let object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_OBJECT_ID,
+            "DeviceObjectReference decode object_id",
+        )?;
+
+        Ok(Self {
+            device_id,
+            object_id,
+        })
+    }
+}
+
+// BACnetObjectPropertyReference (a property of an object in the local device)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectPropertyReference {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+}
+
+impl ObjectPropertyReference {
+    const TAG_OBJECT_ID: u8 = 0;
+    const TAG_PROPERTY_ID: u8 = 1;
+    const TAG_ARRAY_INDEX: u8 = 2;
+
+    pub fn new(object_id: ObjectId, property_id: PropertyId) -> Self {
+        Self {
+            object_id,
+            property_id,
+            array_index: None,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+        encode_context_enumerated(writer, Self::TAG_PROPERTY_ID, &self.property_id);
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::TAG_ARRAY_INDEX, array_index);
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_OBJECT_ID,
+            "ObjectPropertyReference decode object_id",
+        )?;
+        let property_id = decode_context_property_id(
+            reader,
+            buf,
+            Self::TAG_PROPERTY_ID,
+            "ObjectPropertyReference decode property_id",
+        )?;
+        let array_index = match decode_optional_context_tag(reader, buf, Self::TAG_ARRAY_INDEX)? {
+            Some(tag) => Some(decode_unsigned(tag.value, reader, buf)? as u32),
+            None => None,
+        };
+
+        Ok(Self {
+            object_id,
+            property_id,
+            array_index,
+        })
+    }
+}
+
+// BACnetDeviceObjectPropertyReference (e.g. an entry in a List_Of_Object_Property_References)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeviceObjectPropertyReference {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub device_id: Option<ObjectId>,
+}
+
+impl DeviceObjectPropertyReference {
+    const TAG_OBJECT_ID: u8 = 0;
+    const TAG_PROPERTY_ID: u8 = 1;
+    const TAG_ARRAY_INDEX: u8 = 2;
+    const TAG_DEVICE_ID: u8 = 3;
+
+    pub fn new(object_id: ObjectId, property_id: PropertyId) -> Self {
+        Self {
+            object_id,
+            property_id,
+            array_index: None,
+            device_id: None,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+        encode_context_enumerated(writer, Self::TAG_PROPERTY_ID, &self.property_id);
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::TAG_ARRAY_INDEX, array_index);
+        }
+        if let Some(device_id) = &self.device_id {
+            encode_context_object_id(writer, Self::TAG_DEVICE_ID, device_id);
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_OBJECT_ID,
+            "DeviceObjectPropertyReference decode object_id",
+        )?;
+        let property_id = decode_context_property_id(
+            reader,
+            buf,
+            Self::TAG_PROPERTY_ID,
+            "DeviceObjectPropertyReference decode property_id",
+        )?;
+        let array_index = match decode_optional_context_tag(reader, buf, Self::TAG_ARRAY_INDEX)? {
+            Some(tag) => Some(decode_unsigned(tag.value, reader, buf)? as u32),
+            None => None,
+        };
+        let device_id = match decode_optional_context_tag(reader, buf, Self::TAG_DEVICE_ID)? {
+            Some(tag) => Some(ObjectId::decode(tag.value, reader, buf)?),
+            None => None,
+        };
+
+        Ok(Self {
+            object_id,
+            property_id,
+            array_index,
+            device_id,
+        })
+    }
+}
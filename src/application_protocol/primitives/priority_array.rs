@@ -0,0 +1,158 @@
+use crate::{
+    application_protocol::primitives::data_value::ApplicationDataValue,
+    common::{
+        error::Error,
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        property_id::PropertyId,
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+};
+
+// BACnetPriorityArray
+// the commands written to a commandable object where priority 1 is the highest and a relinquished slot is None
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PriorityArray<'a> {
+    #[cfg_attr(all(feature = "serde", not(feature = "alloc")), serde(borrow))]
+    pub slots: [Option<ApplicationDataValue<'a>>; PriorityArray::LEN],
+}
+
+impl<'a> PriorityArray<'a> {
+    pub const LEN: usize = 16;
+
+    pub fn new(slots: [Option<ApplicationDataValue<'a>>; PriorityArray::LEN]) -> Self {
+        Self { slots }
+    }
+
+    // the command at this priority (1 to 16)
+    pub fn get(&self, priority: u8) -> Option<&ApplicationDataValue<'a>> {
+        let index = (priority as usize).checked_sub(1)?;
+        self.slots.get(index)?.as_ref()
+    }
+
+    // the highest priority command and its priority, if the object is being commanded at all
+    pub fn active(&self) -> Option<(u8, &ApplicationDataValue<'a>)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .find_map(|(index, slot)| slot.as_ref().map(|value| (index as u8 + 1, value)))
+    }
+
+    // the value the object takes (its present value) given its Relinquish_Default
+    pub fn present_value<'b>(
+        &'b self,
+        relinquish_default: &'b ApplicationDataValue<'a>,
+    ) -> &'b ApplicationDataValue<'a> {
+        match self.active() {
+            Some((_, value)) => value,
+            None => relinquish_default,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        for slot in self.slots.iter() {
+            match slot {
+                Some(value) => value.encode(writer),
+                None => {
+                    Tag::new(TagNumber::Application(ApplicationTagNumber::Null), 0).encode(writer)
+                }
+            }
+        }
+    }
+
+    // the slots have the same datatype as the present value of the object
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(object_id: &ObjectId, reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let mut slots: [Option<ApplicationDataValue<'a>>; PriorityArray::LEN] = Default::default();
+        for slot in slots.iter_mut() {
+            let tag = Tag::decode(reader, buf)?;
+            if tag.number == TagNumber::Application(ApplicationTagNumber::Null) {
+                continue;
+            }
+            let value = ApplicationDataValue::decode(
+                &tag,
+                object_id,
+                &PropertyId::PropPresentValue,
+                reader,
+                buf,
+            )?;
+            *slot = Some(value);
+        }
+
+        if !reader.eof() {
+            return Err(Error::InvalidValue("priority array has more than 16 slots"));
+        }
+
+        Ok(Self { slots })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::primitives::data_value::{ApplicationDataValue, Enumerated},
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            spec::Binary,
+        },
+    };
+
+    use super::PriorityArray;
+
+    #[test]
+    fn binary_output_commanded_at_priority_8() {
+        // manual operator (8) is commanding the output on and a lower priority (16) is commanding it off
+        #[rustfmt::skip]
+        let buf = &[
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x91, 0x01,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x91, 0x00,
+        ];
+
+        let object_id = ObjectId::new(ObjectType::ObjectBinaryOutput, 1);
+        let mut reader = Reader::new_with_len(buf.len());
+        let priority_array = PriorityArray::decode(&object_id, &mut reader, buf).unwrap();
+
+        assert!(priority_array.get(1).is_none());
+        assert!(priority_array.get(0).is_none());
+        assert!(priority_array.get(17).is_none());
+        assert!(matches!(
+            priority_array.get(16),
+            Some(ApplicationDataValue::Enumerated(Enumerated::Binary(
+                Binary::Off
+            )))
+        ));
+        let (priority, value) = priority_array.active().unwrap();
+        assert_eq!(priority, 8);
+        assert!(matches!(
+            value,
+            ApplicationDataValue::Enumerated(Enumerated::Binary(Binary::On))
+        ));
+
+        let mut out = [0; 32];
+        let mut writer = Writer::new(&mut out);
+        priority_array.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), buf);
+    }
+
+    #[test]
+    fn relinquished_uses_relinquish_default() {
+        let priority_array = PriorityArray::default();
+        assert!(priority_array.active().is_none());
+        let relinquish_default = ApplicationDataValue::Real(20.0);
+        assert!(matches!(
+            priority_array.present_value(&relinquish_default),
+            ApplicationDataValue::Real(x) if *x == 20.0
+        ));
+    }
+
+    #[test]
+    fn short_priority_array_is_error() {
+        let buf = [0x00; 15];
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogOutput, 1);
+        let mut reader = Reader::new_with_len(buf.len());
+        assert!(PriorityArray::decode(&object_id, &mut reader, &buf).is_err());
+    }
+}
@@ -0,0 +1,176 @@
+use crate::common::{
+    error::Error,
+    helper::{
+        decode_unsigned, encode_application_unsigned, encode_closing_tag, encode_context_object_id,
+        encode_opening_tag,
+    },
+    io::{Reader, Writer},
+    object_id::ObjectId,
+    tag::{ApplicationTagNumber, Tag, TagNumber},
+};
+
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+// BACnetRecipient - a notification can be sent to a device or directly to a network address
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Recipient<'a> {
+    Device(ObjectId),
+    Address(RecipientAddress<'a>),
+}
+
+// BACnetAddress - a network number (0 for the local network) and a mac address
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RecipientAddress<'a> {
+    pub network: u16,
+    pub mac: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RecipientAddress<'a> {
+    pub network: u16,
+    pub mac: Vec<u8>,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> RecipientAddress<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(network: u16, mac: &'a [u8]) -> Self {
+        Self { network, mac }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(network: u16, mac: &[u8]) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            network,
+            mac: mac.into(),
+            _phantom: &PHANTOM,
+        }
+    }
+}
+
+impl<'a> Recipient<'a> {
+    const TAG_DEVICE: u8 = 0;
+    const TAG_ADDRESS: u8 = 1;
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn address(network: u16, mac: &'a [u8]) -> Self {
+        Self::Address(RecipientAddress::new(network, mac))
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn address(network: u16, mac: &[u8]) -> Self {
+        Self::Address(RecipientAddress::new(network, mac))
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Device(device_id) => {
+                encode_context_object_id(writer, Self::TAG_DEVICE, device_id);
+            }
+            Self::Address(address) => {
+                encode_opening_tag(writer, Self::TAG_ADDRESS);
+                encode_application_unsigned(writer, address.network as u64);
+                Tag::new(
+                    TagNumber::Application(ApplicationTagNumber::OctetString),
+                    address.mac.len() as u32,
+                )
+                .encode(writer);
+                #[cfg(feature = "alloc")]
+                writer.extend_from_slice(&address.mac);
+                #[cfg(not(feature = "alloc"))]
+                writer.extend_from_slice(address.mac);
+                encode_closing_tag(writer, Self::TAG_ADDRESS);
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let tag = Tag::decode(reader, buf)?;
+        match tag.number {
+            TagNumber::ContextSpecific(Self::TAG_DEVICE) => {
+                let device_id = ObjectId::decode(tag.value, reader, buf)?;
+                Ok(Self::Device(device_id))
+            }
+            TagNumber::ContextSpecificOpening(Self::TAG_ADDRESS) => {
+                let tag = Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+                    "Recipient decode network",
+                )?;
+                let network = decode_unsigned(tag.value, reader, buf)?;
+                if network > u16::MAX as u64 {
+                    return Err(Error::InvalidValue("Recipient network number out of range"));
+                }
+
+                let tag = Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::Application(ApplicationTagNumber::OctetString),
+                    "Recipient decode mac",
+                )?;
+                let mac = reader.read_slice(tag.value as usize, buf)?;
+                Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecificClosing(Self::TAG_ADDRESS),
+                    "Recipient decode address closing tag",
+                )?;
+
+                Ok(Self::Address(RecipientAddress::new(network as u16, mac)))
+            }
+            number => Err(Error::TagNotSupported(("Recipient decode", number))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+    };
+
+    use super::Recipient;
+
+    fn round_trip(recipient: &Recipient, expected: &[u8]) {
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        recipient.encode(&mut writer);
+        let len = writer.index;
+        assert_eq!(&buf[..len], expected);
+
+        let mut reader = Reader::new_with_len(len);
+        let decoded = Recipient::decode(&mut reader, &buf[..len]).unwrap();
+        assert_eq!(&decoded, recipient);
+        assert!(reader.eof());
+    }
+
+    #[test]
+    fn device() {
+        let recipient = Recipient::Device(ObjectId::new(ObjectType::ObjectDevice, 100));
+        round_trip(&recipient, &[0x0c, 0x02, 0x00, 0x00, 0x64]);
+    }
+
+    #[test]
+    fn address() {
+        // 192.168.1.249:47808 on the local network
+        let mac = [192, 168, 1, 249, 0xba, 0xc0];
+        let recipient = Recipient::address(0, &mac);
+        round_trip(
+            &recipient,
+            &[
+                0x1e, 0x21, 0x00, 0x65, 0x06, 192, 168, 1, 249, 0xba, 0xc0, 0x1f,
+            ],
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    application_protocol::primitives::data_value::{DateTime, Time},
+    common::{
+        error::Error,
+        helper::{
+            decode_unsigned, encode_closing_tag, encode_context_unsigned, encode_opening_tag,
+        },
+        io::{Reader, Writer},
+        tag::{Tag, TagNumber},
+    },
+};
+
+// BACnetTimeStamp
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeStamp {
+    Time(Time),
+    SequenceNumber(u32),
+    DateTime(DateTime),
+}
+
+impl TimeStamp {
+    const TAG_TIME: u8 = 0;
+    const TAG_SEQUENCE_NUMBER: u8 = 1;
+    const TAG_DATE_TIME: u8 = 2;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Time(time) => {
+                Tag::new(TagNumber::ContextSpecific(Self::TAG_TIME), Time::LEN).encode(writer);
+                time.encode(writer);
+            }
+            Self::SequenceNumber(sequence_number) => {
+                encode_context_unsigned(writer, Self::TAG_SEQUENCE_NUMBER, *sequence_number);
+            }
+            Self::DateTime(date_time) => {
+                encode_opening_tag(writer, Self::TAG_DATE_TIME);
+                date_time.encode(writer);
+                encode_closing_tag(writer, Self::TAG_DATE_TIME);
+            }
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode(reader, buf)?;
+        match tag.number {
+            TagNumber::ContextSpecific(Self::TAG_TIME) => {
+                tag.expect_value("TimeStamp decode time", Time::LEN)?;
+                let time = Time::decode(reader, buf)?;
+                Ok(Self::Time(time))
+            }
+            TagNumber::ContextSpecific(Self::TAG_SEQUENCE_NUMBER) => {
+                let sequence_number = decode_unsigned(tag.value, reader, buf)? as u32;
+                Ok(Self::SequenceNumber(sequence_number))
+            }
+            TagNumber::ContextSpecificOpening(Self::TAG_DATE_TIME) => {
+                let date_time = DateTime::decode(reader, buf)?;
+                Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecificClosing(Self::TAG_DATE_TIME),
+                    "TimeStamp decode date time closing tag",
+                )?;
+                Ok(Self::DateTime(date_time))
+            }
+            number => Err(Error::TagNotSupported(("TimeStamp decode", number))),
+        }
+    }
+
+    // the Event_Time_Stamps property holds the time of the last to-offnormal, to-fault and to-normal events
+    pub fn decode_event_time_stamps(reader: &mut Reader, buf: &[u8]) -> Result<[Self; 3], Error> {
+        let to_offnormal = Self::decode(reader, buf)?;
+        let to_fault = Self::decode(reader, buf)?;
+        let to_normal = Self::decode(reader, buf)?;
+        Ok([to_offnormal, to_fault, to_normal])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::primitives::data_value::{Date, DateTime, Time},
+        common::{
+            error::Error,
+            io::{Reader, Writer},
+        },
+    };
+
+    use super::TimeStamp;
+
+    fn round_trip(time_stamp: &TimeStamp, expected: &[u8]) {
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        time_stamp.encode(&mut writer);
+        let len = writer.index;
+        assert_eq!(&buf[..len], expected);
+
+        let mut reader = Reader::new_with_len(len);
+        let decoded = TimeStamp::decode(&mut reader, &buf).unwrap();
+        assert_eq!(&decoded, time_stamp);
+        assert!(reader.eof());
+    }
+
+    fn time() -> Time {
+        Time {
+            hour: 13,
+            minute: 30,
+            second: 5,
+            hundredths: 0,
+        }
+    }
+
+    #[test]
+    fn time_choice() {
+        round_trip(&TimeStamp::Time(time()), &[0x0c, 13, 30, 5, 0]);
+    }
+
+    #[test]
+    fn sequence_number_choice() {
+        round_trip(&TimeStamp::SequenceNumber(300), &[0x1a, 0x01, 0x2c]);
+    }
+
+    #[test]
+    fn date_time_choice() {
+        let date = Date {
+            year: 2024,
+            month: 3,
+            day: 14,
+            wday: 4,
+        };
+        let time_stamp = TimeStamp::DateTime(DateTime::new(date, time()));
+        round_trip(
+            &time_stamp,
+            &[0x2e, 0xa4, 124, 3, 14, 4, 0xb4, 13, 30, 5, 0, 0x2f],
+        );
+    }
+
+    #[test]
+    fn event_time_stamps() {
+        let buf = [0x1a, 0x01, 0x2c, 0x0c, 13, 30, 5, 0, 0x19, 0x00];
+        let mut reader = Reader::new_with_len(buf.len());
+        let time_stamps = TimeStamp::decode_event_time_stamps(&mut reader, &buf).unwrap();
+        assert_eq!(
+            time_stamps,
+            [
+                TimeStamp::SequenceNumber(300),
+                TimeStamp::Time(time()),
+                TimeStamp::SequenceNumber(0)
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_choices_are_errors() {
+        let cases: [&[u8]; 4] = [
+            // context tag 3 is not a time stamp choice
+            &[0x39, 0x01],
+            // a time must be 4 bytes
+            &[0x0b, 13, 30, 5],
+            // the date time is missing its closing tag
+            &[0x2e, 0xa4, 124, 3, 14, 4, 0xb4, 13, 30, 5, 0],
+            // the date time must start with a date
+            &[0x2e, 0xb4, 13, 30, 5, 0, 0xa4, 124, 3, 14, 4, 0x2f],
+        ];
+        for buf in cases {
+            let mut reader = Reader::new_with_len(buf.len());
+            assert!(
+                TimeStamp::decode(&mut reader, buf).is_err(),
+                "decoded {:02x?}",
+                buf
+            );
+        }
+
+        let buf = [0x39, 0x01];
+        let mut reader = Reader::new_with_len(buf.len());
+        assert!(matches!(
+            TimeStamp::decode(&mut reader, &buf),
+            Err(Error::TagNotSupported(_))
+        ));
+    }
+}
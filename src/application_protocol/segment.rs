@@ -3,12 +3,21 @@
 
 #[cfg(feature = "alloc")]
 use {
-    crate::common::spooky::Phantom,
-    alloc::{vec, vec::Vec},
+    crate::{
+        application_protocol::{
+            application_pdu::{MaxAdpu, MaxSegments},
+            confirmed::ComplexAck,
+        },
+        common::spooky::Phantom,
+    },
+    alloc::{collections::BTreeMap, vec, vec::Vec},
 };
 
 use crate::{
-    application_protocol::application_pdu::{ApduType, PduFlags},
+    application_protocol::{
+        application_pdu::{ApduType, PduFlags},
+        confirmed::SegmentAck,
+    },
     common::{
         error::Error,
         io::{Reader, Writer},
@@ -122,18 +131,13 @@ impl<'a> Segment<'a> {
     #[cfg(feature = "alloc")]
     fn decode_data(reader: &mut Reader, buf: &[u8]) -> Result<Vec<u8>, Error> {
         // read bytes into owned datastructure
-        let len = reader.end - reader.index;
-        let mut data = vec![0u8; len];
-        let slice = reader.read_slice(len, buf)?;
-        data.copy_from_slice(slice);
-        Ok(data)
+        let slice = reader.read_remaining(buf)?;
+        Ok(slice.to_vec())
     }
 
     #[cfg(not(feature = "alloc"))]
     fn decode_data(reader: &mut Reader, buf: &'a [u8]) -> Result<&'a [u8], Error> {
-        // read bytes into owned datastructure
-        let len = reader.end - reader.index;
-        let data = reader.read_slice(len, buf)?;
+        let data = reader.read_remaining(buf)?;
         Ok(data)
     }
 
@@ -147,32 +151,286 @@ impl<'a> Segment<'a> {
         writer.push(self.sequence_number);
         writer.push(self.window_size);
         writer.push(self.service_choice);
+        #[cfg(feature = "alloc")]
         writer.extend_from_slice(&self.data);
+        #[cfg(not(feature = "alloc"))]
+        writer.extend_from_slice(self.data);
     }
 
     // a special case encoder for when this segment is being accumulated
     // into an unsegmented APDU.
     // returns number of bytes written (TODO: why? - this is redundant and can be calculated by the client)
     pub fn encode_for_accumulation(&self, writer: &mut Writer) -> usize {
-        let start = writer.index;
+        let start = writer.position();
         if self.sequence_number == 0 {
             writer.push((self.apdu_type.clone() as u8) << 4);
             writer.push(self.invoke_id);
             writer.push(self.service_choice);
         }
+        #[cfg(feature = "alloc")]
         writer.extend_from_slice(&self.data);
-        writer.index - start
+        #[cfg(not(feature = "alloc"))]
+        writer.extend_from_slice(self.data);
+        writer.position() - start
+    }
+}
+
+// what the sender of a segmented message should do next
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SegmentAction {
+    Send {
+        sequence_number: u8,
+        more_follows: bool,
+    },
+    WaitForAck,
+    Done,
+}
+
+// Tracks the sending side of a segmented transfer.
+// The window size starts as the proposed window size and is replaced by the actual window size
+// returned in each SegmentAck. Once a full window has been sent the sender must wait for a SegmentAck
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SegmentationState {
+    pub window_size: u8,
+    pub total_segments: u32,
+    sent: u32,
+    acked: u32,
+}
+
+impl SegmentationState {
+    // window sizes must be between 1 and 127
+    const MAX_WINDOW_SIZE: u8 = 127;
+
+    pub fn new(proposed_window_size: u8, total_segments: u32) -> Self {
+        Self {
+            window_size: proposed_window_size.clamp(1, Self::MAX_WINDOW_SIZE),
+            total_segments,
+            sent: 0,
+            acked: 0,
+        }
+    }
+
+    pub fn next_action(&self) -> SegmentAction {
+        if self.acked >= self.total_segments {
+            SegmentAction::Done
+        } else if self.sent < self.total_segments
+            && self.sent - self.acked < self.window_size as u32
+        {
+            SegmentAction::Send {
+                sequence_number: self.sent as u8, // sequence numbers wrap around
+                more_follows: self.sent + 1 < self.total_segments,
+            }
+        } else {
+            SegmentAction::WaitForAck
+        }
+    }
+
+    // call this after sending the segment returned by next_action
+    pub fn segment_sent(&mut self) {
+        if self.sent < self.total_segments {
+            self.sent += 1;
+        }
+    }
+
+    pub fn ack_received(&mut self, ack: &SegmentAck) -> Result<(), Error> {
+        // the ack contains the sequence number of the last segment received (modulo 256)
+        let offset = ack.sequence_num.wrapping_sub(self.acked as u8) as u32;
+        if offset < self.sent - self.acked {
+            self.acked += offset + 1;
+        } else if offset < 256 - Self::MAX_WINDOW_SIZE as u32 {
+            return Err(Error::InvalidValue(
+                "SegmentAck sequence number is outside of the window",
+            ));
+        }
+        // otherwise the ack is a duplicate or older than the last one received so nothing new is acked
+
+        if ack.negative_ack {
+            // resend everything after the acknowledged segment
+            self.sent = self.acked;
+        }
+        if ack.actual_window_size > 0 {
+            self.window_size = ack.actual_window_size.min(Self::MAX_WINDOW_SIZE);
+        }
+        Ok(())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.acked >= self.total_segments
+    }
+}
+
+// what the receiver of a segmented ComplexAck should do after pushing a segment
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AssemblyProgress<'a> {
+    // more segments follow, send the SegmentAck (if any) because the window is full
+    // or a segment is missing (a negative ack)
+    Pending(Option<SegmentAck>),
+    // send the SegmentAck for the last segment, the whole ack has been decoded
+    Complete(SegmentAck, ComplexAck<'a>),
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+struct Transaction {
+    data: Vec<u8>, // invoke_id and service choice followed by the segment data received so far
+    next_sequence_number: u8,
+    received_in_window: u8,
+}
+
+// Reassembles segmented ComplexAcks, keeping one transaction per invoke_id.
+// A segment that arrives out of order is discarded and answered with a negative SegmentAck
+// so the server resends the segments after the last one received in order.
+// A duplicate of a segment already received (e.g. after a lost SegmentAck) is silently discarded.
+// A transaction is limited to the max apdu and max segments accepted in the confirmed request
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct SegmentAssembler {
+    transactions: BTreeMap<u8, Transaction>,
+    max_len: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl Default for SegmentAssembler {
+    fn default() -> Self {
+        Self::new(MaxAdpu::_1476, MaxSegments::_65)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl SegmentAssembler {
+    pub fn new(max_apdu: MaxAdpu, max_segments: MaxSegments) -> Self {
+        Self {
+            transactions: BTreeMap::new(),
+            max_len: max_apdu.octets() * max_segments.segments(),
+        }
+    }
+
+    pub fn push<'a>(&mut self, segment: &Segment) -> Result<AssemblyProgress<'a>, Error> {
+        if segment.apdu_type != ApduType::ComplexAck {
+            return Err(Error::InvalidValue(
+                "only segmented ComplexAck messages can be assembled",
+            ));
+        }
+
+        if segment.sequence_number == 0 && !self.transactions.contains_key(&segment.invoke_id) {
+            let transaction = Transaction {
+                data: vec![segment.invoke_id, segment.service_choice],
+                next_sequence_number: 0,
+                received_in_window: 0,
+            };
+            self.transactions.insert(segment.invoke_id, transaction);
+        }
+
+        let transaction =
+            self.transactions
+                .get_mut(&segment.invoke_id)
+                .ok_or(Error::InvalidValue(
+                    "segment received for an unknown invoke_id",
+                ))?;
+
+        // accept the window size proposed by the server
+        let window_size = segment
+            .window_size
+            .clamp(1, SegmentationState::MAX_WINDOW_SIZE);
+
+        // sequence numbers wrap around so a duplicate is one from the window before the next segment
+        let behind = transaction
+            .next_sequence_number
+            .wrapping_sub(segment.sequence_number);
+        if (1..=window_size).contains(&behind) {
+            return Ok(AssemblyProgress::Pending(None));
+        }
+
+        if transaction.next_sequence_number != segment.sequence_number {
+            // ask the server to resend everything after the last segment received in order
+            transaction.received_in_window = 0;
+            let nak = SegmentAck {
+                negative_ack: true,
+                server: false,
+                invoke_id: segment.invoke_id,
+                sequence_num: transaction.next_sequence_number.wrapping_sub(1),
+                actual_window_size: window_size,
+            };
+            return Ok(AssemblyProgress::Pending(Some(nak)));
+        }
+
+        if transaction.data.len() + segment.data.len() > self.max_len {
+            self.transactions.remove(&segment.invoke_id);
+            return Err(Error::Length((
+                "segmented ComplexAck is larger than the negotiated max apdu and max segments",
+                self.max_len as u32,
+            )));
+        }
+
+        transaction.data.extend_from_slice(&segment.data);
+        transaction.next_sequence_number = segment.sequence_number.wrapping_add(1);
+        transaction.received_in_window += 1;
+
+        let ack = SegmentAck {
+            negative_ack: false,
+            server: false,
+            invoke_id: segment.invoke_id,
+            sequence_num: segment.sequence_number,
+            actual_window_size: window_size,
+        };
+
+        if !segment.more_follows {
+            let transaction = self.transactions.remove(&segment.invoke_id).unwrap();
+            let data = &transaction.data;
+            let mut reader = Reader::new_with_len(data.len());
+            let complex_ack = ComplexAck::decode(&mut reader, data)?;
+            return Ok(AssemblyProgress::Complete(ack, complex_ack));
+        }
+
+        if transaction.received_in_window >= window_size {
+            transaction.received_in_window = 0;
+            Ok(AssemblyProgress::Pending(Some(ack)))
+        } else {
+            Ok(AssemblyProgress::Pending(None))
+        }
+    }
+
+    // drops a partially received transaction (e.g. after a timeout)
+    pub fn discard(&mut self, invoke_id: u8) {
+        self.transactions.remove(&invoke_id);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        application_protocol::application_pdu::ApduType,
+        application_protocol::{application_pdu::ApduType, confirmed::SegmentAck},
         common::io::{Reader, Writer},
     };
 
-    use super::Segment;
+    use super::{Segment, SegmentAction, SegmentationState};
+
+    #[cfg(feature = "alloc")]
+    use {
+        super::{AssemblyProgress, SegmentAssembler},
+        crate::{
+            application_protocol::{
+                application_pdu::{MaxAdpu, MaxSegments},
+                confirmed::{ComplexAck, ComplexAckService},
+                primitives::data_value::{ApplicationDataValue, CharacterString},
+                services::read_property::{ReadPropertyAck, ReadPropertyValue},
+            },
+            common::{
+                object_id::{ObjectId, ObjectType},
+                property_id::PropertyId,
+                spec::BACNET_ARRAY_ALL,
+            },
+        },
+        alloc::vec::Vec,
+    };
 
     #[test]
     fn reversable() {
@@ -195,9 +453,208 @@ mod tests {
         let input: [u8; 6] = [1, 12, 1, 1, 2, 3];
         let mut reader = Reader::new_with_len(input.len());
         let decoded = Segment::decode(false, ApduType::ComplexAck, &mut reader, &input).unwrap();
-        assert_eq!(decoded.more_follows, false);
+        assert!(!decoded.more_follows);
         assert_eq!(decoded.sequence_number, 12);
         assert_eq!(decoded.window_size, 1);
         assert_eq!(decoded.apdu_type, ApduType::ComplexAck);
     }
+
+    #[test]
+    fn window_of_two() {
+        let mut state = SegmentationState::new(2, 4);
+        let ack = |sequence_num| SegmentAck {
+            negative_ack: false,
+            server: false,
+            invoke_id: 1,
+            sequence_num,
+            actual_window_size: 2,
+        };
+
+        // first window
+        for (sequence_number, more_follows) in [(0, true), (1, true)] {
+            assert_eq!(
+                state.next_action(),
+                SegmentAction::Send {
+                    sequence_number,
+                    more_follows
+                }
+            );
+            state.segment_sent();
+        }
+        assert_eq!(state.next_action(), SegmentAction::WaitForAck);
+        state.ack_received(&ack(1)).unwrap();
+
+        // second window ends with the last segment
+        for (sequence_number, more_follows) in [(2, true), (3, false)] {
+            assert_eq!(
+                state.next_action(),
+                SegmentAction::Send {
+                    sequence_number,
+                    more_follows
+                }
+            );
+            state.segment_sent();
+        }
+        assert_eq!(state.next_action(), SegmentAction::WaitForAck);
+        assert!(state.ack_received(&ack(5)).is_err());
+        // a repeated ack for the first window is ignored
+        state.ack_received(&ack(1)).unwrap();
+        assert_eq!(state.next_action(), SegmentAction::WaitForAck);
+        state.ack_received(&ack(3)).unwrap();
+
+        assert_eq!(state.next_action(), SegmentAction::Done);
+        assert!(state.is_complete());
+    }
+
+    #[test]
+    fn negative_ack_resends() {
+        let mut state = SegmentationState::new(3, 3);
+        for _ in 0..3 {
+            state.segment_sent();
+        }
+        assert_eq!(state.next_action(), SegmentAction::WaitForAck);
+
+        // only the first segment arrived
+        let nak = SegmentAck {
+            negative_ack: true,
+            server: false,
+            invoke_id: 1,
+            sequence_num: 0,
+            actual_window_size: 3,
+        };
+        state.ack_received(&nak).unwrap();
+        assert_eq!(
+            state.next_action(),
+            SegmentAction::Send {
+                sequence_number: 1,
+                more_follows: true
+            }
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn assemble_segmented_complex_ack() {
+        let object_id = ObjectId::new(ObjectType::ObjectDevice, 20088);
+        let name = ApplicationDataValue::CharacterString(CharacterString::new(
+            "a device name long enough to need a few segments",
+        ));
+        let ack = ComplexAck {
+            invoke_id: 7,
+            service: ComplexAckService::ReadProperty(ReadPropertyAck {
+                object_id,
+                property_id: PropertyId::PropObjectName,
+                array_index: BACNET_ARRAY_ALL,
+                property_value: ReadPropertyValue::ApplicationDataValue(name),
+            }),
+        };
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        ack.encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        // skip the control byte, invoke_id and service choice
+        let chunks: Vec<_> = encoded[3..].chunks(16).collect();
+        assert_eq!(chunks.len(), 4);
+
+        let mut assembler = SegmentAssembler::default();
+        let mut acks = Vec::new();
+        let mut assembled = None;
+        for (sequence_number, chunk) in chunks.iter().enumerate() {
+            let segment = Segment::new(
+                ApduType::ComplexAck,
+                sequence_number + 1 < chunks.len(),
+                7,
+                sequence_number as u8,
+                2,
+                encoded[2],
+                chunk.to_vec(),
+            );
+            match assembler.push(&segment).unwrap() {
+                AssemblyProgress::Pending(ack) => acks.extend(ack.map(|x| x.sequence_num)),
+                AssemblyProgress::Complete(ack, complex_ack) => {
+                    acks.push(ack.sequence_num);
+                    assembled = Some(complex_ack);
+                }
+            }
+        }
+
+        // one ack per window of two segments
+        assert_eq!(acks, [1, 3]);
+        assert!(assembler.is_empty());
+        let ComplexAckService::ReadProperty(ack) = assembled.unwrap().service else {
+            panic!("expected a read property ack");
+        };
+        assert_eq!(ack.object_id, object_id);
+        let ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::CharacterString(name)) =
+            ack.property_value
+        else {
+            panic!("expected a character string");
+        };
+        assert_eq!(
+            &*name.inner,
+            "a device name long enough to need a few segments"
+        );
+
+        // a missing segment is answered with a negative ack for the last segment received in order
+        let segment = |sequence_number| {
+            Segment::new(
+                ApduType::ComplexAck,
+                true,
+                8,
+                sequence_number,
+                2,
+                12,
+                Vec::from([0]),
+            )
+        };
+        assembler.push(&segment(0)).unwrap();
+        let AssemblyProgress::Pending(Some(nak)) = assembler.push(&segment(2)).unwrap() else {
+            panic!("expected a negative ack");
+        };
+        assert!(nak.negative_ack);
+        assert_eq!(nak.sequence_num, 0);
+        assert!(matches!(
+            assembler.push(&segment(1)).unwrap(),
+            AssemblyProgress::Pending(None)
+        ));
+
+        // duplicates are discarded without an ack and don't restart the transaction
+        for sequence_number in [1, 0] {
+            assert!(matches!(
+                assembler.push(&segment(sequence_number)).unwrap(),
+                AssemblyProgress::Pending(None)
+            ));
+        }
+        let AssemblyProgress::Pending(Some(ack)) = assembler.push(&segment(2)).unwrap() else {
+            panic!("expected an ack");
+        };
+        assert!(!ack.negative_ack);
+        assert_eq!(ack.sequence_num, 2);
+        assembler.discard(8);
+        assert!(assembler.is_empty());
+        assert!(assembler.push(&segment(1)).is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn assemble_past_negotiated_size_is_error() {
+        // 2 segments of at most 50 octets
+        let mut assembler = SegmentAssembler::new(MaxAdpu::_0, MaxSegments::_2);
+        let segment = |sequence_number| {
+            Segment::new(
+                ApduType::ComplexAck,
+                true,
+                9,
+                sequence_number,
+                2,
+                12,
+                Vec::from([0; 40]),
+            )
+        };
+        assembler.push(&segment(0)).unwrap();
+        assembler.push(&segment(1)).unwrap();
+        assert!(assembler.push(&segment(2)).is_err());
+        assert!(assembler.is_empty());
+    }
 }
@@ -0,0 +1,101 @@
+use crate::{
+    application_protocol::primitives::{data_value::CharacterString, time_stamp::TimeStamp},
+    common::{
+        helper::{
+            encode_closing_tag, encode_context_object_id, encode_context_unsigned,
+            encode_opening_tag,
+        },
+        io::Writer,
+        object_id::ObjectId,
+        spec::EventState,
+    },
+};
+
+// acknowledges an alarm reported in an EventNotification (the reply is a SimpleAck)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AcknowledgeAlarm<'a> {
+    pub process_id: u32,
+    pub object_id: ObjectId,
+    pub event_state: EventState, // the to_state of the notification being acknowledged
+    pub time_stamp: TimeStamp,   // the time_stamp of the notification being acknowledged
+    pub source: CharacterString<'a>, // e.g. the name of the operator
+    pub time_of_acknowledgment: TimeStamp,
+}
+
+impl<'a> AcknowledgeAlarm<'a> {
+    const TAG_PROCESS_ID: u8 = 0;
+    const TAG_OBJECT_ID: u8 = 1;
+    const TAG_EVENT_STATE: u8 = 2;
+    const TAG_TIME_STAMP: u8 = 3;
+    const TAG_SOURCE: u8 = 4;
+    const TAG_TIME_OF_ACKNOWLEDGMENT: u8 = 5;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_unsigned(writer, Self::TAG_PROCESS_ID, self.process_id);
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+        // enumerated values are encoded like unsigned values
+        encode_context_unsigned(
+            writer,
+            Self::TAG_EVENT_STATE,
+            self.event_state.clone() as u32,
+        );
+
+        encode_opening_tag(writer, Self::TAG_TIME_STAMP);
+        self.time_stamp.encode(writer);
+        encode_closing_tag(writer, Self::TAG_TIME_STAMP);
+
+        self.source.encode_context(Self::TAG_SOURCE, writer);
+
+        encode_opening_tag(writer, Self::TAG_TIME_OF_ACKNOWLEDGMENT);
+        self.time_of_acknowledgment.encode(writer);
+        encode_closing_tag(writer, Self::TAG_TIME_OF_ACKNOWLEDGMENT);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::primitives::{
+            data_value::{CharacterString, Time},
+            time_stamp::TimeStamp,
+        },
+        common::{
+            io::Writer,
+            object_id::{ObjectId, ObjectType},
+            spec::EventState,
+        },
+    };
+
+    use super::AcknowledgeAlarm;
+
+    #[test]
+    fn encode_acknowledge_alarm() {
+        let request = AcknowledgeAlarm {
+            process_id: 1,
+            object_id: ObjectId::new(ObjectType::ObjectAnalogInput, 5),
+            event_state: EventState::HighLimit,
+            time_stamp: TimeStamp::SequenceNumber(42),
+            source: CharacterString::new("op"),
+            time_of_acknowledgment: TimeStamp::Time(Time {
+                hour: 14,
+                minute: 0,
+                second: 0,
+                hundredths: 0,
+            }),
+        };
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        #[rustfmt::skip]
+        let expected = [
+            0x09, 0x01,
+            0x1c, 0x00, 0x00, 0x00, 0x05,
+            0x29, 0x03,
+            0x3e, 0x19, 0x2a, 0x3f,
+            0x4b, 0x00, b'o', b'p',
+            0x5e, 0x0c, 0x0e, 0x00, 0x00, 0x00, 0x5f,
+        ];
+        assert_eq!(writer.to_bytes(), &expected);
+    }
+}
@@ -0,0 +1,476 @@
+use crate::{
+    application_protocol::confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
+    common::{
+        error::Error,
+        helper::{
+            decode_application_signed, decode_application_unsigned, encode_application_object_id,
+            encode_application_signed, encode_application_unsigned, encode_closing_tag,
+            encode_opening_tag, get_tagged_body,
+        },
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+    network_protocol::data_link::DataLink,
+};
+
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FileAccessMethod {
+    Stream {
+        file_start_position: i32,
+        requested_octet_count: u32,
+    },
+    Record {
+        file_start_record: i32,
+        requested_record_count: u32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AtomicReadFile {
+    pub object_id: ObjectId, // e.g. ObjectFile:1
+    pub access_method: FileAccessMethod,
+}
+
+impl AtomicReadFile {
+    const TAG_STREAM_ACCESS: u8 = 0;
+    const TAG_RECORD_ACCESS: u8 = 1;
+
+    pub fn new(object_id: ObjectId, access_method: FileAccessMethod) -> Self {
+        Self {
+            object_id,
+            access_method,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_object_id(writer, &self.object_id);
+        match &self.access_method {
+            FileAccessMethod::Stream {
+                file_start_position,
+                requested_octet_count,
+            } => {
+                encode_opening_tag(writer, Self::TAG_STREAM_ACCESS);
+                encode_application_signed(writer, *file_start_position);
+                encode_application_unsigned(writer, *requested_octet_count as u64);
+                encode_closing_tag(writer, Self::TAG_STREAM_ACCESS);
+            }
+            FileAccessMethod::Record {
+                file_start_record,
+                requested_record_count,
+            } => {
+                encode_opening_tag(writer, Self::TAG_RECORD_ACCESS);
+                encode_application_signed(writer, *file_start_record);
+                encode_application_unsigned(writer, *requested_record_count as u64);
+                encode_closing_tag(writer, Self::TAG_RECORD_ACCESS);
+            }
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::ObjectId),
+            "AtomicReadFile decode object_id",
+        )?;
+        let object_id = ObjectId::decode(tag.value, reader, buf)?;
+
+        let (inner_buf, tag_number) = get_tagged_body(reader, buf)?;
+        let mut inner_reader = Reader::new_with_len(inner_buf.len());
+        let start =
+            decode_application_signed(&mut inner_reader, inner_buf, "AtomicReadFile decode start")?;
+        let count = decode_application_unsigned(
+            &mut inner_reader,
+            inner_buf,
+            "AtomicReadFile decode count",
+        )?;
+
+        let access_method = match tag_number {
+            Self::TAG_STREAM_ACCESS => FileAccessMethod::Stream {
+                file_start_position: start,
+                requested_octet_count: count,
+            },
+            Self::TAG_RECORD_ACCESS => FileAccessMethod::Record {
+                file_start_record: start,
+                requested_record_count: count,
+            },
+            x => {
+                return Err(Error::InvalidVariant((
+                    "AtomicReadFile decode access method",
+                    x as u32,
+                )))
+            }
+        };
+
+        Ok(Self {
+            object_id,
+            access_method,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AtomicReadFileData<'a> {
+    Stream(StreamData<'a>),
+    Record(RecordData<'a>),
+}
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StreamData<'a> {
+    pub file_start_position: i32,
+    pub file_data: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StreamData<'a> {
+    pub file_start_position: i32,
+    pub file_data: Vec<u8>,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> StreamData<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(file_start_position: i32, file_data: &'a [u8]) -> Self {
+        Self {
+            file_start_position,
+            file_data,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(file_start_position: i32, file_data: &[u8]) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            file_start_position,
+            file_data: file_data.into(),
+            _phantom: &PHANTOM,
+        }
+    }
+}
+
+// the records are kept in their encoded form (a sequence of application tagged octet strings)
+// and decoded lazily using the records() iterator
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RecordData<'a> {
+    pub file_start_record: i32,
+    pub returned_record_count: u32,
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RecordData<'a> {
+    pub file_start_record: i32,
+    pub returned_record_count: u32,
+    buf: Vec<u8>,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> RecordData<'a> {
+    // buf is the encoded list of octet strings that follows the returned record count
+    #[cfg(not(feature = "alloc"))]
+    pub fn new_from_buf(file_start_record: i32, returned_record_count: u32, buf: &'a [u8]) -> Self {
+        Self {
+            file_start_record,
+            returned_record_count,
+            buf,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new_from_buf(file_start_record: i32, returned_record_count: u32, buf: &[u8]) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            file_start_record,
+            returned_record_count,
+            buf: buf.into(),
+            _phantom: &PHANTOM,
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn records(&self) -> FileRecordIter<'a> {
+        FileRecordIter::new(self.buf, self.returned_record_count)
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn records(&self) -> FileRecordIter<'_> {
+        FileRecordIter::new(&self.buf, self.returned_record_count)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FileRecordIter<'a> {
+    reader: Reader,
+    buf: &'a [u8],
+    remaining: u32,
+}
+
+impl<'a> FileRecordIter<'a> {
+    pub fn new(buf: &'a [u8], record_count: u32) -> Self {
+        Self {
+            reader: Reader::new_with_len(buf.len()),
+            buf,
+            remaining: record_count,
+        }
+    }
+
+    fn next_internal(&mut self) -> Result<&'a [u8], Error> {
+        let tag = Tag::decode_expected(
+            &mut self.reader,
+            self.buf,
+            TagNumber::Application(ApplicationTagNumber::OctetString),
+            "FileRecordIter next",
+        )?;
+        self.reader.read_slice(tag.value as usize, self.buf)
+    }
+}
+
+impl<'a> Iterator for FileRecordIter<'a> {
+    type Item = Result<&'a [u8], Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let remaining = self.remaining;
+        self.remaining -= 1;
+        if self.reader.eof() {
+            // stop after reporting the missing records
+            self.remaining = 0;
+            return Some(Err(Error::Length((
+                "FileRecordIter fewer records than returned record count",
+                remaining,
+            ))));
+        }
+
+        Some(self.next_internal())
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AtomicReadFileAck<'a> {
+    pub end_of_file: bool,
+    pub data: AtomicReadFileData<'a>,
+}
+
+impl<'a> TryFrom<DataLink<'a>> for AtomicReadFileAck<'a> {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        let ack: ComplexAck = value.try_into()?;
+        match ack.service {
+            ComplexAckService::AtomicReadFile(ack) => Ok(ack),
+            _ => Err(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService AtomicReadFileAck",
+            )),
+        }
+    }
+}
+
+impl<'a> AtomicReadFileAck<'a> {
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::AtomicReadFile as u8);
+        Tag::new(
+            TagNumber::Application(ApplicationTagNumber::Boolean),
+            self.end_of_file as u32,
+        )
+        .encode(writer);
+
+        match &self.data {
+            AtomicReadFileData::Stream(data) => {
+                encode_opening_tag(writer, AtomicReadFile::TAG_STREAM_ACCESS);
+                encode_application_signed(writer, data.file_start_position);
+                Tag::new(
+                    TagNumber::Application(ApplicationTagNumber::OctetString),
+                    data.file_data.len() as u32,
+                )
+                .encode(writer);
+                #[cfg(feature = "alloc")]
+                writer.extend_from_slice(&data.file_data);
+                #[cfg(not(feature = "alloc"))]
+                writer.extend_from_slice(data.file_data);
+                encode_closing_tag(writer, AtomicReadFile::TAG_STREAM_ACCESS);
+            }
+            AtomicReadFileData::Record(data) => {
+                encode_opening_tag(writer, AtomicReadFile::TAG_RECORD_ACCESS);
+                encode_application_signed(writer, data.file_start_record);
+                encode_application_unsigned(writer, data.returned_record_count as u64);
+                #[cfg(feature = "alloc")]
+                writer.extend_from_slice(&data.buf);
+                #[cfg(not(feature = "alloc"))]
+                writer.extend_from_slice(data.buf);
+                encode_closing_tag(writer, AtomicReadFile::TAG_RECORD_ACCESS);
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Boolean),
+            "AtomicReadFileAck decode end_of_file",
+        )?;
+        let end_of_file = tag.value > 0;
+
+        // the choice is distinguished by the opening tag number
+        let (inner_buf, tag_number) = get_tagged_body(reader, buf)?;
+        let mut inner_reader = Reader::new_with_len(inner_buf.len());
+        let start = decode_application_signed(
+            &mut inner_reader,
+            inner_buf,
+            "AtomicReadFileAck decode start",
+        )?;
+
+        let data = match tag_number {
+            AtomicReadFile::TAG_STREAM_ACCESS => {
+                let tag = Tag::decode_expected(
+                    &mut inner_reader,
+                    inner_buf,
+                    TagNumber::Application(ApplicationTagNumber::OctetString),
+                    "AtomicReadFileAck decode file_data",
+                )?;
+                let file_data = inner_reader.read_slice(tag.value as usize, inner_buf)?;
+                if !inner_reader.eof() {
+                    return Err(Error::InvalidValue(
+                        "AtomicReadFileAck unexpected data after file_data",
+                    ));
+                }
+                AtomicReadFileData::Stream(StreamData::new(start, file_data))
+            }
+            AtomicReadFile::TAG_RECORD_ACCESS => {
+                let count = decode_application_unsigned(
+                    &mut inner_reader,
+                    inner_buf,
+                    "AtomicReadFileAck decode returned_record_count",
+                )?;
+                let records = &inner_buf[inner_reader.index..];
+                AtomicReadFileData::Record(RecordData::new_from_buf(start, count, records))
+            }
+            x => {
+                return Err(Error::InvalidVariant((
+                    "AtomicReadFileAck decode access method",
+                    x as u32,
+                )))
+            }
+        };
+
+        Ok(Self { end_of_file, data })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+    };
+
+    use super::{AtomicReadFile, AtomicReadFileAck, AtomicReadFileData, FileAccessMethod};
+
+    // end_of_file true, record access starting at record 4 with 3 records
+    const RECORD_ACK: [u8; 16] = [
+        0x11, 0x1e, 0x31, 0x04, 0x21, 0x03, 0x62, b'a', b'b', 0x61, b'c', 0x63, b'd', b'e', b'f',
+        0x1f,
+    ];
+
+    #[test]
+    fn decode_record_ack() {
+        let buf = &RECORD_ACK;
+        let mut reader = Reader::new_with_len(buf.len());
+        let ack = AtomicReadFileAck::decode(&mut reader, buf).unwrap();
+        assert!(ack.end_of_file);
+        assert!(reader.eof());
+
+        let AtomicReadFileData::Record(data) = &ack.data else {
+            panic!("expected record access");
+        };
+        assert_eq!(data.file_start_record, 4);
+        assert_eq!(data.returned_record_count, 3);
+        let mut records = data.records();
+        assert_eq!(records.next().unwrap().unwrap(), b"ab");
+        assert_eq!(records.next().unwrap().unwrap(), b"c");
+        assert_eq!(records.next().unwrap().unwrap(), b"def");
+        assert!(records.next().is_none());
+
+        // encoding writes the service choice followed by the same bytes
+        let mut out = [0; 32];
+        let mut writer = Writer::new(&mut out);
+        ack.encode(&mut writer);
+        assert_eq!(&writer.to_bytes()[1..], buf);
+    }
+
+    #[test]
+    fn decode_record_ack_missing_records() {
+        // claims 3 records but only contains 1
+        let buf = [0x10, 0x1e, 0x31, 0x00, 0x21, 0x03, 0x61, b'a', 0x1f];
+        let mut reader = Reader::new_with_len(buf.len());
+        let ack = AtomicReadFileAck::decode(&mut reader, &buf).unwrap();
+        let AtomicReadFileData::Record(data) = &ack.data else {
+            panic!("expected record access");
+        };
+        let mut records = data.records();
+        assert!(records.next().unwrap().is_ok());
+        assert!(records.next().unwrap().is_err());
+        assert!(records.next().is_none());
+    }
+
+    #[test]
+    fn stream_request_and_ack() {
+        // read 512 octets of File:1 starting at 1024
+        let request = AtomicReadFile::new(
+            ObjectId::new(ObjectType::ObjectFile, 1),
+            FileAccessMethod::Stream {
+                file_start_position: 1024,
+                requested_octet_count: 512,
+            },
+        );
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        assert_eq!(
+            writer.to_bytes(),
+            [0xc4, 0x02, 0x80, 0x00, 0x01, 0x0e, 0x32, 0x04, 0x00, 0x22, 0x02, 0x00, 0x0f]
+        );
+
+        // the last 4 octets of the file
+        let buf = [0x11, 0x0e, 0x32, 0x04, 0x00, 0x64, 1, 2, 3, 4, 0x0f];
+        let mut reader = Reader::new_with_len(buf.len());
+        let ack = AtomicReadFileAck::decode(&mut reader, &buf).unwrap();
+        assert!(ack.end_of_file);
+        let AtomicReadFileData::Stream(data) = &ack.data else {
+            panic!("expected stream access");
+        };
+        assert_eq!(data.file_start_position, 1024);
+        assert_eq!(data.file_data, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn stream_ack_with_trailing_data_is_error() {
+        // an extra unsigned value after the file data inside the stream access block
+        let buf = [0x11, 0x0e, 0x31, 0x00, 0x62, 1, 2, 0x21, 0x05, 0x0f];
+        let mut reader = Reader::new_with_len(buf.len());
+        assert!(AtomicReadFileAck::decode(&mut reader, &buf).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{
+    application_protocol::confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
+    common::{
+        error::Error,
+        helper::{
+            decode_signed, encode_application_object_id, encode_application_signed,
+            encode_application_unsigned, encode_closing_tag, encode_context_signed,
+            encode_opening_tag,
+        },
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+    network_protocol::data_link::DataLink,
+};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FileWriteData<'a> {
+    // a file_start_position of -1 appends to the end of the file
+    Stream {
+        file_start_position: i32,
+        file_data: &'a [u8],
+    },
+    // a file_start_record of -1 appends to the end of the file
+    Record {
+        file_start_record: i32,
+        file_records: &'a [&'a [u8]],
+    },
+}
+
+// writes to a File object (the reply is an AtomicWriteFileAck)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AtomicWriteFile<'a> {
+    pub object_id: ObjectId, // e.g. ObjectFile:1
+    pub data: FileWriteData<'a>,
+}
+
+impl<'a> AtomicWriteFile<'a> {
+    const TAG_STREAM_ACCESS: u8 = 0;
+    const TAG_RECORD_ACCESS: u8 = 1;
+
+    pub fn new(object_id: ObjectId, data: FileWriteData<'a>) -> Self {
+        Self { object_id, data }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_object_id(writer, &self.object_id);
+        match &self.data {
+            FileWriteData::Stream {
+                file_start_position,
+                file_data,
+            } => {
+                encode_opening_tag(writer, Self::TAG_STREAM_ACCESS);
+                encode_application_signed(writer, *file_start_position);
+                encode_octet_string(writer, file_data);
+                encode_closing_tag(writer, Self::TAG_STREAM_ACCESS);
+            }
+            FileWriteData::Record {
+                file_start_record,
+                file_records,
+            } => {
+                encode_opening_tag(writer, Self::TAG_RECORD_ACCESS);
+                encode_application_signed(writer, *file_start_record);
+                encode_application_unsigned(writer, file_records.len() as u64);
+                for record in file_records.iter() {
+                    encode_octet_string(writer, record);
+                }
+                encode_closing_tag(writer, Self::TAG_RECORD_ACCESS);
+            }
+        }
+    }
+}
+
+fn encode_octet_string(writer: &mut Writer, data: &[u8]) {
+    Tag::new(
+        TagNumber::Application(ApplicationTagNumber::OctetString),
+        data.len() as u32,
+    )
+    .encode(writer);
+    writer.extend_from_slice(data);
+}
+
+// where the data was actually written (useful when appending)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AtomicWriteFileAck {
+    Stream { file_start_position: i32 },
+    Record { file_start_record: i32 },
+}
+
+impl<'a> TryFrom<DataLink<'a>> for AtomicWriteFileAck {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        let ack: ComplexAck = value.try_into()?;
+        match ack.service {
+            ComplexAckService::AtomicWriteFile(ack) => Ok(ack),
+            _ => Err(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService AtomicWriteFileAck",
+            )),
+        }
+    }
+}
+
+impl AtomicWriteFileAck {
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::AtomicWriteFile as u8);
+        match self {
+            Self::Stream {
+                file_start_position,
+            } => encode_context_signed(
+                writer,
+                AtomicWriteFile::TAG_STREAM_ACCESS,
+                *file_start_position,
+            ),
+            Self::Record { file_start_record } => encode_context_signed(
+                writer,
+                AtomicWriteFile::TAG_RECORD_ACCESS,
+                *file_start_record,
+            ),
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode(reader, buf)?;
+        match tag.number {
+            TagNumber::ContextSpecific(AtomicWriteFile::TAG_STREAM_ACCESS) => {
+                let file_start_position = decode_signed(tag.value, reader, buf)?;
+                Ok(Self::Stream {
+                    file_start_position,
+                })
+            }
+            TagNumber::ContextSpecific(AtomicWriteFile::TAG_RECORD_ACCESS) => {
+                let file_start_record = decode_signed(tag.value, reader, buf)?;
+                Ok(Self::Record { file_start_record })
+            }
+            number => Err(Error::TagNotSupported((
+                "AtomicWriteFileAck decode access method",
+                number,
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+    };
+
+    use super::{AtomicWriteFile, AtomicWriteFileAck, FileWriteData};
+
+    #[test]
+    fn encode_stream_and_record_requests() {
+        let file = ObjectId::new(ObjectType::ObjectFile, 1);
+
+        // append 3 octets to the end of the file
+        let request = AtomicWriteFile::new(
+            file,
+            FileWriteData::Stream {
+                file_start_position: -1,
+                file_data: b"abc",
+            },
+        );
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        #[rustfmt::skip]
+        let expected = [
+            0xc4, 0x02, 0x80, 0x00, 0x01,
+            0x0e, 0x31, 0xff, 0x63, b'a', b'b', b'c', 0x0f,
+        ];
+        assert_eq!(writer.to_bytes(), &expected);
+
+        // overwrite records 2 and 3
+        let request = AtomicWriteFile::new(
+            file,
+            FileWriteData::Record {
+                file_start_record: 2,
+                file_records: &[b"de", b"f"],
+            },
+        );
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        #[rustfmt::skip]
+        let expected = [
+            0xc4, 0x02, 0x80, 0x00, 0x01,
+            0x1e, 0x31, 0x02, 0x21, 0x02, 0x62, b'd', b'e', 0x61, b'f', 0x1f,
+        ];
+        assert_eq!(writer.to_bytes(), &expected);
+    }
+
+    #[test]
+    fn decode_ack() {
+        // appended at position 1024 (the service choice is followed by the context tagged position)
+        let buf = [0x07, 0x0a, 0x04, 0x00];
+        let mut reader = Reader::new_with_len(buf.len() - 1);
+        let ack = AtomicWriteFileAck::decode(&mut reader, &buf[1..]).unwrap();
+        assert_eq!(
+            ack,
+            AtomicWriteFileAck::Stream {
+                file_start_position: 1024
+            }
+        );
+
+        let mut out = [0; 4];
+        let mut writer = Writer::new(&mut out);
+        ack.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), buf);
+
+        let buf = [0x19, 0x02];
+        let mut reader = Reader::new_with_len(buf.len());
+        let ack = AtomicWriteFileAck::decode(&mut reader, &buf).unwrap();
+        assert_eq!(
+            ack,
+            AtomicWriteFileAck::Record {
+                file_start_record: 2
+            }
+        );
+    }
+}
@@ -1,15 +1,22 @@
 // change of value
 
 #[cfg(feature = "alloc")]
-use alloc::vec::Vec;
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
 
 use crate::{
-    application_protocol::primitives::data_value::ApplicationDataValue,
+    application_protocol::{
+        primitives::{
+            data_value::ApplicationDataValue, object_reference::ObjectPropertyReference,
+            recipient::Recipient,
+        },
+        unconfirmed::UnconfirmedServiceChoice,
+    },
     common::{
         error::Error,
         helper::{
-            decode_unsigned, encode_context_bool, encode_context_object_id,
-            encode_context_unsigned, get_tagged_body_for_tag,
+            decode_optional_context_tag, decode_unsigned, encode_closing_tag, encode_context_bool,
+            encode_context_enumerated, encode_context_object_id, encode_context_unsigned,
+            encode_opening_tag, get_tagged_body_for_tag,
         },
         io::{Reader, Writer},
         object_id::{ObjectId, ObjectType},
@@ -32,7 +39,7 @@ pub struct CovNotification<'a> {
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct CovNotificationValues<'a> {
-    _property_results: &'a [PropertyResult<'a>], // use this when encoding is implemented
+    pub property_results: &'a [PropertyResult<'a>],
     object_id: ObjectId,
     buf: &'a [u8],
 }
@@ -41,16 +48,48 @@ pub struct CovNotificationValues<'a> {
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct CovNotificationValues<'a> {
-    pub property_results: Vec<PropertyResult<'a>>, // use this when encoding is implemented
+    pub property_results: Vec<PropertyResult<'a>>,
     pub object_id: ObjectId,
 }
 
 impl<'a> CovNotificationValues<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(object_id: ObjectId, property_results: &'a [PropertyResult<'a>]) -> Self {
+        Self {
+            property_results,
+            object_id,
+            buf: &[],
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(object_id: ObjectId, property_results: Vec<PropertyResult<'a>>) -> Self {
+        Self {
+            property_results,
+            object_id,
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.extend_from_slice(self.buf);
+        for item in self.property_results {
+            item.encode(writer);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn encode(&self, writer: &mut Writer) {
+        for item in self.property_results.iter() {
+            item.encode(writer);
+        }
+    }
+
     #[cfg(not(feature = "alloc"))]
     pub fn decode(_reader: &mut Reader, buf: &'a [u8], object_id: ObjectId) -> Result<Self, Error> {
         Ok(CovNotificationValues {
             buf,
-            _property_results: &[],
+            property_results: &[],
             object_id,
         })
     }
@@ -75,40 +114,70 @@ impl<'a> CovNotificationValues<'a> {
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct PropertyResult<'a> {
     pub id: PropertyId,
+    pub array_index: Option<u32>,
     pub value: ApplicationDataValue<'a>,
+    pub priority: Option<u8>,
 }
 
 impl<'a> PropertyResult<'a> {
+    const TAG_PROPERTY_ID: u8 = 0;
+    const TAG_ARRAY_INDEX: u8 = 1;
+    const TAG_VALUE: u8 = 2;
+    const TAG_PRIORITY: u8 = 3;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_enumerated(writer, Self::TAG_PROPERTY_ID, &self.id);
+
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::TAG_ARRAY_INDEX, array_index);
+        }
+
+        encode_opening_tag(writer, Self::TAG_VALUE);
+        self.value.encode(writer);
+        encode_closing_tag(writer, Self::TAG_VALUE);
+
+        if let Some(priority) = self.priority {
+            encode_context_unsigned(writer, Self::TAG_PRIORITY, priority as u32);
+        }
+    }
+
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
     pub fn decode(reader: &mut Reader, buf: &'a [u8], object_id: &ObjectId) -> Result<Self, Error> {
         // property id
         let tag = Tag::decode_expected(
             reader,
             buf,
-            TagNumber::ContextSpecific(0),
+            TagNumber::ContextSpecific(Self::TAG_PROPERTY_ID),
             "CovNotification next property_id",
         )?;
         let property_id: PropertyId = (decode_unsigned(tag.value, reader, buf)? as u32).into();
 
+        // array index (optional)
+        let array_index = match decode_optional_context_tag(reader, buf, Self::TAG_ARRAY_INDEX)? {
+            Some(tag) => Some(decode_unsigned(tag.value, reader, buf)? as u32),
+            None => None,
+        };
+
         // value
-        Tag::decode_expected(
-            reader,
-            buf,
-            TagNumber::ContextSpecificOpening(2),
-            "CovNotification next expected value opening tag",
-        )?;
-        let tag = Tag::decode(reader, buf)?;
-        let value = ApplicationDataValue::decode(&tag, object_id, &property_id, reader, buf)?;
-        Tag::decode_expected(
+        let value = ApplicationDataValue::decode_wrapped(
+            Self::TAG_VALUE,
+            object_id,
+            &property_id,
             reader,
             buf,
-            TagNumber::ContextSpecificClosing(2),
-            "CovNotification next expected value closing tag",
         )?;
 
+        // priority (optional)
+        let priority = match decode_optional_context_tag(reader, buf, Self::TAG_PRIORITY)? {
+            Some(tag) => Some(decode_unsigned(tag.value, reader, buf)? as u8),
+            None => None,
+        };
+
         Ok(PropertyResult {
             id: property_id,
+            array_index,
             value,
+            priority,
         })
     }
 }
@@ -120,6 +189,18 @@ impl<'a> CovNotification<'a> {
     const TAG_LIFETIME: u8 = 3;
     const TAG_LIST_OF_VALUES: u8 = 4;
 
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(UnconfirmedServiceChoice::CovNotification as u8);
+        encode_context_unsigned(writer, Self::TAG_PROCESS_ID, self.process_id);
+        encode_context_object_id(writer, Self::TAG_DEVICE_ID, &self.device_id);
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+        encode_context_unsigned(writer, Self::TAG_LIFETIME, self.time_remaining_seconds);
+
+        encode_opening_tag(writer, Self::TAG_LIST_OF_VALUES);
+        self.values.encode(writer);
+        encode_closing_tag(writer, Self::TAG_LIST_OF_VALUES);
+    }
+
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
         // parse a tag, starting from after the pdu type and service choice
@@ -225,6 +306,7 @@ pub struct SubscribeCov {
     object_id: ObjectId,
     issue_confirmed_notifications: bool,
     lifetime_seconds: u32, // zero for indefinite
+    cancel: bool,          // when set the two fields above are not sent
 }
 
 impl SubscribeCov {
@@ -244,9 +326,25 @@ impl SubscribeCov {
             object_id,
             issue_confirmed_notifications,
             lifetime_seconds,
+            cancel: false,
+        }
+    }
+
+    // cancels an existing subscription with the same process_id and object_id
+    pub fn cancel(process_id: u32, object_id: ObjectId) -> Self {
+        Self {
+            process_id,
+            object_id,
+            issue_confirmed_notifications: false,
+            lifetime_seconds: 0,
+            cancel: true,
         }
     }
 
+    pub fn is_cancellation(&self) -> bool {
+        self.cancel
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
         // subscriber process_id
         encode_context_unsigned(writer, Self::TAG_PROCESS_ID, self.process_id);
@@ -254,6 +352,11 @@ impl SubscribeCov {
         // object_id
         encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
 
+        // a cancellation omits the remaining fields
+        if self.cancel {
+            return;
+        }
+
         // issue confirmed notifications
         encode_context_bool(
             writer,
@@ -265,3 +368,381 @@ impl SubscribeCov {
         encode_context_unsigned(writer, Self::TAG_LIFETIME, self.lifetime_seconds);
     }
 }
+
+// an entry in the Active_COV_Subscriptions property of a device
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovSubscription<'a> {
+    pub recipient: Recipient<'a>,
+    pub process_id: u32,
+    pub monitored_property: ObjectPropertyReference,
+    pub issue_confirmed_notifications: bool,
+    pub time_remaining_seconds: u32,
+    pub cov_increment: Option<f32>,
+}
+
+impl<'a> CovSubscription<'a> {
+    const TAG_RECIPIENT: u8 = 0;
+    const TAG_MONITORED_PROPERTY: u8 = 1;
+    const TAG_CONFIRMED: u8 = 2;
+    const TAG_TIME_REMAINING: u8 = 3;
+    const TAG_COV_INCREMENT: u8 = 4;
+
+    // tags inside the recipient process
+    const TAG_RECIPIENT_PROCESS_RECIPIENT: u8 = 0;
+    const TAG_RECIPIENT_PROCESS_ID: u8 = 1;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_opening_tag(writer, Self::TAG_RECIPIENT);
+        encode_opening_tag(writer, Self::TAG_RECIPIENT_PROCESS_RECIPIENT);
+        self.recipient.encode(writer);
+        encode_closing_tag(writer, Self::TAG_RECIPIENT_PROCESS_RECIPIENT);
+        encode_context_unsigned(writer, Self::TAG_RECIPIENT_PROCESS_ID, self.process_id);
+        encode_closing_tag(writer, Self::TAG_RECIPIENT);
+
+        encode_opening_tag(writer, Self::TAG_MONITORED_PROPERTY);
+        self.monitored_property.encode(writer);
+        encode_closing_tag(writer, Self::TAG_MONITORED_PROPERTY);
+
+        encode_context_bool(
+            writer,
+            Self::TAG_CONFIRMED,
+            self.issue_confirmed_notifications,
+        );
+        encode_context_unsigned(
+            writer,
+            Self::TAG_TIME_REMAINING,
+            self.time_remaining_seconds,
+        );
+
+        if let Some(cov_increment) = self.cov_increment {
+            Tag::new(TagNumber::ContextSpecific(Self::TAG_COV_INCREMENT), 4).encode(writer);
+            writer.extend_from_slice(&cov_increment.to_be_bytes());
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // recipient process
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_RECIPIENT,
+            "CovSubscription decode recipient process",
+        )?;
+        let mut inner_reader = Reader::new_with_len(inner_buf.len());
+        let recipient_buf = get_tagged_body_for_tag(
+            &mut inner_reader,
+            inner_buf,
+            Self::TAG_RECIPIENT_PROCESS_RECIPIENT,
+            "CovSubscription decode recipient",
+        )?;
+        let recipient = Recipient::decode(
+            &mut Reader::new_with_len(recipient_buf.len()),
+            recipient_buf,
+        )?;
+        let tag = Tag::decode_expected(
+            &mut inner_reader,
+            inner_buf,
+            TagNumber::ContextSpecific(Self::TAG_RECIPIENT_PROCESS_ID),
+            "CovSubscription decode process_id",
+        )?;
+        let process_id = decode_unsigned(tag.value, &mut inner_reader, inner_buf)? as u32;
+
+        // monitored property reference
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_MONITORED_PROPERTY,
+            "CovSubscription decode monitored property",
+        )?;
+        let monitored_property =
+            ObjectPropertyReference::decode(&mut Reader::new_with_len(inner_buf.len()), inner_buf)?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_CONFIRMED),
+            "CovSubscription decode issue_confirmed_notifications",
+        )?;
+        let issue_confirmed_notifications = decode_unsigned(tag.value, reader, buf)? > 0;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_TIME_REMAINING),
+            "CovSubscription decode time_remaining",
+        )?;
+        let time_remaining_seconds = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        let cov_increment = match decode_optional_context_tag(reader, buf, Self::TAG_COV_INCREMENT)?
+        {
+            Some(tag) => {
+                tag.expect_value("CovSubscription decode cov_increment", 4)?;
+                Some(f32::from_be_bytes(reader.read_bytes(buf)?))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            recipient,
+            process_id,
+            monitored_property,
+            issue_confirmed_notifications,
+            time_remaining_seconds,
+            cov_increment,
+        })
+    }
+}
+
+// the value of the Active_COV_Subscriptions property
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovSubscriptionList<'a> {
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovSubscriptionList<'a> {
+    pub subscriptions: Vec<CovSubscription<'a>>,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> CovSubscriptionList<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new_from_buf(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(subscriptions: Vec<CovSubscription<'a>>) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            subscriptions,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.extend_from_slice(self.buf);
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn encode(&self, writer: &mut Writer) {
+        for subscription in self.subscriptions.iter() {
+            subscription.encode(writer);
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn decode(buf: &'a [u8]) -> Result<Self, Error> {
+        Ok(Self::new_from_buf(buf))
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn decode(buf: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new_with_len(buf.len());
+        let mut subscriptions = Vec::new();
+        while !reader.eof() {
+            let subscription = CovSubscription::decode(&mut reader, buf)?;
+            subscriptions.push(subscription);
+        }
+        Ok(Self::new(subscriptions))
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ CovSubscriptionList<'a> {
+    type Item = Result<CovSubscription<'a>, Error>;
+    type IntoIter = CovSubscriptionIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CovSubscriptionIter::new(self.buf)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovSubscriptionIter<'a> {
+    reader: Reader,
+    buf: &'a [u8],
+}
+
+impl<'a> CovSubscriptionIter<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self {
+            reader: Reader::new_with_len(buf.len()),
+            buf,
+        }
+    }
+}
+
+impl<'a> Iterator for CovSubscriptionIter<'a> {
+    type Item = Result<CovSubscription<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.eof() {
+            return None;
+        }
+
+        Some(CovSubscription::decode(&mut self.reader, self.buf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu,
+            primitives::data_value::{ApplicationDataValue, BitString},
+            services::change_of_value::{CovNotification, CovNotificationValues, PropertyResult},
+            unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+        network_protocol::{data_link::DataLink, network_pdu::NetworkMessage},
+    };
+
+    // unconfirmed cov notification from device 1 for analog-value:1 (present value 100.0, status flags clear)
+    // the second value is an array element written at priority 8 to test the optional fields
+    #[rustfmt::skip]
+    const COV_NOTIFICATION: [u8; 53] = [
+        0x81, 0x0a, 0x00, 0x35, 0x01, 0x00,
+        0x10, 0x02,
+        0x09, 0x12,
+        0x1c, 0x02, 0x00, 0x00, 0x01,
+        0x2c, 0x00, 0x80, 0x00, 0x01,
+        0x39, 0x3c,
+        0x4e,
+        0x09, 0x55, 0x2e, 0x44, 0x42, 0xc8, 0x00, 0x00, 0x2f,
+        0x09, 0x6f, 0x2e, 0x82, 0x04, 0x00, 0x2f,
+        0x09, 0x57, 0x19, 0x08, 0x2e, 0x44, 0x41, 0x20, 0x00, 0x00, 0x2f, 0x39, 0x08,
+        0x4f,
+    ];
+
+    #[test]
+    fn decode_unconfirmed_cov_notification() {
+        let buf = &COV_NOTIFICATION;
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, buf).unwrap();
+        let Some(NetworkMessage::Apdu(ApplicationPdu::UnconfirmedRequest(
+            UnconfirmedRequest::CovNotification(notification),
+        ))) = message.into_npdu().map(|x| x.network_message)
+        else {
+            panic!("expected a cov notification");
+        };
+
+        assert_eq!(notification.process_id, 0x12);
+        assert_eq!(
+            notification.device_id,
+            ObjectId::new(ObjectType::ObjectDevice, 1)
+        );
+        assert_eq!(
+            notification.object_id,
+            ObjectId::new(ObjectType::ObjectAnalogValue, 1)
+        );
+        assert_eq!(notification.time_remaining_seconds, 60);
+
+        #[cfg(feature = "alloc")]
+        let mut it = notification.values.property_results.clone().into_iter();
+        #[cfg(not(feature = "alloc"))]
+        let mut it = (&notification.values).into_iter().map(|x| x.unwrap());
+
+        let value = it.next().unwrap();
+        assert_eq!(value.id, PropertyId::PropPresentValue);
+        assert!(matches!(value.value, ApplicationDataValue::Real(x) if x == 100.0));
+        assert_eq!(value.array_index, None);
+        assert_eq!(value.priority, None);
+
+        let value = it.next().unwrap();
+        assert_eq!(value.id, PropertyId::PropStatusFlags);
+        assert!(matches!(
+            value.value,
+            ApplicationDataValue::BitString(BitString::Status(_))
+        ));
+
+        let value = it.next().unwrap();
+        assert_eq!(value.id, PropertyId::PropPriorityArray);
+        assert!(matches!(value.value, ApplicationDataValue::Real(x) if x == 10.0));
+        assert_eq!(value.array_index, Some(8));
+        assert_eq!(value.priority, Some(8));
+
+        assert!(it.next().is_none());
+    }
+
+    #[test]
+    fn encode_cov_notification_round_trip() {
+        let buf = &COV_NOTIFICATION;
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, buf).unwrap();
+        let Some(NetworkMessage::Apdu(ApplicationPdu::UnconfirmedRequest(request))) =
+            message.into_npdu().map(|x| x.network_message)
+        else {
+            panic!("expected an unconfirmed request");
+        };
+
+        // the apdu starts after the 4 byte bvlc header and the 2 byte npdu header
+        let mut out = [0; 64];
+        let mut writer = Writer::new(&mut out);
+        request.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &buf[6..]);
+    }
+
+    #[test]
+    fn encode_cov_notification_from_values() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogValue, 1);
+        let property_results = [PropertyResult {
+            id: PropertyId::PropPriorityArray,
+            array_index: Some(8),
+            value: ApplicationDataValue::Real(10.0),
+            priority: Some(8),
+        }];
+        #[cfg(feature = "alloc")]
+        let values = CovNotificationValues::new(object_id, property_results.to_vec());
+        #[cfg(not(feature = "alloc"))]
+        let values = CovNotificationValues::new(object_id, &property_results);
+        let notification = CovNotification {
+            process_id: 0x12,
+            device_id: ObjectId::new(ObjectType::ObjectDevice, 1),
+            object_id,
+            time_remaining_seconds: 60,
+            values,
+        };
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        UnconfirmedRequest::CovNotification(notification).encode(&mut writer);
+        let buf = writer.to_bytes();
+
+        let mut reader = Reader::new_with_len(buf.len());
+        reader.index = 1;
+        let Ok(UnconfirmedRequest::CovNotification(decoded)) =
+            UnconfirmedRequest::decode(&mut reader, buf)
+        else {
+            panic!("expected a cov notification");
+        };
+        assert_eq!(decoded.process_id, 0x12);
+        assert_eq!(decoded.object_id, object_id);
+        assert_eq!(decoded.time_remaining_seconds, 60);
+
+        #[cfg(feature = "alloc")]
+        let mut it = decoded.values.property_results.clone().into_iter();
+        #[cfg(not(feature = "alloc"))]
+        let mut it = (&decoded.values).into_iter().map(|x| x.unwrap());
+
+        let value = it.next().unwrap();
+        assert_eq!(value.id, PropertyId::PropPriorityArray);
+        assert!(matches!(value.value, ApplicationDataValue::Real(x) if x == 10.0));
+        assert_eq!(value.array_index, Some(8));
+        assert_eq!(value.priority, Some(8));
+        assert!(it.next().is_none());
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{
+    application_protocol::{
+        confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
+        services::write_property_multiple::WritePropertyValue,
+    },
+    common::{
+        error::Error,
+        helper::{
+            encode_application_object_id, encode_closing_tag, encode_context_object_id,
+            encode_context_unsigned, encode_opening_tag,
+        },
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+    network_protocol::data_link::DataLink,
+};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+// either let the server pick the instance number or ask for a specific object
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ObjectSpecifier {
+    ObjectType(ObjectType),
+    ObjectId(ObjectId),
+}
+
+// creates an object, optionally setting some of its properties (the reply is a CreateObjectAck)
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CreateObject<'a> {
+    pub object_specifier: ObjectSpecifier,
+    pub initial_values: &'a [WritePropertyValue<'a>],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CreateObject<'a> {
+    pub object_specifier: ObjectSpecifier,
+    pub initial_values: Vec<WritePropertyValue<'a>>,
+}
+
+impl<'a> CreateObject<'a> {
+    const TAG_OBJECT_SPECIFIER: u8 = 0;
+    const TAG_INITIAL_VALUES: u8 = 1;
+
+    // tags inside the object specifier
+    const TAG_OBJECT_TYPE: u8 = 0;
+    const TAG_OBJECT_ID: u8 = 1;
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(
+        object_specifier: ObjectSpecifier,
+        initial_values: &'a [WritePropertyValue<'a>],
+    ) -> Self {
+        Self {
+            object_specifier,
+            initial_values,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(
+        object_specifier: ObjectSpecifier,
+        initial_values: Vec<WritePropertyValue<'a>>,
+    ) -> Self {
+        Self {
+            object_specifier,
+            initial_values,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_opening_tag(writer, Self::TAG_OBJECT_SPECIFIER);
+        match &self.object_specifier {
+            ObjectSpecifier::ObjectType(object_type) => {
+                // enumerated values are encoded like unsigned values
+                encode_context_unsigned(writer, Self::TAG_OBJECT_TYPE, u32::from(*object_type))
+            }
+            ObjectSpecifier::ObjectId(object_id) => {
+                encode_context_object_id(writer, Self::TAG_OBJECT_ID, object_id)
+            }
+        }
+        encode_closing_tag(writer, Self::TAG_OBJECT_SPECIFIER);
+
+        // the list of initial values is optional
+        if !self.initial_values.is_empty() {
+            encode_opening_tag(writer, Self::TAG_INITIAL_VALUES);
+            for value in self.initial_values.iter() {
+                value.encode(writer);
+            }
+            encode_closing_tag(writer, Self::TAG_INITIAL_VALUES);
+        }
+    }
+}
+
+// the id of the object that was created
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CreateObjectAck {
+    pub object_id: ObjectId,
+}
+
+impl<'a> TryFrom<DataLink<'a>> for CreateObjectAck {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        let ack: ComplexAck = value.try_into()?;
+        match ack.service {
+            ComplexAckService::CreateObject(ack) => Ok(ack),
+            _ => Err(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService CreateObjectAck",
+            )),
+        }
+    }
+}
+
+impl CreateObjectAck {
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::CreateObject as u8);
+        encode_application_object_id(writer, &self.object_id);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::ObjectId),
+            "CreateObjectAck object_id",
+        )?;
+        let object_id = ObjectId::decode(tag.value, reader, buf)?;
+        Ok(Self { object_id })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            primitives::data_value::ApplicationDataValueWrite,
+            services::write_property_multiple::WritePropertyValue,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+        network_protocol::data_link::DataLink,
+    };
+
+    use super::{CreateObject, CreateObjectAck, ObjectSpecifier};
+
+    #[test]
+    fn encode_create_object() {
+        // any analog value with an initial cov increment
+        let cov_increment = WritePropertyValue::new(
+            PropertyId::PropCovIncrement,
+            ApplicationDataValueWrite::Real(0.5),
+        );
+        let specifier = ObjectSpecifier::ObjectType(ObjectType::ObjectAnalogValue);
+
+        #[cfg(feature = "alloc")]
+        let request = CreateObject::new(specifier, alloc::vec![cov_increment]);
+        #[cfg(not(feature = "alloc"))]
+        let values = [cov_increment];
+        #[cfg(not(feature = "alloc"))]
+        let request = CreateObject::new(specifier, &values);
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        #[rustfmt::skip]
+        let expected = [
+            0x0e, 0x09, 0x02, 0x0f,
+            0x1e, 0x09, 0x16, 0x2e, 0x44, 0x3f, 0x00, 0x00, 0x00, 0x2f, 0x1f,
+        ];
+        assert_eq!(writer.to_bytes(), &expected);
+
+        // a specific trend log without initial values
+        let object_id = ObjectId::new(ObjectType::ObjectTrendlog, 3);
+        #[cfg(feature = "alloc")]
+        let request = CreateObject::new(ObjectSpecifier::ObjectId(object_id), alloc::vec![]);
+        #[cfg(not(feature = "alloc"))]
+        let request = CreateObject::new(ObjectSpecifier::ObjectId(object_id), &[]);
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        assert_eq!(
+            writer.to_bytes(),
+            [0x0e, 0x1c, 0x05, 0x00, 0x00, 0x03, 0x0f]
+        );
+    }
+
+    #[test]
+    fn decode_create_object_ack() {
+        // complex ack for invoke_id 4: AnalogValue:7 was created
+        let buf = [
+            0x81, 0x0a, 0x00, 0x0e, 0x01, 0x00, 0x30, 0x04, 0x0a, 0xc4, 0x00, 0x80, 0x00, 0x07,
+        ];
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, &buf).unwrap();
+        let ack: CreateObjectAck = message.try_into().unwrap();
+        assert_eq!(
+            ack.object_id,
+            ObjectId::new(ObjectType::ObjectAnalogValue, 7)
+        );
+
+        let mut out = [0; 6];
+        let mut writer = Writer::new(&mut out);
+        ack.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &buf[8..]);
+    }
+}
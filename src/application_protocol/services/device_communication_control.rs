@@ -0,0 +1,103 @@
+use crate::{
+    application_protocol::primitives::data_value::CharacterString,
+    common::{helper::encode_context_unsigned, io::Writer},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u32)]
+pub enum EnableDisable {
+    Enable = 0,
+    Disable = 1,
+    DisableInitiation = 2, // the device still responds but does not initiate any communication
+}
+
+// enables or disables communication on a device (the reply is a SimpleAck)
+// a time_duration of None or Some(0) disables communication indefinitely (until it is enabled again)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeviceCommunicationControl<'a> {
+    pub time_duration: Option<u16>, // minutes
+    pub enable_disable: EnableDisable,
+    pub password: Option<CharacterString<'a>>,
+}
+
+impl<'a> DeviceCommunicationControl<'a> {
+    const TAG_TIME_DURATION: u8 = 0;
+    const TAG_ENABLE_DISABLE: u8 = 1;
+    const TAG_PASSWORD: u8 = 2;
+
+    pub fn new(
+        time_duration: Option<u16>,
+        enable_disable: EnableDisable,
+        password: Option<CharacterString<'a>>,
+    ) -> Self {
+        Self {
+            time_duration,
+            enable_disable,
+            password,
+        }
+    }
+
+    pub fn is_indefinite(&self) -> bool {
+        matches!(self.time_duration, None | Some(0))
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        if let Some(time_duration) = self.time_duration {
+            encode_context_unsigned(writer, Self::TAG_TIME_DURATION, time_duration as u32);
+        }
+        // enumerated values are encoded like unsigned values
+        encode_context_unsigned(writer, Self::TAG_ENABLE_DISABLE, self.enable_disable as u32);
+        if let Some(password) = &self.password {
+            password.encode_context(Self::TAG_PASSWORD, writer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::primitives::data_value::CharacterString, common::io::Writer,
+    };
+
+    use super::{DeviceCommunicationControl, EnableDisable};
+
+    #[test]
+    fn encode_timed_disable() {
+        // silence the device for 90 minutes
+        let password = CharacterString::new("pw");
+        let request =
+            DeviceCommunicationControl::new(Some(90), EnableDisable::Disable, Some(password));
+        assert!(!request.is_indefinite());
+
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        assert_eq!(
+            writer.to_bytes(),
+            [0x09, 0x5a, 0x19, 0x01, 0x2b, 0x00, b'p', b'w']
+        );
+    }
+
+    #[test]
+    fn encode_indefinite_disable() {
+        // the duration is omitted
+        let request = DeviceCommunicationControl::new(None, EnableDisable::Disable, None);
+        assert!(request.is_indefinite());
+
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), [0x19, 0x01]);
+
+        // a duration of 0 is sent as is
+        let request = DeviceCommunicationControl::new(Some(0), EnableDisable::Disable, None);
+        assert!(request.is_indefinite());
+
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), [0x09, 0x00, 0x19, 0x01]);
+    }
+}
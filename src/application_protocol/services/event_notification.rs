@@ -0,0 +1,708 @@
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+use crate::{
+    application_protocol::primitives::{
+        data_value::{BitString, CharacterString},
+        time_stamp::TimeStamp,
+    },
+    common::{
+        error::Error,
+        helper::{
+            decode_context_real, decode_context_unsigned, decode_optional_context_tag, decode_real,
+            decode_unsigned, encode_closing_tag, encode_context_bool, encode_context_object_id,
+            encode_context_real, encode_context_unsigned, encode_opening_tag, get_tagged_body,
+            get_tagged_body_for_tag, with_context_tag,
+        },
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+        spec::{EventState, EventType, NotifyType, Status},
+        tag::{Tag, TagNumber},
+    },
+};
+
+// an alarm or event raised by a device, sent as a confirmed or unconfirmed request
+// ack_required and from_state are not sent with an ack notification (notify_type AckNotification)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EventNotification<'a> {
+    pub process_id: u32,
+    pub device_id: ObjectId,
+    pub object_id: ObjectId,
+    pub time_stamp: TimeStamp,
+    pub notification_class: u32,
+    pub priority: u8,
+    pub event_type: EventType,
+    pub message_text: Option<CharacterString<'a>>,
+    pub notify_type: NotifyType,
+    pub ack_required: Option<bool>,
+    pub from_state: Option<EventState>,
+    pub to_state: EventState,
+    pub event_values: Option<EventValues<'a>>,
+}
+
+// BACnetNotificationParameters
+// only the most common event types are decoded, the values of any other event type are kept as encoded
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EventValues<'a> {
+    ChangeOfBitstring {
+        referenced_bitstring: BitString<'a>,
+        status_flags: Status,
+    },
+    ChangeOfState {
+        new_state: PropertyState,
+        status_flags: Status,
+    },
+    ChangeOfValue {
+        new_value: ChangeOfValueNewValue<'a>,
+        status_flags: Status,
+    },
+    OutOfRange {
+        exceeding_value: f32,
+        status_flags: Status,
+        deadband: f32,
+        exceeded_limit: f32,
+    },
+    Other(OtherEventValues<'a>),
+}
+
+// the values of an event type that is not decoded, kept as encoded so that they can be written back
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OtherEventValues<'a> {
+    pub tag_number: u8, // the context tag of the event type
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OtherEventValues<'a> {
+    pub tag_number: u8, // the context tag of the event type
+    buf: Vec<u8>,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> OtherEventValues<'a> {
+    // buf is the encoded body between the opening and closing tag of the event type
+    #[cfg(not(feature = "alloc"))]
+    pub fn new_from_buf(tag_number: u8, buf: &'a [u8]) -> Self {
+        Self { tag_number, buf }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new_from_buf(tag_number: u8, buf: &[u8]) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            tag_number,
+            buf: buf.into(),
+            _phantom: &PHANTOM,
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn as_slice(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ChangeOfValueNewValue<'a> {
+    ChangedBits(BitString<'a>),
+    ChangedValue(f32),
+}
+
+// BACnetPropertyStates is a choice of (mostly enumerated) values
+// e.g. tag 1 is a binary-value where 0 is inactive and 1 is active
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PropertyState {
+    pub tag: u8,
+    pub value: u32,
+}
+
+impl<'a> EventNotification<'a> {
+    const TAG_PROCESS_ID: u8 = 0;
+    const TAG_DEVICE_ID: u8 = 1;
+    const TAG_OBJECT_ID: u8 = 2;
+    const TAG_TIME_STAMP: u8 = 3;
+    const TAG_NOTIFICATION_CLASS: u8 = 4;
+    const TAG_PRIORITY: u8 = 5;
+    const TAG_EVENT_TYPE: u8 = 6;
+    const TAG_MESSAGE_TEXT: u8 = 7;
+    const TAG_NOTIFY_TYPE: u8 = 8;
+    const TAG_ACK_REQUIRED: u8 = 9;
+    const TAG_FROM_STATE: u8 = 10;
+    const TAG_TO_STATE: u8 = 11;
+    const TAG_EVENT_VALUES: u8 = 12;
+
+    // the service choice is written by the confirmed or unconfirmed request
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_unsigned(writer, Self::TAG_PROCESS_ID, self.process_id);
+        encode_context_object_id(writer, Self::TAG_DEVICE_ID, &self.device_id);
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+        encode_opening_tag(writer, Self::TAG_TIME_STAMP);
+        self.time_stamp.encode(writer);
+        encode_closing_tag(writer, Self::TAG_TIME_STAMP);
+        encode_context_unsigned(
+            writer,
+            Self::TAG_NOTIFICATION_CLASS,
+            self.notification_class,
+        );
+        encode_context_unsigned(writer, Self::TAG_PRIORITY, self.priority as u32);
+        encode_context_unsigned(writer, Self::TAG_EVENT_TYPE, u32::from(&self.event_type));
+        if let Some(message_text) = &self.message_text {
+            message_text.encode_context(Self::TAG_MESSAGE_TEXT, writer);
+        }
+        encode_context_unsigned(
+            writer,
+            Self::TAG_NOTIFY_TYPE,
+            self.notify_type.clone() as u32,
+        );
+        if let Some(ack_required) = self.ack_required {
+            encode_context_bool(writer, Self::TAG_ACK_REQUIRED, ack_required);
+        }
+        if let Some(from_state) = &self.from_state {
+            encode_context_unsigned(writer, Self::TAG_FROM_STATE, from_state.clone() as u32);
+        }
+        encode_context_unsigned(writer, Self::TAG_TO_STATE, self.to_state.clone() as u32);
+        if let Some(event_values) = &self.event_values {
+            encode_opening_tag(writer, Self::TAG_EVENT_VALUES);
+            event_values.encode(writer);
+            encode_closing_tag(writer, Self::TAG_EVENT_VALUES);
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // parse a tag, starting from after the pdu type and service choice
+
+        let process_id = decode_context_unsigned(
+            reader,
+            buf,
+            Self::TAG_PROCESS_ID,
+            "EventNotification process_id",
+        )?;
+
+        // device_id
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_DEVICE_ID),
+            "EventNotification device_id",
+        )?;
+        let device_id = ObjectId::decode(tag.value, reader, buf)?;
+        if device_id.object_type != ObjectType::ObjectDevice {
+            return Err(Error::InvalidValue(
+                "expected device object type for EventNotification device_id field",
+            ));
+        }
+
+        // object_id
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_OBJECT_ID),
+            "EventNotification object_id",
+        )?;
+        let object_id = ObjectId::decode(tag.value, reader, buf)?;
+
+        let time_stamp = with_context_tag(
+            reader,
+            buf,
+            Self::TAG_TIME_STAMP,
+            "EventNotification time_stamp",
+            TimeStamp::decode,
+        )?;
+
+        let notification_class = decode_context_unsigned(
+            reader,
+            buf,
+            Self::TAG_NOTIFICATION_CLASS,
+            "EventNotification notification_class",
+        )?;
+
+        let priority = decode_context_unsigned(
+            reader,
+            buf,
+            Self::TAG_PRIORITY,
+            "EventNotification priority",
+        )?;
+        let priority = u8::try_from(priority)
+            .map_err(|_| Error::InvalidValue("EventNotification priority out of range"))?;
+
+        let event_type = decode_context_unsigned(
+            reader,
+            buf,
+            Self::TAG_EVENT_TYPE,
+            "EventNotification event_type",
+        )?;
+        let event_type = EventType::try_from(event_type)
+            .map_err(|x| Error::InvalidVariant(("EventNotification event_type", x)))?;
+
+        let message_text = match decode_optional_context_tag(reader, buf, Self::TAG_MESSAGE_TEXT)? {
+            Some(tag) => Some(CharacterString::decode(tag.value, reader, buf)?),
+            None => None,
+        };
+
+        let notify_type = decode_context_unsigned(
+            reader,
+            buf,
+            Self::TAG_NOTIFY_TYPE,
+            "EventNotification notify_type",
+        )?;
+        let notify_type = NotifyType::try_from(notify_type)
+            .map_err(|x| Error::InvalidVariant(("EventNotification notify_type", x)))?;
+
+        let ack_required = match decode_optional_context_tag(reader, buf, Self::TAG_ACK_REQUIRED)? {
+            Some(tag) => {
+                tag.expect_value("EventNotification ack_required", 1)?;
+                Some(reader.read_byte(buf)? > 0)
+            }
+            None => None,
+        };
+
+        let from_state = match decode_optional_context_tag(reader, buf, Self::TAG_FROM_STATE)? {
+            Some(tag) => {
+                let from_state = decode_unsigned(tag.value, reader, buf)? as u32;
+                let from_state = EventState::try_from(from_state)
+                    .map_err(|x| Error::InvalidVariant(("EventNotification from_state", x)))?;
+                Some(from_state)
+            }
+            None => None,
+        };
+
+        let to_state = decode_context_unsigned(
+            reader,
+            buf,
+            Self::TAG_TO_STATE,
+            "EventNotification to_state",
+        )?;
+        let to_state = EventState::try_from(to_state)
+            .map_err(|x| Error::InvalidVariant(("EventNotification to_state", x)))?;
+
+        let event_values = if reader.eof() {
+            None
+        } else {
+            let inner_buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::TAG_EVENT_VALUES,
+                "EventNotification event_values",
+            )?;
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            Some(EventValues::decode(&mut inner_reader, inner_buf)?)
+        };
+
+        Ok(Self {
+            process_id,
+            device_id,
+            object_id,
+            time_stamp,
+            notification_class,
+            priority,
+            event_type,
+            message_text,
+            notify_type,
+            ack_required,
+            from_state,
+            to_state,
+            event_values,
+        })
+    }
+}
+
+impl<'a> EventValues<'a> {
+    // the context tag of the inner opening tag is the event type
+    const TAG_CHANGE_OF_BITSTRING: u8 = 0;
+    const TAG_CHANGE_OF_STATE: u8 = 1;
+    const TAG_CHANGE_OF_VALUE: u8 = 2;
+    const TAG_OUT_OF_RANGE: u8 = 5;
+
+    // the status flags are context tag 1 for every supported event type
+    const TAG_STATUS_FLAGS: u8 = 1;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::ChangeOfBitstring {
+                referenced_bitstring,
+                status_flags,
+            } => {
+                encode_opening_tag(writer, Self::TAG_CHANGE_OF_BITSTRING);
+                referenced_bitstring.encode_context(0, writer);
+                Self::encode_status_flags(writer, status_flags);
+                encode_closing_tag(writer, Self::TAG_CHANGE_OF_BITSTRING);
+            }
+            Self::ChangeOfState {
+                new_state,
+                status_flags,
+            } => {
+                encode_opening_tag(writer, Self::TAG_CHANGE_OF_STATE);
+                encode_opening_tag(writer, 0);
+                encode_context_unsigned(writer, new_state.tag, new_state.value);
+                encode_closing_tag(writer, 0);
+                Self::encode_status_flags(writer, status_flags);
+                encode_closing_tag(writer, Self::TAG_CHANGE_OF_STATE);
+            }
+            Self::ChangeOfValue {
+                new_value,
+                status_flags,
+            } => {
+                encode_opening_tag(writer, Self::TAG_CHANGE_OF_VALUE);
+                encode_opening_tag(writer, 0);
+                match new_value {
+                    ChangeOfValueNewValue::ChangedBits(bits) => bits.encode_context(0, writer),
+                    ChangeOfValueNewValue::ChangedValue(value) => {
+                        encode_context_real(writer, 1, *value)
+                    }
+                }
+                encode_closing_tag(writer, 0);
+                Self::encode_status_flags(writer, status_flags);
+                encode_closing_tag(writer, Self::TAG_CHANGE_OF_VALUE);
+            }
+            Self::OutOfRange {
+                exceeding_value,
+                status_flags,
+                deadband,
+                exceeded_limit,
+            } => {
+                encode_opening_tag(writer, Self::TAG_OUT_OF_RANGE);
+                encode_context_real(writer, 0, *exceeding_value);
+                Self::encode_status_flags(writer, status_flags);
+                encode_context_real(writer, 2, *deadband);
+                encode_context_real(writer, 3, *exceeded_limit);
+                encode_closing_tag(writer, Self::TAG_OUT_OF_RANGE);
+            }
+            Self::Other(values) => {
+                encode_opening_tag(writer, values.tag_number);
+                writer.extend_from_slice(values.as_slice());
+                encode_closing_tag(writer, values.tag_number);
+            }
+        }
+    }
+
+    fn encode_status_flags(writer: &mut Writer, status_flags: &Status) {
+        BitString::Status(status_flags.clone()).encode_context(Self::TAG_STATUS_FLAGS, writer);
+    }
+
+    fn decode_status_flags(reader: &mut Reader, buf: &[u8]) -> Result<Status, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_STATUS_FLAGS),
+            "EventValues status_flags",
+        )?;
+        BitString::decode_status_flags(tag.value, reader, buf)
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let (inner_buf, tag_number) = get_tagged_body(reader, buf)?;
+        let mut reader = Reader::new_with_len(inner_buf.len());
+        let reader = &mut reader;
+        let buf = inner_buf;
+
+        match tag_number {
+            Self::TAG_CHANGE_OF_BITSTRING => {
+                let tag = Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecific(0),
+                    "EventValues change_of_bitstring referenced_bitstring",
+                )?;
+                let referenced_bitstring = BitString::decode_uninterpreted(tag.value, reader, buf)?;
+                let status_flags = Self::decode_status_flags(reader, buf)?;
+                Ok(Self::ChangeOfBitstring {
+                    referenced_bitstring,
+                    status_flags,
+                })
+            }
+            Self::TAG_CHANGE_OF_STATE => {
+                let new_state = with_context_tag(
+                    reader,
+                    buf,
+                    0,
+                    "EventValues change_of_state new_state",
+                    |reader, buf| {
+                        let tag = Tag::decode(reader, buf)?;
+                        let tag_number = match tag.number {
+                            TagNumber::ContextSpecific(x) => x,
+                            number => {
+                                return Err(Error::TagNotSupported((
+                                    "EventValues change_of_state new_state",
+                                    number,
+                                )))
+                            }
+                        };
+                        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+                        Ok(PropertyState {
+                            tag: tag_number,
+                            value,
+                        })
+                    },
+                )?;
+                let status_flags = Self::decode_status_flags(reader, buf)?;
+                Ok(Self::ChangeOfState {
+                    new_state,
+                    status_flags,
+                })
+            }
+            Self::TAG_CHANGE_OF_VALUE => {
+                let new_value = with_context_tag(
+                    reader,
+                    buf,
+                    0,
+                    "EventValues change_of_value new_value",
+                    |reader, buf| {
+                        let tag = Tag::decode(reader, buf)?;
+                        match tag.number {
+                            TagNumber::ContextSpecific(0) => {
+                                let bits = BitString::decode_uninterpreted(tag.value, reader, buf)?;
+                                Ok(ChangeOfValueNewValue::ChangedBits(bits))
+                            }
+                            TagNumber::ContextSpecific(1) => {
+                                let value = decode_real(
+                                    &tag,
+                                    reader,
+                                    buf,
+                                    "EventValues change_of_value new_value",
+                                )?;
+                                Ok(ChangeOfValueNewValue::ChangedValue(value))
+                            }
+                            number => Err(Error::TagNotSupported((
+                                "EventValues change_of_value new_value",
+                                number,
+                            ))),
+                        }
+                    },
+                )?;
+                let status_flags = Self::decode_status_flags(reader, buf)?;
+                Ok(Self::ChangeOfValue {
+                    new_value,
+                    status_flags,
+                })
+            }
+            Self::TAG_OUT_OF_RANGE => {
+                let exceeding_value =
+                    decode_context_real(reader, buf, 0, "EventValues exceeding_value")?;
+                let status_flags = Self::decode_status_flags(reader, buf)?;
+                let deadband = decode_context_real(reader, buf, 2, "EventValues deadband")?;
+                let exceeded_limit =
+                    decode_context_real(reader, buf, 3, "EventValues exceeded_limit")?;
+                Ok(Self::OutOfRange {
+                    exceeding_value,
+                    status_flags,
+                    deadband,
+                    exceeded_limit,
+                })
+            }
+            x => Ok(Self::Other(OtherEventValues::new_from_buf(x, buf))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu,
+            confirmed::ConfirmedRequestService,
+            primitives::{
+                data_value::{Date, DateTime},
+                time_stamp::TimeStamp,
+            },
+            unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            spec::{EventState, EventType, NotifyType},
+        },
+        network_protocol::{data_link::DataLink, network_pdu::NetworkMessage},
+    };
+
+    use super::{EventNotification, EventValues, PropertyState};
+
+    // AnalogInput:5 on device 1234 went above its high limit of 80.0
+    #[rustfmt::skip]
+    const UNCONFIRMED_OUT_OF_RANGE: &[u8] = &[
+        0x81, 0x0a, 0x00, 0x4a, 0x01, 0x00,
+        0x10, 0x03,
+        0x09, 0x01,
+        0x1c, 0x02, 0x00, 0x04, 0xd2,
+        0x2c, 0x00, 0x00, 0x00, 0x05,
+        0x3e, 0x2e, 0xa4, 0x7c, 0x03, 0x0f, 0x05, 0xb4, 0x0d, 0x2d, 0x1e, 0x00, 0x2f, 0x3f,
+        0x49, 0x0a,
+        0x59, 0x64,
+        0x69, 0x05,
+        0x7b, 0x00, b'h', b'i',
+        0x89, 0x00,
+        0x99, 0x01,
+        0xa9, 0x00,
+        0xb9, 0x03,
+        0xce, 0x5e,
+        0x0c, 0x42, 0xaa, 0x00, 0x00,
+        0x1a, 0x04, 0x80,
+        0x2c, 0x3f, 0x80, 0x00, 0x00,
+        0x3c, 0x42, 0xa0, 0x00, 0x00,
+        0x5f, 0xcf,
+    ];
+
+    // BinaryInput:3 changed to active
+    #[rustfmt::skip]
+    const CONFIRMED_CHANGE_OF_STATE: &[u8] = &[
+        0x81, 0x0a, 0x00, 0x33, 0x01, 0x04,
+        0x00, 0x05, 0x07, 0x02,
+        0x09, 0x01,
+        0x1c, 0x02, 0x00, 0x04, 0xd2,
+        0x2c, 0x00, 0xc0, 0x00, 0x03,
+        0x3e, 0x19, 0x2a, 0x3f,
+        0x49, 0x0a,
+        0x59, 0xc8,
+        0x69, 0x01,
+        0x89, 0x01,
+        0x99, 0x00,
+        0xa9, 0x00,
+        0xb9, 0x02,
+        0xce, 0x1e,
+        0x0e, 0x19, 0x01, 0x0f,
+        0x1a, 0x04, 0x80,
+        0x1f, 0xcf,
+    ];
+
+    fn decode_apdu(buf: &[u8]) -> ApplicationPdu<'_> {
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, buf).unwrap();
+        match message.into_npdu().unwrap().network_message {
+            NetworkMessage::Apdu(apdu) => apdu,
+            x => panic!("expected apdu, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn decode_unconfirmed_out_of_range() {
+        let notification = match decode_apdu(UNCONFIRMED_OUT_OF_RANGE) {
+            ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::EventNotification(x)) => x,
+            x => panic!("expected event notification, got {:?}", x),
+        };
+
+        assert_eq!(notification.process_id, 1);
+        assert_eq!(
+            notification.device_id,
+            ObjectId::new(ObjectType::ObjectDevice, 1234)
+        );
+        assert_eq!(
+            notification.object_id,
+            ObjectId::new(ObjectType::ObjectAnalogInput, 5)
+        );
+        assert!(matches!(
+            notification.time_stamp,
+            TimeStamp::DateTime(DateTime {
+                date: Date { year: 2024, .. },
+                ..
+            })
+        ));
+        assert_eq!(notification.notification_class, 10);
+        assert_eq!(notification.priority, 100);
+        assert_eq!(notification.event_type, EventType::OutOfRange);
+        assert_eq!(notification.message_text.unwrap().as_str(), "hi");
+        assert!(matches!(notification.notify_type, NotifyType::Alarm));
+        assert_eq!(notification.ack_required, Some(true));
+        assert!(matches!(notification.from_state, Some(EventState::Normal)));
+        assert!(matches!(notification.to_state, EventState::HighLimit));
+        match notification.event_values {
+            Some(EventValues::OutOfRange {
+                exceeding_value,
+                status_flags,
+                deadband,
+                exceeded_limit,
+            }) => {
+                assert_eq!(exceeding_value, 85.0);
+                assert!(status_flags.in_alarm());
+                assert_eq!(deadband, 1.0);
+                assert_eq!(exceeded_limit, 80.0);
+            }
+            x => panic!("expected out of range values, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn decode_confirmed_change_of_state() {
+        let notification: EventNotification = match decode_apdu(CONFIRMED_CHANGE_OF_STATE) {
+            ApplicationPdu::ConfirmedRequest(request) => {
+                assert_eq!(request.invoke_id, 7);
+                match request.service {
+                    ConfirmedRequestService::EventNotification(x) => x,
+                    x => panic!("expected event notification, got {:?}", x),
+                }
+            }
+            x => panic!("expected confirmed request, got {:?}", x),
+        };
+
+        assert_eq!(notification.time_stamp, TimeStamp::SequenceNumber(42));
+        assert_eq!(notification.priority, 200);
+        assert_eq!(notification.event_type, EventType::ChangeOfState);
+        assert!(notification.message_text.is_none());
+        assert_eq!(notification.ack_required, Some(false));
+        assert!(matches!(notification.to_state, EventState::OffNormal));
+        match notification.event_values {
+            Some(EventValues::ChangeOfState {
+                new_state,
+                status_flags,
+            }) => {
+                assert_eq!(new_state, PropertyState { tag: 1, value: 1 });
+                assert!(status_flags.in_alarm());
+            }
+            x => panic!("expected change of state values, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn encode_round_trip() {
+        // the bvlc and npdu headers are 6 bytes in both frames
+        for buf in [UNCONFIRMED_OUT_OF_RANGE, CONFIRMED_CHANGE_OF_STATE] {
+            let apdu = decode_apdu(buf);
+            let mut out = [0; 128];
+            let mut writer = Writer::new(&mut out);
+            apdu.encode(&mut writer);
+            assert_eq!(writer.to_bytes(), &buf[6..]);
+        }
+    }
+
+    #[test]
+    fn other_event_values_round_trip() {
+        // floating-limit values (not decoded): reference value 20.0, status flags clear,
+        // setpoint 20.0 and error limit 1.0
+        #[rustfmt::skip]
+        let buf = [
+            0x4e,
+            0x0c, 0x41, 0xa0, 0x00, 0x00,
+            0x1a, 0x04, 0x00,
+            0x2c, 0x41, 0xa0, 0x00, 0x00,
+            0x3c, 0x3f, 0x80, 0x00, 0x00,
+            0x4f,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let values = EventValues::decode(&mut reader, &buf).unwrap();
+        assert!(reader.eof());
+        let EventValues::Other(other) = &values else {
+            panic!("expected other event values, got {:?}", values);
+        };
+        assert_eq!(other.tag_number, 4);
+        assert_eq!(other.as_slice(), &buf[1..buf.len() - 1]);
+
+        let mut out = [0; 32];
+        let mut writer = Writer::new(&mut out);
+        values.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), buf);
+    }
+}
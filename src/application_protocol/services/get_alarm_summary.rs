@@ -0,0 +1,248 @@
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+use crate::{
+    application_protocol::{
+        confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
+        primitives::data_value::{BitString, BitStringBuilder},
+    },
+    common::{
+        error::Error,
+        helper::{decode_unsigned, encode_application_enumerated, encode_application_object_id},
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        spec::{EventState, EventTransitionBits},
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+    network_protocol::data_link::DataLink,
+};
+
+// an object that is currently in alarm
+// GetAlarmSummary has no request parameters (see ConfirmedRequestService::GetAlarmSummary)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AlarmSummary {
+    pub object_id: ObjectId,
+    pub alarm_state: EventState,
+    pub acknowledged_transitions: EventTransitionBits,
+}
+
+impl AlarmSummary {
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_object_id(writer, &self.object_id);
+        encode_application_enumerated(writer, self.alarm_state.clone() as u32);
+        BitStringBuilder::from(&self.acknowledged_transitions)
+            .build()
+            .encode_application(writer);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::ObjectId),
+            "AlarmSummary decode object_id",
+        )?;
+        let object_id = ObjectId::decode(tag.value, reader, buf)?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Enumerated),
+            "AlarmSummary decode alarm_state",
+        )?;
+        let alarm_state = decode_unsigned(tag.value, reader, buf)? as u32;
+        let alarm_state = EventState::try_from(alarm_state)
+            .map_err(|x| Error::InvalidVariant(("AlarmSummary decode alarm_state", x)))?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::BitString),
+            "AlarmSummary decode acknowledged_transitions",
+        )?;
+        let acknowledged_transitions =
+            BitString::decode_uninterpreted(tag.value, reader, buf)?.event_transition_bits()?;
+
+        Ok(Self {
+            object_id,
+            alarm_state,
+            acknowledged_transitions,
+        })
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetAlarmSummaryAck<'a> {
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetAlarmSummaryAck<'a> {
+    pub alarm_summaries: Vec<AlarmSummary>,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> TryFrom<DataLink<'a>> for GetAlarmSummaryAck<'a> {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        let ack: ComplexAck = value.try_into()?;
+        match ack.service {
+            ComplexAckService::GetAlarmSummary(ack) => Ok(ack),
+            _ => Err(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService GetAlarmSummaryAck",
+            )),
+        }
+    }
+}
+
+impl<'a> GetAlarmSummaryAck<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new_from_buf(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(alarm_summaries: Vec<AlarmSummary>) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            alarm_summaries,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::GetAlarmSummary as u8);
+
+        #[cfg(not(feature = "alloc"))]
+        writer.extend_from_slice(self.buf);
+
+        #[cfg(feature = "alloc")]
+        for alarm_summary in self.alarm_summaries.iter() {
+            alarm_summary.encode(writer);
+        }
+    }
+
+    // the list of alarm summaries takes up the rest of the buffer
+    #[cfg(not(feature = "alloc"))]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let buf = reader.read_remaining(buf)?;
+        Ok(Self::new_from_buf(buf))
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let mut alarm_summaries = Vec::new();
+        while !reader.eof() {
+            let alarm_summary = AlarmSummary::decode(reader, buf)?;
+            alarm_summaries.push(alarm_summary);
+        }
+        Ok(Self::new(alarm_summaries))
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ GetAlarmSummaryAck<'a> {
+    type Item = Result<AlarmSummary, Error>;
+    type IntoIter = AlarmSummaryIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        AlarmSummaryIter::new(self.buf)
+    }
+}
+
+pub struct AlarmSummaryIter<'a> {
+    reader: Reader,
+    buf: &'a [u8],
+}
+
+impl<'a> AlarmSummaryIter<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self {
+            reader: Reader::new_with_len(buf.len()),
+            buf,
+        }
+    }
+}
+
+impl<'a> Iterator for AlarmSummaryIter<'a> {
+    type Item = Result<AlarmSummary, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.eof() {
+            return None;
+        }
+
+        Some(AlarmSummary::decode(&mut self.reader, self.buf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            spec::EventState,
+        },
+        network_protocol::data_link::DataLink,
+    };
+
+    use super::{AlarmSummary, GetAlarmSummaryAck};
+
+    #[test]
+    fn decode_ack() {
+        // AnalogInput:5 is in high-limit (unacknowledged) and BinaryInput:3 is offnormal (acknowledged)
+        #[rustfmt::skip]
+        let buf = [
+            0x81, 0x0a, 0x00, 0x1d, 0x01, 0x00,
+            0x30, 0x02, 0x03,
+            0xc4, 0x00, 0x00, 0x00, 0x05, 0x91, 0x03, 0x82, 0x05, 0x60,
+            0xc4, 0x00, 0xc0, 0x00, 0x03, 0x91, 0x02, 0x82, 0x05, 0xe0,
+        ];
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, &buf).unwrap();
+        let ack: GetAlarmSummaryAck = message.try_into().unwrap();
+
+        #[cfg(feature = "alloc")]
+        let mut summaries = ack.alarm_summaries.clone().into_iter();
+        #[cfg(not(feature = "alloc"))]
+        let mut summaries = (&ack).into_iter().map(|x| x.unwrap());
+
+        let summary = summaries.next().unwrap();
+        assert_eq!(
+            summary.object_id,
+            ObjectId::new(ObjectType::ObjectAnalogInput, 5)
+        );
+        assert!(matches!(summary.alarm_state, EventState::HighLimit));
+        assert!(!summary.acknowledged_transitions.to_offnormal());
+
+        let summary = summaries.next().unwrap();
+        assert_eq!(
+            summary.object_id,
+            ObjectId::new(ObjectType::ObjectBinaryInput, 3)
+        );
+        assert!(matches!(summary.alarm_state, EventState::OffNormal));
+        assert!(summary.acknowledged_transitions.to_offnormal());
+        assert!(summaries.next().is_none());
+
+        let mut out = [0; 32];
+        let mut writer = Writer::new(&mut out);
+        ack.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &buf[8..]);
+    }
+
+    #[test]
+    fn decode_transition_bits_of_wrong_length_is_error() {
+        // acknowledged transitions with 8 bits instead of 3
+        let buf = [0xc4, 0x00, 0x00, 0x00, 0x05, 0x91, 0x03, 0x82, 0x00, 0x60];
+        let mut reader = Reader::new_with_len(buf.len());
+        assert!(AlarmSummary::decode(&mut reader, &buf).is_err());
+    }
+}
@@ -0,0 +1,441 @@
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+use crate::{
+    application_protocol::{
+        confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
+        primitives::{
+            data_value::{BitString, BitStringBuilder},
+            time_stamp::TimeStamp,
+        },
+    },
+    common::{
+        error::Error,
+        helper::{
+            decode_context_object_id, decode_optional_context_tag, decode_unsigned,
+            encode_application_unsigned, encode_closing_tag, encode_context_bool,
+            encode_context_object_id, encode_context_unsigned, encode_opening_tag,
+            get_tagged_body_for_tag, with_context_tag,
+        },
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        spec::{EventState, EventTransitionBits, NotifyType},
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+    network_protocol::data_link::DataLink,
+};
+
+// asks a device for the objects that have active alarms or unacknowledged transitions
+// the reply may not fit in a single ack, in that case more_events is set in the ack and the request
+// should be repeated with the last object id received
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetEventInformation {
+    pub last_received_object_id: Option<ObjectId>,
+}
+
+impl GetEventInformation {
+    const TAG_LAST_RECEIVED_OBJECT_ID: u8 = 0;
+
+    pub fn new(last_received_object_id: Option<ObjectId>) -> Self {
+        Self {
+            last_received_object_id,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        if let Some(object_id) = &self.last_received_object_id {
+            encode_context_object_id(writer, Self::TAG_LAST_RECEIVED_OBJECT_ID, object_id);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EventSummary {
+    pub object_id: ObjectId,
+    pub event_state: EventState,
+    pub acknowledged_transitions: EventTransitionBits,
+    pub event_time_stamps: [TimeStamp; 3], // to-offnormal, to-fault and to-normal
+    pub notify_type: NotifyType,
+    pub event_enable: EventTransitionBits,
+    pub event_priorities: [u32; 3], // to-offnormal, to-fault and to-normal
+}
+
+impl EventSummary {
+    const TAG_OBJECT_ID: u8 = 0;
+    const TAG_EVENT_STATE: u8 = 1;
+    const TAG_ACKNOWLEDGED_TRANSITIONS: u8 = 2;
+    const TAG_EVENT_TIME_STAMPS: u8 = 3;
+    const TAG_NOTIFY_TYPE: u8 = 4;
+    const TAG_EVENT_ENABLE: u8 = 5;
+    const TAG_EVENT_PRIORITIES: u8 = 6;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+        // enumerated values are encoded like unsigned values
+        encode_context_unsigned(
+            writer,
+            Self::TAG_EVENT_STATE,
+            self.event_state.clone() as u32,
+        );
+        encode_transition_bits(
+            writer,
+            Self::TAG_ACKNOWLEDGED_TRANSITIONS,
+            &self.acknowledged_transitions,
+        );
+
+        encode_opening_tag(writer, Self::TAG_EVENT_TIME_STAMPS);
+        for time_stamp in self.event_time_stamps.iter() {
+            time_stamp.encode(writer);
+        }
+        encode_closing_tag(writer, Self::TAG_EVENT_TIME_STAMPS);
+
+        encode_context_unsigned(
+            writer,
+            Self::TAG_NOTIFY_TYPE,
+            self.notify_type.clone() as u32,
+        );
+        encode_transition_bits(writer, Self::TAG_EVENT_ENABLE, &self.event_enable);
+
+        encode_opening_tag(writer, Self::TAG_EVENT_PRIORITIES);
+        for priority in self.event_priorities.iter() {
+            encode_application_unsigned(writer, *priority as u64);
+        }
+        encode_closing_tag(writer, Self::TAG_EVENT_PRIORITIES);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_OBJECT_ID,
+            "EventSummary decode object_id",
+        )?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_EVENT_STATE),
+            "EventSummary decode event_state",
+        )?;
+        let event_state = decode_unsigned(tag.value, reader, buf)? as u32;
+        let event_state = EventState::try_from(event_state)
+            .map_err(|x| Error::InvalidVariant(("EventSummary decode event_state", x)))?;
+
+        let acknowledged_transitions =
+            decode_transition_bits(reader, buf, Self::TAG_ACKNOWLEDGED_TRANSITIONS)?;
+
+        let event_time_stamps = with_context_tag(
+            reader,
+            buf,
+            Self::TAG_EVENT_TIME_STAMPS,
+            "EventSummary decode event_time_stamps",
+            TimeStamp::decode_event_time_stamps,
+        )?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_NOTIFY_TYPE),
+            "EventSummary decode notify_type",
+        )?;
+        let notify_type = decode_unsigned(tag.value, reader, buf)? as u32;
+        let notify_type = NotifyType::try_from(notify_type)
+            .map_err(|x| Error::InvalidVariant(("EventSummary decode notify_type", x)))?;
+
+        let event_enable = decode_transition_bits(reader, buf, Self::TAG_EVENT_ENABLE)?;
+
+        let event_priorities = with_context_tag(
+            reader,
+            buf,
+            Self::TAG_EVENT_PRIORITIES,
+            "EventSummary decode event_priorities",
+            |reader, buf| {
+                let mut priorities = [0; 3];
+                for priority in priorities.iter_mut() {
+                    let tag = Tag::decode_expected(
+                        reader,
+                        buf,
+                        TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+                        "EventSummary decode event_priorities",
+                    )?;
+                    *priority = decode_unsigned(tag.value, reader, buf)? as u32;
+                }
+                Ok(priorities)
+            },
+        )?;
+
+        Ok(Self {
+            object_id,
+            event_state,
+            acknowledged_transitions,
+            event_time_stamps,
+            notify_type,
+            event_enable,
+            event_priorities,
+        })
+    }
+}
+
+fn encode_transition_bits(writer: &mut Writer, tag_number: u8, bits: &EventTransitionBits) {
+    BitStringBuilder::from(bits)
+        .build()
+        .encode_context(tag_number, writer);
+}
+
+fn decode_transition_bits(
+    reader: &mut Reader,
+    buf: &[u8],
+    tag_number: u8,
+) -> Result<EventTransitionBits, Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::ContextSpecific(tag_number),
+        "EventSummary decode transition bits",
+    )?;
+    BitString::decode_uninterpreted(tag.value, reader, buf)?.event_transition_bits()
+}
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetEventInformationAck<'a> {
+    pub more_events: bool, // request again with the last object id received to get the rest
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetEventInformationAck<'a> {
+    pub event_summaries: Vec<EventSummary>,
+    pub more_events: bool, // request again with the last object id received to get the rest
+    _phantom: &'a Phantom,
+}
+
+impl<'a> TryFrom<DataLink<'a>> for GetEventInformationAck<'a> {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        let ack: ComplexAck = value.try_into()?;
+        match ack.service {
+            ComplexAckService::GetEventInformation(ack) => Ok(ack),
+            _ => Err(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService GetEventInformationAck",
+            )),
+        }
+    }
+}
+
+impl<'a> GetEventInformationAck<'a> {
+    const TAG_LIST_OF_EVENT_SUMMARIES: u8 = 0;
+    const TAG_MORE_EVENTS: u8 = 1;
+
+    #[cfg(feature = "alloc")]
+    pub fn new(event_summaries: Vec<EventSummary>, more_events: bool) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            event_summaries,
+            more_events,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::GetEventInformation as u8);
+        encode_opening_tag(writer, Self::TAG_LIST_OF_EVENT_SUMMARIES);
+
+        #[cfg(not(feature = "alloc"))]
+        writer.extend_from_slice(self.buf);
+
+        #[cfg(feature = "alloc")]
+        for event_summary in self.event_summaries.iter() {
+            event_summary.encode(writer);
+        }
+
+        encode_closing_tag(writer, Self::TAG_LIST_OF_EVENT_SUMMARIES);
+        encode_context_bool(writer, Self::TAG_MORE_EVENTS, self.more_events);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_LIST_OF_EVENT_SUMMARIES,
+            "GetEventInformationAck decode list of event summaries",
+        )?;
+
+        let tag = decode_optional_context_tag(reader, buf, Self::TAG_MORE_EVENTS)?.ok_or(
+            Error::InvalidValue("GetEventInformationAck decode more_events is missing"),
+        )?;
+        let more_events = decode_unsigned(tag.value, reader, buf)? > 0;
+
+        #[cfg(not(feature = "alloc"))]
+        let ack = Self {
+            more_events,
+            buf: inner_buf,
+        };
+
+        #[cfg(feature = "alloc")]
+        let ack = {
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            let mut event_summaries = Vec::new();
+            while !inner_reader.eof() {
+                let event_summary = EventSummary::decode(&mut inner_reader, inner_buf)?;
+                event_summaries.push(event_summary);
+            }
+            Self::new(event_summaries, more_events)
+        };
+
+        Ok(ack)
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ GetEventInformationAck<'a> {
+    type Item = Result<EventSummary, Error>;
+    type IntoIter = EventSummaryIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        EventSummaryIter::new(self.buf)
+    }
+}
+
+pub struct EventSummaryIter<'a> {
+    reader: Reader,
+    buf: &'a [u8],
+}
+
+impl<'a> EventSummaryIter<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self {
+            reader: Reader::new_with_len(buf.len()),
+            buf,
+        }
+    }
+}
+
+impl<'a> Iterator for EventSummaryIter<'a> {
+    type Item = Result<EventSummary, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.eof() {
+            return None;
+        }
+
+        Some(EventSummary::decode(&mut self.reader, self.buf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::primitives::time_stamp::TimeStamp,
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            spec::{EventState, NotifyType},
+        },
+        network_protocol::data_link::DataLink,
+    };
+
+    use super::{EventSummary, GetEventInformation, GetEventInformationAck};
+
+    #[test]
+    fn encode_request() {
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        GetEventInformation::default().encode(&mut writer);
+        assert!(writer.to_bytes().is_empty());
+
+        // continue after AnalogInput:5
+        let last = ObjectId::new(ObjectType::ObjectAnalogInput, 5);
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        GetEventInformation::new(Some(last)).encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &[0x0c, 0x00, 0x00, 0x00, 0x05]);
+    }
+
+    #[test]
+    fn decode_ack() {
+        // AnalogInput:5 is in high-limit and the to-offnormal transition has not been acknowledged
+        #[rustfmt::skip]
+        let buf = [
+            0x81, 0x0a, 0x00, 0x2c, 0x01, 0x00,
+            0x30, 0x01, 0x1d,
+            0x0e,
+            0x0c, 0x00, 0x00, 0x00, 0x05,
+            0x19, 0x03,
+            0x2a, 0x05, 0x60,
+            0x3e, 0x19, 0x2a, 0x19, 0x00, 0x19, 0x29, 0x3f,
+            0x49, 0x00,
+            0x5a, 0x05, 0xe0,
+            0x6e, 0x21, 0x64, 0x21, 0xc8, 0x21, 0xc8, 0x6f,
+            0x0f,
+            0x19, 0x00,
+        ];
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, &buf).unwrap();
+        let ack: GetEventInformationAck = message.try_into().unwrap();
+        assert!(!ack.more_events);
+
+        #[cfg(feature = "alloc")]
+        let mut summaries = ack.event_summaries.clone().into_iter();
+        #[cfg(not(feature = "alloc"))]
+        let mut summaries = (&ack).into_iter().map(|x| x.unwrap());
+
+        let summary = summaries.next().unwrap();
+        assert!(summaries.next().is_none());
+        assert_eq!(
+            summary.object_id,
+            ObjectId::new(ObjectType::ObjectAnalogInput, 5)
+        );
+        assert!(matches!(summary.event_state, EventState::HighLimit));
+        assert!(!summary.acknowledged_transitions.to_offnormal());
+        assert!(summary.acknowledged_transitions.to_fault());
+        assert!(summary.acknowledged_transitions.to_normal());
+        assert_eq!(
+            summary.event_time_stamps,
+            [
+                TimeStamp::SequenceNumber(42),
+                TimeStamp::SequenceNumber(0),
+                TimeStamp::SequenceNumber(41)
+            ]
+        );
+        assert!(matches!(summary.notify_type, NotifyType::Alarm));
+        assert!(summary.event_enable.to_offnormal());
+        assert_eq!(summary.event_priorities, [100, 200, 200]);
+
+        let mut out = [0; 64];
+        let mut writer = Writer::new(&mut out);
+        ack.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &buf[8..]);
+    }
+
+    #[test]
+    fn decode_transition_bits_of_wrong_length_is_error() {
+        // acknowledged transitions with 4 bits instead of 3
+        #[rustfmt::skip]
+        let buf = [
+            0x0c, 0x00, 0x00, 0x00, 0x05,
+            0x19, 0x03,
+            0x2a, 0x04, 0x60,
+            0x3e, 0x19, 0x2a, 0x19, 0x00, 0x19, 0x29, 0x3f,
+            0x49, 0x00,
+            0x5a, 0x05, 0xe0,
+            0x6e, 0x21, 0x64, 0x21, 0xc8, 0x21, 0xc8, 0x6f,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        assert!(EventSummary::decode(&mut reader, &buf).is_err());
+
+        // unused bits must be less than 8
+        let mut buf = buf;
+        buf[8] = 0x08;
+        let mut reader = Reader::new_with_len(buf.len());
+        assert!(EventSummary::decode(&mut reader, &buf).is_err());
+    }
+}
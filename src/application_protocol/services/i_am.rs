@@ -1,5 +1,8 @@
 use crate::{
-    application_protocol::unconfirmed::UnconfirmedServiceChoice,
+    application_protocol::{
+        application_pdu::{ApplicationPdu, MaxAdpu},
+        unconfirmed::{UnconfirmedRequest, UnconfirmedServiceChoice},
+    },
     common::{
         error::Error,
         helper::{
@@ -11,10 +14,15 @@ use crate::{
         spec::Segmentation,
         tag::{ApplicationTagNumber, Tag, TagNumber},
     },
+    network_protocol::{
+        data_link::DataLink,
+        network_pdu::{NetworkAddress, NetworkMessage},
+    },
 };
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IAm {
     pub device_id: ObjectId,
     pub max_apdu: usize,
@@ -22,7 +30,44 @@ pub struct IAm {
     pub vendor_id: u16,
 }
 
+impl<'a> TryFrom<DataLink<'a>> for IAm {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        match value.into_npdu() {
+            Some(x) => match x.network_message {
+                NetworkMessage::Apdu(ApplicationPdu::UnconfirmedRequest(
+                    UnconfirmedRequest::IAm(i_am),
+                )) => Ok(i_am),
+                _ => Err(Error::ConvertDataLink("npdu message is not an apdu i_am")),
+            },
+            _ => Err(Error::ConvertDataLink("no npdu defined in message")),
+        }
+    }
+}
+
 impl IAm {
+    pub fn new(
+        device_id: ObjectId,
+        max_apdu: MaxAdpu,
+        segmentation: Segmentation,
+        vendor_id: u16,
+    ) -> Self {
+        Self {
+            device_id,
+            max_apdu: max_apdu.octets(),
+            segmentation,
+            vendor_id,
+        }
+    }
+
+    // pairs the device id with the address it can be reached at (see NetworkPdu::source_address)
+    // so that it can be stored in an address binding cache
+    // NOTE: check device_id.is_unconfigured_device() first as many devices can share that id
+    pub fn binding(&self, source_addr: NetworkAddress) -> (ObjectId, NetworkAddress) {
+        (self.device_id, source_addr)
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
         writer.push(UnconfirmedServiceChoice::IAm as u8);
         encode_application_object_id(writer, &self.device_id);
@@ -87,3 +132,79 @@ impl IAm {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{application_pdu::MaxAdpu, unconfirmed::UnconfirmedServiceChoice},
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            spec::Segmentation,
+        },
+        network_protocol::{
+            data_link::DataLink,
+            network_pdu::{Addr, NetworkAddress},
+        },
+    };
+
+    use super::IAm;
+
+    // broadcast i_am from device 1234 with no routing information
+    const I_AM: [u8; 24] = [
+        0x81, 0x0b, 0x00, 0x18, 0x01, 0x20, 0xff, 0xff, 0x00, 0xff, 0x10, 0x00, 0xc4, 0x02, 0x00,
+        0x04, 0xd2, 0x22, 0x05, 0xc4, 0x91, 0x03, 0x21, 0x0f,
+    ];
+
+    #[test]
+    fn binding_from_broadcast() {
+        let mut reader = Reader::default();
+        let data_link = DataLink::decode(&mut reader, &I_AM).unwrap();
+        let npdu = data_link.npdu().cloned().unwrap();
+
+        let mac = Addr {
+            ipv4: [192, 168, 1, 249],
+            port: 0xbac0,
+        };
+        let source_addr = npdu.source_address(mac.clone());
+        let i_am: IAm = data_link.try_into().unwrap();
+        let (device_id, addr) = i_am.binding(source_addr);
+
+        assert_eq!(device_id, ObjectId::new(ObjectType::ObjectDevice, 1234));
+        assert_eq!(addr, NetworkAddress::local(mac));
+    }
+
+    #[test]
+    fn encode_with_capabilities() {
+        let device_id = ObjectId::new(ObjectType::ObjectDevice, 1234);
+        let i_am = IAm::new(device_id, MaxAdpu::_480, Segmentation::Receive, 260);
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        i_am.encode(&mut writer);
+        let buf = writer.to_bytes();
+        assert_eq!(buf[0], UnconfirmedServiceChoice::IAm as u8);
+
+        let mut reader = Reader::new_with_len(buf.len());
+        reader.index = 1;
+        let decoded = IAm::decode(&mut reader, buf).unwrap();
+        assert_eq!(decoded.device_id, device_id);
+        assert_eq!(decoded.max_apdu, 480);
+        assert!(matches!(decoded.segmentation, Segmentation::Receive));
+        assert_eq!(decoded.vendor_id, 260);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_json_round_trip() {
+        let device_id = ObjectId::new(ObjectType::ObjectDevice, 1234);
+        let i_am = IAm::new(device_id, MaxAdpu::_480, Segmentation::Receive, 260);
+
+        let json = serde_json::to_string(&i_am).unwrap();
+        let decoded: IAm = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.device_id, device_id);
+        assert_eq!(decoded.max_apdu, 480);
+        assert!(matches!(decoded.segmentation, Segmentation::Receive));
+        assert_eq!(decoded.vendor_id, 260);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{
+    application_protocol::primitives::data_value::ApplicationDataValue,
+    common::{
+        helper::{
+            encode_closing_tag, encode_context_enumerated, encode_context_object_id,
+            encode_context_unsigned, encode_opening_tag,
+        },
+        io::Writer,
+        object_id::ObjectId,
+        property_id::PropertyId,
+    },
+};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+// adds elements to or removes elements from a list property (the reply is a SimpleAck)
+// used for both AddListElement and RemoveListElement as they take the same parameters
+// if an element cannot be processed the Error PDU reports its (1 based) index in first_failed_element
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ListElementRequest<'a> {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub elements: &'a [ApplicationDataValue<'a>],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ListElementRequest<'a> {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub elements: Vec<ApplicationDataValue<'a>>,
+}
+
+impl<'a> ListElementRequest<'a> {
+    const TAG_OBJECT_ID: u8 = 0;
+    const TAG_PROPERTY_ID: u8 = 1;
+    const TAG_ARRAY_INDEX: u8 = 2;
+    const TAG_LIST_OF_ELEMENTS: u8 = 3;
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(
+        object_id: ObjectId,
+        property_id: PropertyId,
+        array_index: Option<u32>,
+        elements: &'a [ApplicationDataValue<'a>],
+    ) -> Self {
+        Self {
+            object_id,
+            property_id,
+            array_index,
+            elements,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(
+        object_id: ObjectId,
+        property_id: PropertyId,
+        array_index: Option<u32>,
+        elements: Vec<ApplicationDataValue<'a>>,
+    ) -> Self {
+        Self {
+            object_id,
+            property_id,
+            array_index,
+            elements,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+        encode_context_enumerated(writer, Self::TAG_PROPERTY_ID, &self.property_id);
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::TAG_ARRAY_INDEX, array_index);
+        }
+
+        encode_opening_tag(writer, Self::TAG_LIST_OF_ELEMENTS);
+        for element in self.elements.iter() {
+            element.encode(writer);
+        }
+        encode_closing_tag(writer, Self::TAG_LIST_OF_ELEMENTS);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::primitives::data_value::ApplicationDataValue,
+        common::{
+            io::Writer,
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+    };
+
+    use super::ListElementRequest;
+
+    #[test]
+    fn encode_list_of_elements() {
+        // add states 3 and 4 to the Alarm_Values of MultiStateInput:1
+        let elements = [
+            ApplicationDataValue::UnsignedInt(3),
+            ApplicationDataValue::UnsignedInt(4),
+        ];
+        #[cfg(feature = "alloc")]
+        let elements = elements.to_vec();
+        #[cfg(not(feature = "alloc"))]
+        let elements = &elements;
+
+        let request = ListElementRequest::new(
+            ObjectId::new(ObjectType::ObjectMultiStateInput, 1),
+            PropertyId::PropAlarmValues,
+            None,
+            elements,
+        );
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        #[rustfmt::skip]
+        let expected = [
+            0x0c, 0x03, 0x40, 0x00, 0x01,
+            0x19, 0x07,
+            0x3e, 0x21, 0x03, 0x21, 0x04, 0x3f,
+        ];
+        assert_eq!(writer.to_bytes(), &expected);
+    }
+}
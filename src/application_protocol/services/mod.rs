@@ -1,8 +1,23 @@
+pub mod acknowledge_alarm;
+pub mod atomic_read_file;
+pub mod atomic_write_file;
 pub mod change_of_value;
+pub mod create_object;
+pub mod device_communication_control;
+pub mod event_notification;
+pub mod get_alarm_summary;
+pub mod get_event_information;
 pub mod i_am;
+pub mod list_element;
 pub mod read_property;
 pub mod read_property_multiple;
 pub mod read_range;
+pub mod reinitialize_device;
 pub mod time_synchronization;
+pub mod who_has;
 pub mod who_is;
 pub mod write_property;
+pub mod write_property_multiple;
+
+#[cfg(test)]
+mod round_trip_tests;
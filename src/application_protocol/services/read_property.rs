@@ -1,14 +1,22 @@
 use crate::{
     application_protocol::{
         confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
-        primitives::data_value::ApplicationDataValue,
+        primitives::{
+            data_value::ApplicationDataValue,
+            object_reference::{DeviceObjectPropertyReference, DeviceObjectReference},
+            priority_array::PriorityArray,
+            time_stamp::TimeStamp,
+        },
+        services::change_of_value::CovSubscriptionList,
     },
     common::{
+        daily_schedule::WeeklySchedule,
         error::Error,
         helper::{
-            decode_context_object_id, decode_context_property_id, encode_closing_tag,
-            encode_context_enumerated, encode_context_object_id, encode_context_unsigned,
-            encode_opening_tag, get_tagged_body_for_tag,
+            decode_context_object_id, decode_context_property_id, decode_optional_context_tag,
+            decode_unsigned, encode_closing_tag, encode_context_enumerated,
+            encode_context_object_id, encode_context_unsigned, encode_opening_tag,
+            get_tagged_body_for_tag,
         },
         io::{Reader, Writer},
         object_id::ObjectId,
@@ -28,6 +36,10 @@ use {
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ReadPropertyValue<'a> {
     ObjectIdList(ObjectIdList<'a>),
+    ReferenceList(ReferenceList<'a>),
+    ObjectPropertyReference(DeviceObjectPropertyReference), // e.g. Log_DeviceObjectProperty
+    CovSubscriptionList(CovSubscriptionList<'a>),
+    PriorityArray(PriorityArray<'a>),
     ApplicationDataValue(ApplicationDataValue<'a>),
 }
 
@@ -147,11 +159,149 @@ impl<'a> Iterator for ObjectIdIter<'a> {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ObjectReference {
+    DeviceObject(DeviceObjectReference),
+    DeviceObjectProperty(DeviceObjectPropertyReference),
+}
+
+impl ObjectReference {
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::DeviceObject(x) => x.encode(writer),
+            Self::DeviceObjectProperty(x) => x.encode(writer),
+        }
+    }
+}
+
+// a list valued property where each entry is a reference to another object
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReferenceList<'a> {
+    pub property_id: PropertyId,
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReferenceList<'a> {
+    pub property_id: PropertyId,
+    pub references: Vec<ObjectReference>,
+    _phantom: &'a Phantom,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReferenceIter<'a> {
+    reader: Reader,
+    buf: &'a [u8],
+    property_id: PropertyId,
+}
+
+impl<'a> ReferenceList<'a> {
+    // the properties that are decoded as a list of references
+    pub fn is_reference_list(property_id: &PropertyId) -> bool {
+        matches!(
+            property_id,
+            PropertyId::PropSubordinateList | PropertyId::PropListOfObjectPropertyReferences
+        )
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn new_from_buf(property_id: PropertyId, buf: &'a [u8]) -> Self {
+        Self { property_id, buf }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(property_id: PropertyId, references: Vec<ObjectReference>) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            property_id,
+            references,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.extend_from_slice(self.buf);
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn encode(&self, writer: &mut Writer) {
+        for reference in self.references.iter() {
+            reference.encode(writer);
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn decode(property_id: PropertyId, buf: &'a [u8]) -> Result<Self, Error> {
+        Ok(Self::new_from_buf(property_id, buf))
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn decode(property_id: PropertyId, buf: &[u8]) -> Result<Self, Error> {
+        let references: Result<Vec<ObjectReference>, Error> =
+            ReferenceIter::new(property_id, buf).collect();
+        Ok(Self::new(property_id, references?))
+    }
+}
+
+impl<'a> ReferenceIter<'a> {
+    pub fn new(property_id: PropertyId, buf: &'a [u8]) -> Self {
+        Self {
+            reader: Reader::new_with_len(buf.len()),
+            buf,
+            property_id,
+        }
+    }
+
+    fn next_internal(&mut self) -> Result<ObjectReference, Error> {
+        match self.property_id {
+            PropertyId::PropSubordinateList => {
+                let reference = DeviceObjectReference::decode(&mut self.reader, self.buf)?;
+                Ok(ObjectReference::DeviceObject(reference))
+            }
+            _ => {
+                let reference = DeviceObjectPropertyReference::decode(&mut self.reader, self.buf)?;
+                Ok(ObjectReference::DeviceObjectProperty(reference))
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ ReferenceList<'a> {
+    type Item = Result<ObjectReference, Error>;
+    type IntoIter = ReferenceIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ReferenceIter::new(self.property_id, self.buf)
+    }
+}
+
+impl<'a> Iterator for ReferenceIter<'a> {
+    type Item = Result<ObjectReference, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.eof() {
+            None
+        } else {
+            Some(self.next_internal())
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ReadPropertyAck<'a> {
     pub object_id: ObjectId,
     pub property_id: PropertyId,
+    pub array_index: u32, // BACNET_ARRAY_ALL unless a single array element was read
     pub property_value: ReadPropertyValue<'a>,
 }
 
@@ -170,10 +320,36 @@ impl<'a> TryFrom<DataLink<'a>> for ReadPropertyAck<'a> {
 }
 
 impl<'a> ReadPropertyAck<'a> {
+    // an object always reports its own id in Object_Identifier so a mismatch points to a routing or
+    // addressing problem (e.g. the request was answered by a different device)
+    pub fn verify_object_identifier(&self, expected: &ObjectId) -> Result<(), Error> {
+        if self.property_id != PropertyId::PropObjectIdentifier {
+            return Err(Error::InvalidValue(
+                "ReadPropertyAck is not for the Object_Identifier property",
+            ));
+        }
+        match &self.property_value {
+            ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::ObjectId(x))
+                if x == expected && self.object_id == *expected =>
+            {
+                Ok(())
+            }
+            ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::ObjectId(_)) => Err(
+                Error::InvalidValue("Object_Identifier does not match the requested object"),
+            ),
+            _ => Err(Error::InvalidValue(
+                "Object_Identifier value is not an object id",
+            )),
+        }
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
         writer.push(ConfirmedServiceChoice::ReadProperty as u8);
         encode_context_object_id(writer, 0, &self.object_id);
         encode_context_enumerated(writer, 1, &self.property_id);
+        if self.array_index != BACNET_ARRAY_ALL {
+            encode_context_unsigned(writer, 2, self.array_index);
+        }
         encode_opening_tag(writer, 3);
         match &self.property_value {
             ReadPropertyValue::ApplicationDataValue(value) => {
@@ -182,6 +358,18 @@ impl<'a> ReadPropertyAck<'a> {
             ReadPropertyValue::ObjectIdList(value) => {
                 value.encode(writer);
             }
+            ReadPropertyValue::ReferenceList(value) => {
+                value.encode(writer);
+            }
+            ReadPropertyValue::ObjectPropertyReference(value) => {
+                value.encode(writer);
+            }
+            ReadPropertyValue::CovSubscriptionList(value) => {
+                value.encode(writer);
+            }
+            ReadPropertyValue::PriorityArray(value) => {
+                value.encode(writer);
+            }
         }
         encode_closing_tag(writer, 3);
     }
@@ -192,30 +380,134 @@ impl<'a> ReadPropertyAck<'a> {
             decode_context_object_id(reader, buf, 0, "ReadPropertyAck decode object_id")?;
         let property_id =
             decode_context_property_id(reader, buf, 1, "ReadPropertyAck decode property_id")?;
+        let array_index = match decode_optional_context_tag(reader, buf, 2)? {
+            Some(tag) => decode_unsigned(tag.value, reader, buf)? as u32,
+            None => BACNET_ARRAY_ALL,
+        };
 
         let buf = get_tagged_body_for_tag(reader, buf, 3, "ReadPropertyAck decode data values")?;
         let mut reader = Reader::new_with_len(buf.len());
 
+        // index 0 is the length of the array and any other index is a single element which is
+        // decoded the same way as an element of the whole property
+        let element = array_index != BACNET_ARRAY_ALL;
+        if array_index == 0 {
+            let value =
+                ApplicationDataValue::decode_inner(&object_id, &property_id, &mut reader, buf)?;
+            return Ok(Self {
+                object_id,
+                property_id,
+                array_index,
+                property_value: ReadPropertyValue::ApplicationDataValue(value),
+            });
+        }
+
         match property_id {
-            PropertyId::PropObjectList => {
+            PropertyId::PropObjectList if !element => {
                 let object_ids = ObjectIdList::decode(&mut reader, buf)?;
                 let property_value = ReadPropertyValue::ObjectIdList(object_ids);
 
                 Ok(Self {
                     object_id,
                     property_id,
+                    array_index,
+                    property_value,
+                })
+            }
+            PropertyId::PropActiveCovSubscriptions => {
+                let subscriptions = CovSubscriptionList::decode(buf)?;
+                let property_value = ReadPropertyValue::CovSubscriptionList(subscriptions);
+
+                Ok(Self {
+                    object_id,
+                    property_id,
+                    array_index,
+                    property_value,
+                })
+            }
+            PropertyId::PropPriorityArray if !element => {
+                let priority_array = PriorityArray::decode(&object_id, &mut reader, buf)?;
+                let property_value = ReadPropertyValue::PriorityArray(priority_array);
+
+                Ok(Self {
+                    object_id,
+                    property_id,
+                    array_index,
+                    property_value,
+                })
+            }
+            PropertyId::PropEventTimeStamps => {
+                let value = if element {
+                    ApplicationDataValue::TimeStamp(TimeStamp::decode(&mut reader, buf)?)
+                } else {
+                    let time_stamps = TimeStamp::decode_event_time_stamps(&mut reader, buf)?;
+                    ApplicationDataValue::EventTimeStamps(time_stamps)
+                };
+                let property_value = ReadPropertyValue::ApplicationDataValue(value);
+
+                Ok(Self {
+                    object_id,
+                    property_id,
+                    array_index,
+                    property_value,
+                })
+            }
+            PropertyId::PropWeeklySchedule => {
+                let value = if element {
+                    ApplicationDataValue::DailySchedule(WeeklySchedule::decode_day(
+                        &mut reader,
+                        buf,
+                    )?)
+                } else {
+                    ApplicationDataValue::WeeklySchedule(WeeklySchedule::decode(&mut reader, buf)?)
+                };
+                let property_value = ReadPropertyValue::ApplicationDataValue(value);
+
+                Ok(Self {
+                    object_id,
+                    property_id,
+                    array_index,
+                    property_value,
+                })
+            }
+            PropertyId::PropLogDeviceObjectProperty => {
+                // a single reference rather than a list
+                let reference = DeviceObjectPropertyReference::decode(&mut reader, buf)?;
+                if !reader.eof() {
+                    return Err(Error::InvalidValue(
+                        "ReadPropertyAck unexpected data after the object property reference",
+                    ));
+                }
+                let property_value = ReadPropertyValue::ObjectPropertyReference(reference);
+
+                Ok(Self {
+                    object_id,
+                    property_id,
+                    array_index,
+                    property_value,
+                })
+            }
+            property_id if ReferenceList::is_reference_list(&property_id) => {
+                let references = ReferenceList::decode(property_id, buf)?;
+                let property_value = ReadPropertyValue::ReferenceList(references);
+
+                Ok(Self {
+                    object_id,
+                    property_id,
+                    array_index,
                     property_value,
                 })
             }
             property_id => {
-                let tag = Tag::decode(&mut reader, buf)?;
+                // the context wrapper (tag 3) has already been stripped above
                 let value =
-                    ApplicationDataValue::decode(&tag, &object_id, &property_id, &mut reader, buf)?;
+                    ApplicationDataValue::decode_inner(&object_id, &property_id, &mut reader, buf)?;
                 let property_value = ReadPropertyValue::ApplicationDataValue(value);
 
                 Ok(Self {
                     object_id,
                     property_id,
+                    array_index,
                     property_value,
                 })
             }
@@ -223,7 +515,7 @@ impl<'a> ReadPropertyAck<'a> {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ReadProperty {
     pub object_id: ObjectId,     // e.g ObjectDevice:20088
@@ -240,6 +532,13 @@ impl ReadProperty {
         }
     }
 
+    // reads a single element of an array property e.g. Priority_Array[8]
+    // index 0 reads the length of the array
+    pub fn with_array_index(mut self, array_index: u32) -> Self {
+        self.array_index = array_index;
+        self
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
         // object_id
         encode_context_object_id(writer, 0, &self.object_id);
@@ -261,10 +560,331 @@ impl ReadProperty {
         let property_id =
             decode_context_property_id(reader, buf, 1, "ReadProperty decode property_id")?;
 
+        // array_index (optional)
+        let array_index = match decode_optional_context_tag(reader, buf, 2)? {
+            Some(tag) => decode_unsigned(tag.value, reader, buf)? as u32,
+            None => BACNET_ARRAY_ALL,
+        };
+
         Ok(Self {
             object_id,
             property_id,
-            array_index: BACNET_ARRAY_ALL,
+            array_index,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::primitives::{
+            data_value::{ApplicationDataValue, Date, DateTime, Time},
+            object_reference::{DeviceObjectPropertyReference, DeviceObjectReference},
+            recipient::Recipient,
+            time_stamp::TimeStamp,
+        },
+        common::{
+            error::Error,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::BACNET_ARRAY_ALL,
+            time_value::SimpleApplicationDataValue,
+        },
+    };
+
+    use super::{ObjectReference, ReadProperty, ReadPropertyAck, ReadPropertyValue};
+
+    // Subordinate_List of StructuredView:1 with a local and a remote reference
+    const SUBORDINATE_LIST_ACK: [u8; 24] = [
+        0x0c, 0x07, 0x40, 0x00, 0x01, 0x19, 0xd3, 0x3e, 0x1c, 0x00, 0x00, 0x00, 0x05, 0x0c, 0x02,
+        0x00, 0x00, 0x64, 0x1c, 0x00, 0x80, 0x00, 0x02, 0x3f,
+    ];
+
+    #[test]
+    fn decode_subordinate_list() {
+        let buf = &SUBORDINATE_LIST_ACK;
+        let mut reader = Reader::new_with_len(buf.len());
+        let ack = ReadPropertyAck::decode(&mut reader, buf).unwrap();
+        assert_eq!(ack.property_id, PropertyId::PropSubordinateList);
+
+        let ReadPropertyValue::ReferenceList(list) = &ack.property_value else {
+            panic!("expected a reference list");
+        };
+        #[cfg(feature = "alloc")]
+        let mut references = list.references.clone().into_iter();
+        #[cfg(not(feature = "alloc"))]
+        let mut references = list.into_iter().map(|x| x.unwrap());
+
+        assert_eq!(
+            references.next(),
+            Some(ObjectReference::DeviceObject(DeviceObjectReference::new(
+                None,
+                ObjectId::new(ObjectType::ObjectAnalogInput, 5)
+            )))
+        );
+        assert_eq!(
+            references.next(),
+            Some(ObjectReference::DeviceObject(DeviceObjectReference::new(
+                Some(ObjectId::new(ObjectType::ObjectDevice, 100)),
+                ObjectId::new(ObjectType::ObjectAnalogValue, 2)
+            )))
+        );
+        assert_eq!(references.next(), None);
+    }
+
+    // Log_DeviceObjectProperty of TrendLog:1 which logs the present value of AnalogInput:1
+    const LOG_DEVICE_OBJECT_PROPERTY_ACK: [u8; 16] = [
+        0x0c, 0x05, 0x00, 0x00, 0x01, 0x19, 0x84, 0x3e, 0x0c, 0x00, 0x00, 0x00, 0x01, 0x19, 0x55,
+        0x3f,
+    ];
+
+    #[test]
+    fn decode_log_device_object_property() {
+        let buf = &LOG_DEVICE_OBJECT_PROPERTY_ACK;
+        let mut reader = Reader::new_with_len(buf.len());
+        let ack = ReadPropertyAck::decode(&mut reader, buf).unwrap();
+        assert_eq!(ack.property_id, PropertyId::PropLogDeviceObjectProperty);
+
+        let ReadPropertyValue::ObjectPropertyReference(reference) = &ack.property_value else {
+            panic!("expected an object property reference");
+        };
+        assert_eq!(
+            *reference,
+            DeviceObjectPropertyReference::new(
+                ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+                PropertyId::PropPresentValue
+            )
+        );
+
+        let mut out = [0; 32];
+        let mut writer = Writer::new(&mut out);
+        ack.encode(&mut writer);
+        assert_eq!(&writer.to_bytes()[1..], buf);
+    }
+
+    // Active_COV_Subscriptions of Device:1 with a single subscription from Device:100
+    const ACTIVE_COV_SUBSCRIPTIONS_ACK: [u8; 38] = [
+        0x0c, 0x02, 0x00, 0x00, 0x01, 0x19, 0x98, 0x3e, 0x0e, 0x0e, 0x0c, 0x02, 0x00, 0x00, 0x64,
+        0x0f, 0x19, 0x07, 0x0f, 0x1e, 0x0c, 0x00, 0x00, 0x00, 0x05, 0x19, 0x55, 0x1f, 0x29, 0x01,
+        0x39, 0x3c, 0x4c, 0x3f, 0x80, 0x00, 0x00, 0x3f,
+    ];
+
+    #[test]
+    fn decode_active_cov_subscriptions() {
+        let buf = &ACTIVE_COV_SUBSCRIPTIONS_ACK;
+        let mut reader = Reader::new_with_len(buf.len());
+        let ack = ReadPropertyAck::decode(&mut reader, buf).unwrap();
+
+        let ReadPropertyValue::CovSubscriptionList(list) = &ack.property_value else {
+            panic!("expected a cov subscription list");
+        };
+
+        #[cfg(feature = "alloc")]
+        let mut subscriptions = list.subscriptions.clone().into_iter();
+        #[cfg(not(feature = "alloc"))]
+        let mut subscriptions = list.into_iter().map(|x| x.unwrap());
+
+        let subscription = subscriptions.next().unwrap();
+        assert!(matches!(
+            subscription.recipient,
+            Recipient::Device(ObjectId {
+                object_type: ObjectType::ObjectDevice,
+                id: 100
+            })
+        ));
+        assert_eq!(subscription.process_id, 7);
+        assert_eq!(
+            subscription.monitored_property.object_id,
+            ObjectId::new(ObjectType::ObjectAnalogInput, 5)
+        );
+        assert_eq!(
+            subscription.monitored_property.property_id,
+            PropertyId::PropPresentValue
+        );
+        assert!(subscription.issue_confirmed_notifications);
+        assert_eq!(subscription.time_remaining_seconds, 60);
+        assert_eq!(subscription.cov_increment, Some(1.0));
+        assert!(subscriptions.next().is_none());
+    }
+
+    // Present_Value of AnalogValue:1 returned as the character string "on"
+    const STRING_PRESENT_VALUE_ACK: [u8; 13] = [
+        0x0c, 0x00, 0x80, 0x00, 0x01, 0x19, 0x55, 0x3e, 0x73, 0x00, b'o', b'n', 0x3f,
+    ];
+
+    // Present_Value of AnalogValue:1 returned as a 2 byte octet string
+    const OCTET_PRESENT_VALUE_ACK: [u8; 12] = [
+        0x0c, 0x00, 0x80, 0x00, 0x01, 0x19, 0x55, 0x3e, 0x62, 0xde, 0xad, 0x3f,
+    ];
+
+    #[test]
+    fn decode_string_present_value() {
+        let buf = &STRING_PRESENT_VALUE_ACK;
+        let mut reader = Reader::new_with_len(buf.len());
+        let ack = ReadPropertyAck::decode(&mut reader, buf).unwrap();
+        assert_eq!(
+            ack.object_id,
+            ObjectId::new(ObjectType::ObjectAnalogValue, 1)
+        );
+        assert_eq!(ack.property_id, PropertyId::PropPresentValue);
+
+        let ReadPropertyValue::ApplicationDataValue(value) = &ack.property_value else {
+            panic!("expected an application data value");
+        };
+        assert_eq!(value.as_str(), Some("on"));
+        assert_eq!(value.as_bytes(), None);
+    }
+
+    #[test]
+    fn decode_octet_string_present_value() {
+        let buf = &OCTET_PRESENT_VALUE_ACK;
+        let mut reader = Reader::new_with_len(buf.len());
+        let ack = ReadPropertyAck::decode(&mut reader, buf).unwrap();
+
+        let ReadPropertyValue::ApplicationDataValue(value) = &ack.property_value else {
+            panic!("expected an application data value");
+        };
+        assert_eq!(value.as_bytes(), Some([0xde, 0xad].as_slice()));
+        assert_eq!(value.as_str(), None);
+    }
+
+    fn object_identifier_ack(object_id: ObjectId, value: ObjectId) -> ReadPropertyAck<'static> {
+        ReadPropertyAck {
+            object_id,
+            property_id: PropertyId::PropObjectIdentifier,
+            array_index: BACNET_ARRAY_ALL,
+            property_value: ReadPropertyValue::ApplicationDataValue(
+                ApplicationDataValue::ObjectId(value),
+            ),
+        }
+    }
+
+    #[test]
+    fn verify_object_identifier() {
+        let device = ObjectId::new(ObjectType::ObjectDevice, 20088);
+        let other = ObjectId::new(ObjectType::ObjectDevice, 20089);
+
+        let ack = object_identifier_ack(device, device);
+        assert!(ack.verify_object_identifier(&device).is_ok());
+
+        // the ack echoes a different object id
+        let ack = object_identifier_ack(device, other);
+        let result = ack.verify_object_identifier(&device);
+        assert!(matches!(result, Err(Error::InvalidValue(_))));
+    }
+
+    #[test]
+    fn read_priority_array_element() {
+        let request = ReadProperty::new(
+            ObjectId::new(ObjectType::ObjectAnalogOutput, 1),
+            PropertyId::PropPriorityArray,
+        );
+        assert_eq!(request.array_index, BACNET_ARRAY_ALL);
+        let request = request.with_array_index(8);
+
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        let buf = writer.to_bytes();
+        assert_eq!(buf, [0x0c, 0x00, 0x40, 0x00, 0x01, 0x19, 0x57, 0x29, 0x08]);
+
+        let mut reader = Reader::new_with_len(buf.len());
+        let decoded = ReadProperty::decode(&mut reader, buf).unwrap();
+        assert_eq!(decoded.array_index, 8);
+    }
+
+    #[test]
+    fn decode_priority_array() {
+        // AnalogOutput:1 commanded to 21.5 at priority 8 and 18.0 at priority 16
+        #[rustfmt::skip]
+        let buf = [
+            0x0c, 0x00, 0x40, 0x00, 0x01, 0x19, 0x57, 0x3e,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x44, 0x41, 0xac, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x44, 0x41, 0x90, 0x00, 0x00,
+            0x3f,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let ack = ReadPropertyAck::decode(&mut reader, &buf).unwrap();
+        let ReadPropertyValue::PriorityArray(priority_array) = &ack.property_value else {
+            panic!("expected a priority array");
+        };
+        assert!(matches!(
+            priority_array.active(),
+            Some((8, ApplicationDataValue::Real(x))) if *x == 21.5
+        ));
+        assert!(matches!(
+            priority_array.get(16),
+            Some(ApplicationDataValue::Real(x)) if *x == 18.0
+        ));
+    }
+
+    #[test]
+    fn decode_event_time_stamps_element() {
+        // Event_Time_Stamps[2] (to-fault) of AnalogInput:1 as a date time
+        #[rustfmt::skip]
+        let buf = [
+            0x0c, 0x00, 0x00, 0x00, 0x01, 0x19, 0x82, 0x29, 0x02, 0x3e,
+            0x2e, 0xa4, 124, 3, 14, 4, 0xb4, 13, 30, 5, 0, 0x2f,
+            0x3f,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let ack = ReadPropertyAck::decode(&mut reader, &buf).unwrap();
+        assert_eq!(ack.array_index, 2);
+
+        let ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::TimeStamp(time_stamp)) =
+            &ack.property_value
+        else {
+            panic!("expected a time stamp");
+        };
+        let date = Date {
+            year: 2024,
+            month: 3,
+            day: 14,
+            wday: 4,
+        };
+        let time = Time {
+            hour: 13,
+            minute: 30,
+            second: 5,
+            hundredths: 0,
+        };
+        assert_eq!(*time_stamp, TimeStamp::DateTime(DateTime::new(date, time)));
+
+        let mut out = [0; 32];
+        let mut writer = Writer::new(&mut out);
+        ack.encode(&mut writer);
+        assert_eq!(&writer.to_bytes()[1..], buf);
+    }
+
+    #[test]
+    fn decode_weekly_schedule_element() {
+        // Weekly_Schedule[1] (monday) of Schedule:1 which sets 21.5 at 08:00
+        #[rustfmt::skip]
+        let buf = [
+            0x0c, 0x04, 0x40, 0x00, 0x01, 0x19, 0x7b, 0x29, 0x01, 0x3e,
+            0x0e, 0xb4, 8, 0, 0, 0, 0x44, 0x41, 0xac, 0x00, 0x00, 0x0f,
+            0x3f,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let ack = ReadPropertyAck::decode(&mut reader, &buf).unwrap();
+
+        let ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::DailySchedule(day)) =
+            &ack.property_value
+        else {
+            panic!("expected a daily schedule");
+        };
+        #[cfg(feature = "alloc")]
+        let mut time_values = day.clone().into_iter();
+        #[cfg(not(feature = "alloc"))]
+        let mut time_values = day.into_iter().map(|x| x.unwrap());
+
+        let time_value = time_values.next().unwrap();
+        assert_eq!(time_value.time.hour, 8);
+        assert!(matches!(
+            time_value.value,
+            SimpleApplicationDataValue::Real(x) if x == 21.5
+        ));
+        assert!(time_values.next().is_none());
+    }
+}
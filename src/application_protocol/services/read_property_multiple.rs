@@ -3,15 +3,19 @@ use core::fmt::Display;
 use crate::{
     application_protocol::{
         confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
-        primitives::data_value::ApplicationDataValue,
+        primitives::{
+            data_value::{ApplicationDataValue, CharacterString},
+            time_stamp::TimeStamp,
+        },
     },
     common::{
         daily_schedule::WeeklySchedule,
         error::Error,
         helper::{
             decode_context_object_id, decode_context_property_id, decode_unsigned,
-            encode_closing_tag, encode_context_enumerated, encode_context_object_id,
-            encode_context_unsigned, encode_opening_tag, get_tagged_body, get_tagged_body_for_tag,
+            encode_application_enumerated, encode_closing_tag, encode_context_enumerated,
+            encode_context_object_id, encode_context_unsigned, encode_opening_tag, get_tagged_body,
+            get_tagged_body_for_tag,
         },
         io::{Reader, Writer},
         object_id::{ObjectId, ObjectType},
@@ -25,7 +29,7 @@ use crate::{
 #[cfg(feature = "alloc")]
 use {
     crate::common::spooky::Phantom,
-    alloc::{string::String, vec::Vec},
+    alloc::{string::String, vec, vec::Vec},
 };
 
 #[cfg(not(feature = "alloc"))]
@@ -43,6 +47,31 @@ pub struct ReadPropertyMultipleAck<'a> {
     pub objects_with_results: Vec<ObjectWithResults<'a>>,
 }
 
+// upper bounds on the number of items decoded into owned collections
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReadPropertyMultipleLimits {
+    pub max_objects: usize,
+    pub max_results_per_object: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl ReadPropertyMultipleLimits {
+    pub const DEFAULT_MAX_OBJECTS: usize = 1024;
+    pub const DEFAULT_MAX_RESULTS_PER_OBJECT: usize = 1024;
+}
+
+#[cfg(feature = "alloc")]
+impl Default for ReadPropertyMultipleLimits {
+    fn default() -> Self {
+        Self {
+            max_objects: Self::DEFAULT_MAX_OBJECTS,
+            max_results_per_object: Self::DEFAULT_MAX_RESULTS_PER_OBJECT,
+        }
+    }
+}
+
 #[cfg(not(feature = "alloc"))]
 impl<'a> IntoIterator for &'_ ReadPropertyMultipleAck<'a> {
     type Item = Result<ObjectWithResults<'a>, Error>;
@@ -135,6 +164,15 @@ impl<'a> ObjectWithResults<'a> {
 
     #[cfg(feature = "alloc")]
     pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        Self::decode_with_limits(reader, buf, &ReadPropertyMultipleLimits::default())
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn decode_with_limits(
+        reader: &mut Reader,
+        buf: &[u8],
+        limits: &ReadPropertyMultipleLimits,
+    ) -> Result<Self, Error> {
         let object_id =
             decode_context_object_id(reader, buf, 0, "ObjectWithResults decode object_id")?;
         let inner_buf =
@@ -143,7 +181,14 @@ impl<'a> ObjectWithResults<'a> {
 
         let mut property_results = Vec::new();
         while !inner_reader.eof() {
-            let property_result = PropertyResult::decode(&mut inner_reader, inner_buf, &object_id)?;
+            if property_results.len() >= limits.max_results_per_object {
+                return Err(Error::Length((
+                    "ObjectWithResults decode too many property results",
+                    limits.max_results_per_object as u32,
+                )));
+            }
+            let property_result = PropertyResult::decode(&mut inner_reader, inner_buf, &object_id)
+                .map_err(|e| e.with_context("ObjectWithResults property_results"))?;
             property_results.push(property_result);
         }
 
@@ -172,15 +217,13 @@ impl<'a> Iterator for PropertyResultIter<'a> {
             return None;
         }
 
-        Some(PropertyResult::decode(
-            &mut self.reader,
-            self.buf,
-            &self.object_id,
-        ))
+        let property_result = PropertyResult::decode(&mut self.reader, self.buf, &self.object_id)
+            .map_err(|e| e.with_context("ObjectWithResults property_results"));
+        Some(property_result)
     }
 }
 
-fn read_error(reader: &mut Reader, buf: &[u8]) -> Result<PropertyAccessError, Error> {
+pub(crate) fn read_error(reader: &mut Reader, buf: &[u8]) -> Result<PropertyAccessError, Error> {
     // error class enumerated
     let tag = Tag::decode_expected(
         reader,
@@ -237,6 +280,7 @@ impl<'a> PropertyResultList<'a> {
     }
 
     pub fn encode(&self, writer: &mut Writer) {
+        writer.extend_from_slice(self.buf);
         for item in self.property_results {
             item.encode(writer);
         }
@@ -256,16 +300,24 @@ impl<'a> PropertyResult<'a> {
     const PROPERTY_VALUE_ERROR_TAG: u8 = 5;
 
     pub fn encode(&self, writer: &mut Writer) {
-        encode_context_unsigned(writer, Self::PROPERTY_ID_TAG, self.id as u32);
+        encode_context_unsigned(writer, Self::PROPERTY_ID_TAG, u32::from(self.id));
         match &self.value {
             PropertyValue::PropValue(val) => {
                 encode_opening_tag(writer, Self::PROPERTY_VALUE_TAG);
                 val.encode(writer);
                 encode_closing_tag(writer, Self::PROPERTY_VALUE_TAG);
             }
-            PropertyValue::PropError(_) => todo!(),
-            PropertyValue::PropObjectName(_) => todo!(),
-            PropertyValue::PropDescription(_) => todo!(),
+            PropertyValue::PropError(error) => {
+                encode_opening_tag(writer, Self::PROPERTY_VALUE_ERROR_TAG);
+                encode_application_enumerated(writer, u32::from(&error.error_class));
+                encode_application_enumerated(writer, u32::from(&error.error_code));
+                encode_closing_tag(writer, Self::PROPERTY_VALUE_ERROR_TAG);
+            }
+            PropertyValue::PropObjectName(x) | PropertyValue::PropDescription(x) => {
+                encode_opening_tag(writer, Self::PROPERTY_VALUE_TAG);
+                CharacterString::new(x).encode_application(writer);
+                encode_closing_tag(writer, Self::PROPERTY_VALUE_TAG);
+            }
         }
     }
 
@@ -290,7 +342,8 @@ impl<'a> PropertyResult<'a> {
             tag_number,
             &property_id,
             object_id,
-        )?;
+        )
+        .map_err(|e| e.with_context("PropertyResult value"))?;
 
         Ok(PropertyResult {
             id: property_id,
@@ -309,10 +362,10 @@ impl<'a> PropertyResult<'a> {
         if tag_number == Self::PROPERTY_VALUE_TAG {
             match property_id {
                 PropertyId::PropEventTimeStamps => {
-                    // ignore for now
-                    Ok(PropertyValue::PropValue(ApplicationDataValue::Boolean(
-                        false,
-                    )))
+                    let time_stamps = TimeStamp::decode_event_time_stamps(reader, buf)?;
+                    Ok(PropertyValue::PropValue(
+                        ApplicationDataValue::EventTimeStamps(time_stamps),
+                    ))
                 }
                 PropertyId::PropWeeklySchedule => {
                     let weekly_schedule = WeeklySchedule::decode(reader, buf)?;
@@ -321,10 +374,20 @@ impl<'a> PropertyResult<'a> {
                     ))
                 }
                 property_id => {
-                    let tag = Tag::decode(reader, buf)?;
+                    // the context wrapper has already been stripped by get_tagged_body
                     let value =
-                        ApplicationDataValue::decode(&tag, object_id, property_id, reader, buf)?;
-                    Ok(PropertyValue::PropValue(value))
+                        ApplicationDataValue::decode_inner(object_id, property_id, reader, buf)?;
+
+                    // names are typed so that clients don't have to dig them out of a data value
+                    match (property_id, value) {
+                        (PropertyId::PropObjectName, ApplicationDataValue::CharacterString(x)) => {
+                            Ok(PropertyValue::PropObjectName(x.inner))
+                        }
+                        (PropertyId::PropDescription, ApplicationDataValue::CharacterString(x)) => {
+                            Ok(PropertyValue::PropDescription(x.inner))
+                        }
+                        (_, value) => Ok(PropertyValue::PropValue(value)),
+                    }
                 }
             }
         } else if tag_number == Self::PROPERTY_VALUE_ERROR_TAG {
@@ -346,7 +409,7 @@ impl<'a> PropertyResult<'a> {
 pub enum PropertyValue<'a> {
     PropValue(ApplicationDataValue<'a>),
     PropError(PropertyAccessError),
-    // TODO: figure out if we need these
+    // character string values of the Description and Object_Name properties
     PropDescription(&'a str),
     PropObjectName(&'a str),
 }
@@ -357,13 +420,14 @@ pub enum PropertyValue<'a> {
 pub enum PropertyValue<'a> {
     PropValue(ApplicationDataValue<'a>),
     PropError(PropertyAccessError),
-    // TODO: figure out if we need these
+    // character string values of the Description and Object_Name properties
     PropDescription(String),
     PropObjectName(String),
 }
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PropertyAccessError {
     pub error_class: ErrorClass,
     pub error_code: ErrorCode,
@@ -373,6 +437,7 @@ impl<'a> Display for PropertyValue<'a> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match &self {
             Self::PropValue(x) => write!(f, "{}", x),
+            Self::PropDescription(x) | Self::PropObjectName(x) => write!(f, "{}", x),
             _ => write!(f, "property value unprintable",),
         }
     }
@@ -404,6 +469,11 @@ impl<'a> ReadPropertyMultipleAck<'a> {
 
     pub fn encode(&self, writer: &mut Writer) {
         writer.push(ConfirmedServiceChoice::ReadPropMultiple as u8);
+
+        // a decoded ack re-encodes the raw objects it was decoded from
+        #[cfg(not(feature = "alloc"))]
+        writer.extend_from_slice(self.buf);
+
         for item in self.objects_with_results.iter() {
             item.encode(writer);
         }
@@ -411,23 +481,38 @@ impl<'a> ReadPropertyMultipleAck<'a> {
 
     #[cfg(feature = "alloc")]
     pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        Self::decode_with_limits(reader, buf, &ReadPropertyMultipleLimits::default())
+    }
+
+    // a crafted frame could otherwise make us allocate far more than a constrained device can afford
+    #[cfg(feature = "alloc")]
+    pub fn decode_with_limits(
+        reader: &mut Reader,
+        buf: &[u8],
+        limits: &ReadPropertyMultipleLimits,
+    ) -> Result<Self, Error> {
         let mut objects_with_results = Vec::new();
 
         while !reader.eof() {
-            let object_with_results = ObjectWithResults::decode(reader, buf)?;
+            if objects_with_results.len() >= limits.max_objects {
+                return Err(Error::Length((
+                    "ReadPropertyMultipleAck decode too many objects",
+                    limits.max_objects as u32,
+                )));
+            }
+            let object_with_results = ObjectWithResults::decode_with_limits(reader, buf, limits)
+                .map_err(|e| e.with_context("ReadPropertyMultipleAck objects_with_results"))?;
             objects_with_results.push(object_with_results);
         }
 
         Ok(Self::new(objects_with_results))
     }
 
+    // the objects are decoded lazily when iterated over so nothing is copied here
     #[cfg(not(feature = "alloc"))]
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
-        let buf = &buf[reader.index..reader.end];
-        Ok(Self {
-            buf,
-            objects_with_results: &[],
-        })
+        let buf = reader.read_remaining(buf)?;
+        Ok(Self::new_from_buf(buf))
     }
 }
 
@@ -444,7 +529,8 @@ impl<'a> Iterator for ObjectWithResultsIter<'a> {
             return None;
         }
 
-        let object_with_results = ObjectWithResults::decode(&mut self.reader, self.buf);
+        let object_with_results = ObjectWithResults::decode(&mut self.reader, self.buf)
+            .map_err(|e| e.with_context("ReadPropertyMultipleAck objects_with_results"));
         Some(object_with_results)
     }
 }
@@ -682,6 +768,24 @@ impl<'a> ReadPropertyMultiple<'a> {
         }
     }
 
+    // groups a flat list of (object, property) pairs by object
+    // objects appear in the order they are first seen and properties keep their relative order
+    #[cfg(feature = "alloc")]
+    pub fn from_pairs(pairs: &[(ObjectId, PropertyId)]) -> Self {
+        let mut objects: Vec<ReadPropertyMultipleObject<'a>> = Vec::new();
+        for (object_id, property_id) in pairs {
+            match objects.iter_mut().find(|x| x.object_id == *object_id) {
+                Some(object) => object.property_ids.push(*property_id),
+                None => objects.push(ReadPropertyMultipleObject::new(
+                    *object_id,
+                    vec![*property_id],
+                )),
+            }
+        }
+
+        Self::new(objects)
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
         for object in self.objects.iter() {
             object.encode(writer)
@@ -690,7 +794,7 @@ impl<'a> ReadPropertyMultiple<'a> {
 
     #[cfg(not(feature = "alloc"))]
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
-        let buf = &buf[reader.index..reader.end];
+        let buf = reader.read_remaining(buf)?;
         Ok(Self {
             buf,
             _array_index: BACNET_ARRAY_ALL,
@@ -700,7 +804,7 @@ impl<'a> ReadPropertyMultiple<'a> {
 
     #[cfg(feature = "alloc")]
     pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
-        let inner_buf = &buf[reader.index..reader.end];
+        let inner_buf = reader.read_remaining(buf)?;
         let mut inner_reader = Reader::new_with_len(inner_buf.len());
         let mut objects = Vec::new();
 
@@ -714,6 +818,121 @@ impl<'a> ReadPropertyMultiple<'a> {
     }
 }
 
+// groups (object, property) pairs without a heap for up to OBJECTS objects with up to PROPERTIES property ids each
+// the no-alloc equivalent of ReadPropertyMultiple::from_pairs
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReadPropertyMultipleBuilder<const OBJECTS: usize, const PROPERTIES: usize> {
+    object_ids: [ObjectId; OBJECTS],
+    property_ids: [[PropertyId; PROPERTIES]; OBJECTS],
+    property_counts: [usize; OBJECTS],
+    object_count: usize,
+}
+
+// the objects of a builder, borrowed in the form ReadPropertyMultiple::new expects
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReadPropertyMultipleObjects<'a, const OBJECTS: usize> {
+    objects: [ReadPropertyMultipleObject<'a>; OBJECTS],
+    len: usize,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a, const OBJECTS: usize> ReadPropertyMultipleObjects<'a, OBJECTS> {
+    pub fn as_slice(&self) -> &[ReadPropertyMultipleObject<'a>] {
+        &self.objects[..self.len]
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<const OBJECTS: usize, const PROPERTIES: usize> Default
+    for ReadPropertyMultipleBuilder<OBJECTS, PROPERTIES>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<const OBJECTS: usize, const PROPERTIES: usize>
+    ReadPropertyMultipleBuilder<OBJECTS, PROPERTIES>
+{
+    pub fn new() -> Self {
+        Self {
+            object_ids: [ObjectId::new(ObjectType::ObjectDevice, 0); OBJECTS],
+            property_ids: [[PropertyId::PropAll; PROPERTIES]; OBJECTS],
+            property_counts: [0; OBJECTS],
+            object_count: 0,
+        }
+    }
+
+    // objects appear in the order they are first seen and properties keep their relative order
+    pub fn from_pairs(pairs: &[(ObjectId, PropertyId)]) -> Result<Self, Error> {
+        let mut builder = Self::new();
+        for (object_id, property_id) in pairs {
+            builder.add(*object_id, *property_id)?;
+        }
+        Ok(builder)
+    }
+
+    // fails if there is no room left for the object or its property id
+    pub fn add(&mut self, object_id: ObjectId, property_id: PropertyId) -> Result<(), Error> {
+        let index = match self.object_ids[..self.object_count]
+            .iter()
+            .position(|x| *x == object_id)
+        {
+            Some(index) => index,
+            None => {
+                if self.object_count == OBJECTS {
+                    return Err(Error::Length((
+                        "ReadPropertyMultipleBuilder has no room for another object",
+                        OBJECTS as u32,
+                    )));
+                }
+                self.object_ids[self.object_count] = object_id;
+                self.object_count += 1;
+                self.object_count - 1
+            }
+        };
+
+        let count = self.property_counts[index];
+        if count == PROPERTIES {
+            return Err(Error::Length((
+                "ReadPropertyMultipleBuilder has no room for another property id",
+                PROPERTIES as u32,
+            )));
+        }
+        self.property_ids[index][count] = property_id;
+        self.property_counts[index] += 1;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.object_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.object_count == 0
+    }
+
+    // let objects = builder.objects();
+    // let request = ReadPropertyMultiple::new(objects.as_slice());
+    pub fn objects(&self) -> ReadPropertyMultipleObjects<'_, OBJECTS> {
+        let objects = core::array::from_fn(|i| {
+            ReadPropertyMultipleObject::new(
+                self.object_ids[i],
+                &self.property_ids[i][..self.property_counts[i]],
+            )
+        });
+        ReadPropertyMultipleObjects {
+            objects,
+            len: self.object_count,
+        }
+    }
+}
+
 #[cfg(not(feature = "alloc"))]
 impl<'a> IntoIterator for &'_ ReadPropertyMultiple<'a> {
     type Item = Result<ReadPropertyMultipleObject<'a>, Error>;
@@ -746,3 +965,328 @@ impl<'a> Iterator for ReadPropertyMultipleIter<'a> {
         Some(object_with_property_ids)
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "alloc")]
+mod tests {
+    use crate::{
+        application_protocol::primitives::{
+            data_value::{ApplicationDataValue, Date, DateTime, Time},
+            time_stamp::TimeStamp,
+        },
+        common::{
+            error::Error,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::ErrorCode,
+        },
+    };
+
+    use alloc::vec::Vec;
+
+    use super::{
+        PropertyValue, ReadPropertyMultiple, ReadPropertyMultipleAck, ReadPropertyMultipleLimits,
+    };
+
+    // analog-input:1 with two present values (real 1.0) followed by analog-input:2 with one
+    const ACK: [u8; 41] = [
+        0x0c, 0x00, 0x00, 0x00, 0x01, 0x1e, 0x29, 0x55, 0x4e, 0x44, 0x3f, 0x80, 0x00, 0x00, 0x4f,
+        0x29, 0x55, 0x4e, 0x44, 0x3f, 0x80, 0x00, 0x00, 0x4f, 0x1f, 0x0c, 0x00, 0x00, 0x00, 0x02,
+        0x1e, 0x29, 0x55, 0x4e, 0x44, 0x3f, 0x80, 0x00, 0x00, 0x4f, 0x1f,
+    ];
+
+    #[test]
+    fn decode_within_limits() {
+        let mut reader = Reader::new_with_len(ACK.len());
+        let decoded = ReadPropertyMultipleAck::decode(&mut reader, &ACK).unwrap();
+        assert_eq!(decoded.objects_with_results.len(), 2);
+        assert_eq!(decoded.objects_with_results[0].property_results.len(), 2);
+    }
+
+    #[test]
+    fn decode_too_many_objects() {
+        let mut reader = Reader::new_with_len(ACK.len());
+        let limits = ReadPropertyMultipleLimits {
+            max_objects: 1,
+            ..Default::default()
+        };
+        let result = ReadPropertyMultipleAck::decode_with_limits(&mut reader, &ACK, &limits);
+        assert!(matches!(result, Err(Error::Length(_))));
+    }
+
+    #[test]
+    fn decode_too_many_results() {
+        let mut reader = Reader::new_with_len(ACK.len());
+        let limits = ReadPropertyMultipleLimits {
+            max_results_per_object: 1,
+            ..Default::default()
+        };
+        let error =
+            ReadPropertyMultipleAck::decode_with_limits(&mut reader, &ACK, &limits).unwrap_err();
+        assert!(matches!(error.root(), Error::Length(_)));
+    }
+
+    #[test]
+    fn group_pairs_by_object() {
+        let ai_1 = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let ai_2 = ObjectId::new(ObjectType::ObjectAnalogInput, 2);
+        let pairs = [
+            (ai_1, PropertyId::PropPresentValue),
+            (ai_2, PropertyId::PropPresentValue),
+            (ai_1, PropertyId::PropUnits),
+        ];
+        let rpm = ReadPropertyMultiple::from_pairs(&pairs);
+        assert_eq!(rpm.objects.len(), 2);
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        rpm.encode(&mut writer);
+        assert_eq!(
+            writer.to_bytes(),
+            &[
+                0x0c, 0x00, 0x00, 0x00, 0x01, 0x1e, 0x09, 0x55, 0x09, 0x75, 0x1f, 0x0c, 0x00, 0x00,
+                0x00, 0x02, 0x1e, 0x09, 0x55, 0x1f,
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_error_reports_field_path() {
+        // analog-input:1 present value with a reserved application tag
+        let buf = [
+            0x0c, 0x00, 0x00, 0x00, 0x01, 0x1e, 0x29, 0x55, 0x4e, 0xd0, 0x4f, 0x1f,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let error = ReadPropertyMultipleAck::decode(&mut reader, &buf).unwrap_err();
+
+        let path: Vec<_> = error.context_path().collect();
+        assert_eq!(
+            path,
+            [
+                "ReadPropertyMultipleAck objects_with_results",
+                "ObjectWithResults property_results",
+                "PropertyResult value",
+            ]
+        );
+        assert!(matches!(error.root(), Error::TagNotSupported(_)));
+    }
+
+    #[test]
+    fn decode_names_are_typed() {
+        // analog-input:1 object name "Zone" and description "Temp"
+        #[rustfmt::skip]
+        let buf = [
+            0x0c, 0x00, 0x00, 0x00, 0x01, 0x1e,
+            0x29, 0x4d, 0x4e, 0x75, 0x05, 0x00, b'Z', b'o', b'n', b'e', 0x4f,
+            0x29, 0x1c, 0x4e, 0x75, 0x05, 0x00, b'T', b'e', b'm', b'p', 0x4f,
+            0x1f,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let ack = ReadPropertyMultipleAck::decode(&mut reader, &buf).unwrap();
+        let results = &ack.objects_with_results[0].property_results;
+
+        assert!(matches!(
+            &results[0].value,
+            PropertyValue::PropObjectName(x) if x == "Zone"
+        ));
+        assert!(matches!(
+            &results[1].value,
+            PropertyValue::PropDescription(x) if x == "Temp"
+        ));
+
+        let mut out = [0; 64];
+        let mut writer = Writer::new(&mut out);
+        ack.encode(&mut writer);
+        assert_eq!(&writer.to_bytes()[1..], &buf);
+    }
+
+    #[test]
+    fn decode_property_error() {
+        // analog-input:1 present value followed by property / unknown-property for the units
+        #[rustfmt::skip]
+        let buf = [
+            0x0c, 0x00, 0x00, 0x00, 0x01, 0x1e,
+            0x29, 0x55, 0x4e, 0x44, 0x3f, 0x80, 0x00, 0x00, 0x4f,
+            0x29, 0x75, 0x5e, 0x91, 0x02, 0x91, 0x20, 0x5f,
+            0x1f,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let ack = ReadPropertyMultipleAck::decode(&mut reader, &buf).unwrap();
+        let results = &ack.objects_with_results[0].property_results;
+        assert!(matches!(
+            &results[1].value,
+            PropertyValue::PropError(x) if x.error_code == ErrorCode::UnknownProperty
+        ));
+
+        let mut out = [0; 64];
+        let mut writer = Writer::new(&mut out);
+        ack.encode(&mut writer);
+        assert_eq!(&writer.to_bytes()[1..], &buf);
+    }
+
+    #[test]
+    fn decode_truncated_is_error() {
+        // only the whole ack and a cut after the first object (at 25 bytes) are valid
+        for len in 1..ACK.len() {
+            let mut reader = Reader::new_with_len(len);
+            let result = ReadPropertyMultipleAck::decode(&mut reader, &ACK[..len]);
+            assert_eq!(result.is_ok(), len == 25, "truncated to {} bytes", len);
+        }
+    }
+
+    #[test]
+    fn decode_event_time_stamps() {
+        // analog-input:1 event time stamps (sequence number 79, a date time and a time)
+        // followed by its present value, 79 is the same byte as the closing tag 4
+        #[rustfmt::skip]
+        let buf = [
+            0x0c, 0x00, 0x00, 0x00, 0x01, 0x1e,
+            0x29, 0x82, 0x4e,
+            0x19, 0x4f,
+            0x2e, 0xa4, 0x7c, 0x03, 0x0e, 0x04, 0xb4, 0x0d, 0x1e, 0x05, 0x00, 0x2f,
+            0x0c, 0x0d, 0x1e, 0x05, 0x00,
+            0x4f,
+            0x29, 0x55, 0x4e, 0x44, 0x3f, 0x80, 0x00, 0x00, 0x4f,
+            0x1f,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let ack = ReadPropertyMultipleAck::decode(&mut reader, &buf).unwrap();
+        let results = &ack.objects_with_results[0].property_results;
+        assert_eq!(results.len(), 2);
+
+        let time = Time {
+            hour: 13,
+            minute: 30,
+            second: 5,
+            hundredths: 0,
+        };
+        let date = Date {
+            year: 2024,
+            month: 3,
+            day: 14,
+            wday: 4,
+        };
+        assert_eq!(results[0].id, PropertyId::PropEventTimeStamps);
+        match &results[0].value {
+            PropertyValue::PropValue(ApplicationDataValue::EventTimeStamps(time_stamps)) => {
+                assert_eq!(
+                    time_stamps,
+                    &[
+                        TimeStamp::SequenceNumber(79),
+                        TimeStamp::DateTime(DateTime::new(date, time.clone())),
+                        TimeStamp::Time(time),
+                    ]
+                )
+            }
+            x => panic!("expected event time stamps, got {:?}", x),
+        }
+        assert!(matches!(
+            results[1].value,
+            PropertyValue::PropValue(ApplicationDataValue::Real(x)) if x == 1.0
+        ));
+
+        let mut out = [0; 64];
+        let mut writer = Writer::new(&mut out);
+        ack.encode(&mut writer);
+        assert_eq!(&writer.to_bytes()[1..], &buf);
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "alloc"))]
+mod no_alloc_tests {
+    use crate::{
+        application_protocol::primitives::data_value::ApplicationDataValue,
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+    };
+
+    use super::{
+        PropertyValue, ReadPropertyMultiple, ReadPropertyMultipleAck, ReadPropertyMultipleBuilder,
+    };
+
+    // analog-input:1 with two present values (real 1.0) followed by analog-input:2 with one
+    const ACK: [u8; 41] = [
+        0x0c, 0x00, 0x00, 0x00, 0x01, 0x1e, 0x29, 0x55, 0x4e, 0x44, 0x3f, 0x80, 0x00, 0x00, 0x4f,
+        0x29, 0x55, 0x4e, 0x44, 0x3f, 0x80, 0x00, 0x00, 0x4f, 0x1f, 0x0c, 0x00, 0x00, 0x00, 0x02,
+        0x1e, 0x29, 0x55, 0x4e, 0x44, 0x3f, 0x80, 0x00, 0x00, 0x4f, 0x1f,
+    ];
+
+    #[test]
+    fn decode_lazily_from_buffer() {
+        let mut reader = Reader::new_with_len(ACK.len());
+        let ack = ReadPropertyMultipleAck::decode(&mut reader, &ACK).unwrap();
+        assert!(reader.eof());
+
+        let mut objects = (&ack).into_iter().map(|x| x.unwrap());
+        let object = objects.next().unwrap();
+        assert_eq!(
+            object.object_id,
+            ObjectId::new(ObjectType::ObjectAnalogInput, 1)
+        );
+        let mut count = 0;
+        for result in &object.property_results {
+            let result = result.unwrap();
+            assert_eq!(result.id, PropertyId::PropPresentValue);
+            assert!(matches!(
+                result.value,
+                PropertyValue::PropValue(ApplicationDataValue::Real(x)) if x == 1.0
+            ));
+            count += 1;
+        }
+        assert_eq!(count, 2);
+
+        let object = objects.next().unwrap();
+        assert_eq!(
+            object.object_id,
+            ObjectId::new(ObjectType::ObjectAnalogInput, 2)
+        );
+        assert!(objects.next().is_none());
+
+        // a decoded ack encodes back to the same bytes
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        ack.encode(&mut writer);
+        assert_eq!(&writer.to_bytes()[1..], &ACK);
+    }
+
+    #[test]
+    fn build_without_heap() {
+        let ai1 = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let ai2 = ObjectId::new(ObjectType::ObjectAnalogInput, 2);
+        let builder = ReadPropertyMultipleBuilder::<2, 2>::from_pairs(&[
+            (ai1, PropertyId::PropPresentValue),
+            (ai2, PropertyId::PropPresentValue),
+            (ai1, PropertyId::PropObjectName),
+        ])
+        .unwrap();
+        assert_eq!(builder.len(), 2);
+
+        let objects = builder.objects();
+        let request = ReadPropertyMultiple::new(objects.as_slice());
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        #[rustfmt::skip]
+        let expected = [
+            0x0c, 0x00, 0x00, 0x00, 0x01, 0x1e, 0x09, 0x55, 0x09, 0x4d, 0x1f,
+            0x0c, 0x00, 0x00, 0x00, 0x02, 0x1e, 0x09, 0x55, 0x1f,
+        ];
+        assert_eq!(writer.to_bytes(), expected);
+    }
+
+    #[test]
+    fn build_past_capacity_is_error() {
+        let ai1 = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let ai2 = ObjectId::new(ObjectType::ObjectAnalogInput, 2);
+        let mut builder = ReadPropertyMultipleBuilder::<1, 1>::new();
+        builder.add(ai1, PropertyId::PropPresentValue).unwrap();
+        assert!(builder.add(ai1, PropertyId::PropObjectName).is_err());
+        assert!(builder.add(ai2, PropertyId::PropPresentValue).is_err());
+        assert_eq!(builder.len(), 1);
+    }
+}
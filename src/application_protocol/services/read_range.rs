@@ -1,15 +1,18 @@
 use crate::{
     application_protocol::{
         confirmed::ConfirmedServiceChoice,
-        primitives::data_value::{BitString, Date, Time},
+        primitives::data_value::{BitString, Date, DateTime, Time},
+        services::read_property_multiple::{read_error, PropertyAccessError},
     },
     common::{
         error::{Error, Unimplemented},
         helper::{
-            decode_context_object_id, decode_context_property_id, decode_signed, decode_unsigned,
+            decode_context_object_id, decode_context_property_id, decode_optional_context_tag,
+            decode_signed, decode_unsigned, encode_application_enumerated,
             encode_application_signed, encode_application_unsigned, encode_closing_tag,
-            encode_context_enumerated, encode_context_object_id, encode_context_unsigned,
-            encode_opening_tag, get_tagged_body_for_tag,
+            encode_context_bool, encode_context_enumerated, encode_context_object_id,
+            encode_context_signed, encode_context_unsigned, encode_opening_tag,
+            get_tagged_body_for_tag, with_context_tag,
         },
         io::{Reader, Writer},
         object_id::ObjectId,
@@ -22,7 +25,7 @@ use crate::{
 #[cfg(feature = "alloc")]
 use {alloc::vec::Vec, bacnet_macros::remove_lifetimes_from_fn_args};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ReadRange {
     pub object_id: ObjectId,     // e.g ObjectTrendLog
@@ -31,7 +34,7 @@ pub struct ReadRange {
     pub request_type: ReadRangeRequestType,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ReadRangeRequestType {
     ByPosition(ReadRangeByPosition),
@@ -40,30 +43,39 @@ pub enum ReadRangeRequestType {
     All,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+// a positive count reads the item at index and the items after it,
+// a negative count reads the item at index and the items before it
 pub struct ReadRangeByPosition {
     pub index: u32,
-    pub count: u32,
+    pub count: i32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+// a positive count reads the item with sequence_num and the items after it,
+// a negative count reads the item with sequence_num and the items before it
 pub struct ReadRangeBySequence {
     pub sequence_num: u32,
-    pub count: u32,
+    pub count: i32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+// a positive count reads the items logged after the date and time,
+// a negative count reads the items logged before it
 pub struct ReadRangeByTime {
     pub date: Date,
     pub time: Time,
-    pub count: u32,
+    pub count: i32,
 }
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+// without alloc the items borrow the decode buffer so the ack can only be serialized
+#[cfg_attr(all(feature = "serde", feature = "alloc"), derive(serde::Deserialize))]
 pub struct ReadRangeAck<'a> {
     pub object_id: ObjectId,
     pub property_id: PropertyId,
@@ -175,9 +187,31 @@ pub struct ReadRangeItems<'a> {
     buf: &'a [u8],
 }
 
+// serializes the same way as the alloc version: a struct with a list of items
+#[cfg(all(feature = "serde", not(feature = "alloc")))]
+impl serde::Serialize for ReadRangeItems<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        struct Items<'b, 'a>(&'b ReadRangeItems<'a>);
+
+        impl serde::Serialize for Items<'_, '_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let items = self.0.items.iter().cloned().map(Ok).chain(self.0);
+                crate::common::helper::serialize_decoded(serializer, items)
+            }
+        }
+
+        let mut state = serializer.serialize_struct("ReadRangeItems", 1)?;
+        state.serialize_field("items", &Items(self))?;
+        state.end()
+    }
+}
+
 #[cfg(feature = "alloc")]
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReadRangeItems<'a> {
     pub items: Vec<ReadRangeItem<'a>>,
 }
@@ -185,18 +219,22 @@ pub struct ReadRangeItems<'a> {
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub enum ReadRangeValue {
-    Status,
+pub enum ReadRangeValue<'a> {
+    // a change in the state of the log itself (log-disabled, buffer-purged, log-interrupted)
+    #[cfg_attr(all(feature = "serde", not(feature = "alloc")), serde(borrow))]
+    Status(BitString<'a>),
     Bool(bool),
     Real(f32),
     Enum(u32),
     Unsigned(u32),
     Signed(i32),
-    Bits,
+    #[cfg_attr(all(feature = "serde", not(feature = "alloc")), serde(borrow))]
+    Bits(BitString<'a>),
     Null,
-    Error,
-    Delta,
-    Any,
+    // the device failed to read the monitored property
+    Error(PropertyAccessError),
+    // the clock was changed by this many seconds
+    Delta(f32),
 }
 
 #[derive(Debug, Clone)]
@@ -313,11 +351,14 @@ impl<'a> Iterator for ReadRangeItemsIter<'a> {
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReadRangeItem<'a> {
     pub date: Date,
     pub time: Time,
-    pub value: ReadRangeValue,
-    pub status_flags: BitString<'a>,
+    #[cfg_attr(all(feature = "serde", not(feature = "alloc")), serde(borrow))]
+    pub value: ReadRangeValue<'a>,
+    #[cfg_attr(all(feature = "serde", not(feature = "alloc")), serde(borrow))]
+    pub status_flags: Option<BitString<'a>>, // not all logs record status flags
 }
 
 impl<'a> ReadRangeItem<'a> {
@@ -328,23 +369,13 @@ impl<'a> ReadRangeItem<'a> {
     pub fn encode(&self, writer: &mut Writer) {
         // date and time
         Tag::new(TagNumber::ContextSpecificOpening(Self::DATE_TIME_TAG), 0).encode(writer);
-        Tag::new(
-            TagNumber::Application(ApplicationTagNumber::Date),
-            Date::LEN,
-        )
-        .encode(writer);
-        self.date.encode(writer);
-        Tag::new(
-            TagNumber::Application(ApplicationTagNumber::Time),
-            Time::LEN,
-        )
-        .encode(writer);
-        self.time.encode(writer);
+        self.date.encode_application(writer);
+        self.time.encode_application(writer);
         Tag::new(TagNumber::ContextSpecificClosing(Self::DATE_TIME_TAG), 0).encode(writer);
 
         // value
         Tag::new(TagNumber::ContextSpecificOpening(Self::VALUE_TAG), 0).encode(writer);
-        match self.value {
+        match &self.value {
             ReadRangeValue::Real(value) => {
                 Tag::new(
                     TagNumber::ContextSpecific(ReadRangeValueType::Real as u8),
@@ -353,81 +384,141 @@ impl<'a> ReadRangeItem<'a> {
                 .encode(writer);
                 writer.extend_from_slice(&value.to_be_bytes());
             }
-            _ => todo!("{:?}", self.value),
+            ReadRangeValue::Bool(value) => {
+                encode_context_bool(writer, ReadRangeValueType::Bool as u8, *value);
+            }
+            ReadRangeValue::Status(value) => {
+                value.encode_context(ReadRangeValueType::Status as u8, writer);
+            }
+            ReadRangeValue::Enum(value) => {
+                encode_context_unsigned(writer, ReadRangeValueType::Enum as u8, *value);
+            }
+            ReadRangeValue::Unsigned(value) => {
+                encode_context_unsigned(writer, ReadRangeValueType::Unsigned as u8, *value);
+            }
+            ReadRangeValue::Signed(value) => {
+                encode_context_signed(writer, ReadRangeValueType::Signed as u8, *value);
+            }
+            ReadRangeValue::Bits(value) => {
+                value.encode_context(ReadRangeValueType::Bits as u8, writer);
+            }
+            ReadRangeValue::Null => {
+                Tag::new(
+                    TagNumber::ContextSpecific(ReadRangeValueType::Null as u8),
+                    0,
+                )
+                .encode(writer);
+            }
+            ReadRangeValue::Error(error) => {
+                encode_opening_tag(writer, ReadRangeValueType::Error as u8);
+                encode_application_enumerated(writer, u32::from(&error.error_class));
+                encode_application_enumerated(writer, u32::from(&error.error_code));
+                encode_closing_tag(writer, ReadRangeValueType::Error as u8);
+            }
+            ReadRangeValue::Delta(value) => {
+                Tag::new(
+                    TagNumber::ContextSpecific(ReadRangeValueType::Delta as u8),
+                    4,
+                )
+                .encode(writer);
+                writer.extend_from_slice(&value.to_be_bytes());
+            }
         }
         Tag::new(TagNumber::ContextSpecificClosing(Self::VALUE_TAG), 0).encode(writer);
 
         // status
-        self.status_flags
-            .encode_context(Self::STATUS_FLAGS_TAG, writer);
+        if let Some(status_flags) = &self.status_flags {
+            status_flags.encode_context(Self::STATUS_FLAGS_TAG, writer);
+        }
     }
 
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
         // date and time
-        Tag::decode_expected(
-            reader,
-            buf,
-            TagNumber::ContextSpecificOpening(Self::DATE_TIME_TAG),
-            "ReadRangeItem decode",
-        )?;
-        Tag::decode_expected(
+        let DateTime { date, time } = with_context_tag(
             reader,
             buf,
-            TagNumber::Application(ApplicationTagNumber::Date),
-            "ReadRangeItem decode",
-        )?;
-        let date = Date::decode(reader, buf)?;
-        Tag::decode_expected(
-            reader,
-            buf,
-            TagNumber::Application(ApplicationTagNumber::Time),
-            "ReadRangeItem decode",
-        )?;
-        let time = Time::decode(reader, buf)?;
-        Tag::decode_expected(
-            reader,
-            buf,
-            TagNumber::ContextSpecificClosing(Self::DATE_TIME_TAG),
+            Self::DATE_TIME_TAG,
             "ReadRangeItem decode",
+            DateTime::decode,
         )?;
 
         // value
-        Tag::decode_expected(
-            reader,
-            buf,
-            TagNumber::ContextSpecificOpening(Self::VALUE_TAG),
-            "ReadRangeItem decode",
-        )?;
-        let tag = Tag::decode(reader, buf)?;
-        let value_type: ReadRangeValueType = match tag.number {
-            TagNumber::ContextSpecific(tag_number) => tag_number
-                .try_into()
-                .map_err(|x| Error::InvalidVariant(("ReadRangeValueType", x as u32)))?,
-            x => return Err(Error::TagNotSupported(("ReadRangeItems next value", x))),
-        };
-        let value = match value_type {
-            ReadRangeValueType::Real => {
-                let value = f32::from_be_bytes(reader.read_bytes(buf)?);
-                ReadRangeValue::Real(value)
-            }
-            x => return Err(Error::Unimplemented(Unimplemented::ReadRangeValueType(x))),
-        };
-        Tag::decode_expected(
+        let value = with_context_tag(
             reader,
             buf,
-            TagNumber::ContextSpecificClosing(Self::VALUE_TAG),
+            Self::VALUE_TAG,
             "ReadRangeItem decode",
+            |reader, buf| {
+                let tag = Tag::decode(reader, buf)?;
+                let value_type: ReadRangeValueType = match tag.number {
+                    TagNumber::ContextSpecific(tag_number)
+                    | TagNumber::ContextSpecificOpening(tag_number) => tag_number
+                        .try_into()
+                        .map_err(|x| Error::InvalidVariant(("ReadRangeValueType", x as u32)))?,
+                    x => return Err(Error::TagNotSupported(("ReadRangeItems next value", x))),
+                };
+                let value = match value_type {
+                    ReadRangeValueType::Real => {
+                        let value = f32::from_be_bytes(reader.read_bytes(buf)?);
+                        ReadRangeValue::Real(value)
+                    }
+                    ReadRangeValueType::Bool => {
+                        let value = reader.read_byte(buf)? > 0;
+                        ReadRangeValue::Bool(value)
+                    }
+                    ReadRangeValueType::Status => {
+                        let value = BitString::decode_uninterpreted(tag.value, reader, buf)?;
+                        ReadRangeValue::Status(value)
+                    }
+                    ReadRangeValueType::Enum => {
+                        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+                        ReadRangeValue::Enum(value)
+                    }
+                    ReadRangeValueType::Unsigned => {
+                        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+                        ReadRangeValue::Unsigned(value)
+                    }
+                    ReadRangeValueType::Signed => {
+                        let value = decode_signed(tag.value, reader, buf)?;
+                        ReadRangeValue::Signed(value)
+                    }
+                    ReadRangeValueType::Bits => {
+                        // the logged bits are not status flags so decode them as a custom bit string
+                        let value = BitString::decode_uninterpreted(tag.value, reader, buf)?;
+                        ReadRangeValue::Bits(value)
+                    }
+                    ReadRangeValueType::Null => ReadRangeValue::Null,
+                    ReadRangeValueType::Error => {
+                        let error = read_error(reader, buf)?;
+                        Tag::decode_expected(
+                            reader,
+                            buf,
+                            TagNumber::ContextSpecificClosing(ReadRangeValueType::Error as u8),
+                            "ReadRangeItem decode failure",
+                        )?;
+                        ReadRangeValue::Error(error)
+                    }
+                    ReadRangeValueType::Delta => {
+                        let value = f32::from_be_bytes(reader.read_bytes(buf)?);
+                        ReadRangeValue::Delta(value)
+                    }
+                    x => return Err(Error::Unimplemented(Unimplemented::ReadRangeValueType(x))),
+                };
+                Ok(value)
+            },
         )?;
 
-        // status flags
-        Tag::decode_expected(
-            reader,
-            buf,
-            TagNumber::ContextSpecific(Self::STATUS_FLAGS_TAG),
-            "ReadRangeItem decode",
-        )?;
-        let status_flags = BitString::decode(&PropertyId::PropStatusFlags, tag.value, reader, buf)?;
+        // status flags (optional)
+        let status_flags = match decode_optional_context_tag(reader, buf, Self::STATUS_FLAGS_TAG)? {
+            Some(tag) => Some(BitString::decode(
+                &PropertyId::PropStatusFlags,
+                tag.value,
+                reader,
+                buf,
+            )?),
+            None => None,
+        };
 
         Ok(ReadRangeItem {
             date,
@@ -473,15 +564,22 @@ impl ReadRange {
             "ReadRange decode property_id",
         )?;
 
-        let mut tag = Tag::decode(reader, buf)?;
-        let array_index = if tag.number == TagNumber::ContextSpecific(Self::ARRAY_INDEX_TAG) {
-            let value = decode_unsigned(tag.value, reader, buf)? as u32;
-            tag = Tag::decode(reader, buf)?;
-            value
-        } else {
-            BACNET_ARRAY_ALL
+        let array_index = match decode_optional_context_tag(reader, buf, Self::ARRAY_INDEX_TAG)? {
+            Some(tag) => decode_unsigned(tag.value, reader, buf)? as u32,
+            None => BACNET_ARRAY_ALL,
         };
 
+        // no range means that all items are requested
+        if reader.eof() {
+            return Ok(Self {
+                array_index,
+                object_id,
+                property_id,
+                request_type: ReadRangeRequestType::All,
+            });
+        }
+
+        let tag = Tag::decode(reader, buf)?;
         let request_type = match tag.number {
             TagNumber::ContextSpecificOpening(Self::BY_POSITION_TAG) => {
                 // index
@@ -494,26 +592,7 @@ impl ReadRange {
                 let index = decode_unsigned(index_tag.value, reader, buf)? as u32;
 
                 // count
-                let count_tag = Tag::decode(reader, buf)?;
-                let count = match count_tag.number {
-                    TagNumber::Application(ApplicationTagNumber::UnsignedInt) => {
-                        decode_unsigned(count_tag.value, reader, buf)? as u32
-                    }
-                    TagNumber::Application(ApplicationTagNumber::SignedInt) => {
-                        let count = decode_signed(count_tag.value, reader, buf)?;
-                        if count < 0 {
-                            return Err(Error::InvalidValue("ReadRange count cannot be negative"));
-                        }
-
-                        count as u32
-                    }
-                    _ => {
-                        return Err(Error::TagNotSupported((
-                            "ReadRange count tag",
-                            count_tag.number,
-                        )))
-                    }
-                };
+                let count = Self::decode_count(reader, buf)?;
 
                 // closing tag
                 Tag::decode_expected(
@@ -523,11 +602,41 @@ impl ReadRange {
                     "ReadRange decode closing position",
                 )?;
 
-                ReadRangeRequestType::ByPosition(ReadRangeByPosition {
-                    count: count as u32,
-                    index,
+                ReadRangeRequestType::ByPosition(ReadRangeByPosition { count, index })
+            }
+            TagNumber::ContextSpecificOpening(Self::BY_SEQUENCE_TAG) => {
+                let tag = Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+                    "ReadRange decode sequence_num",
+                )?;
+                let sequence_num = decode_unsigned(tag.value, reader, buf)? as u32;
+                let count = Self::decode_count(reader, buf)?;
+                Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecificClosing(Self::BY_SEQUENCE_TAG),
+                    "ReadRange decode closing sequence",
+                )?;
+
+                ReadRangeRequestType::BySequence(ReadRangeBySequence {
+                    sequence_num,
+                    count,
                 })
             }
+            TagNumber::ContextSpecificOpening(Self::BY_TIME_TAG) => {
+                let DateTime { date, time } = DateTime::decode(reader, buf)?;
+                let count = Self::decode_count(reader, buf)?;
+                Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecificClosing(Self::BY_TIME_TAG),
+                    "ReadRange decode closing time",
+                )?;
+
+                ReadRangeRequestType::ByTime(ReadRangeByTime { date, time, count })
+            }
             number => return Err(Error::TagNotSupported(("ReadRange opening tag", number))),
         };
 
@@ -539,6 +648,32 @@ impl ReadRange {
         })
     }
 
+    // the count is signed on the wire, a negative count reads backwards from the reference item
+    fn decode_count(reader: &mut Reader, buf: &[u8]) -> Result<i32, Error> {
+        let count_tag = Tag::decode(reader, buf)?;
+        let count = match count_tag.number {
+            TagNumber::Application(ApplicationTagNumber::SignedInt) => {
+                decode_signed(count_tag.value, reader, buf)?
+            }
+            // some devices send the count unsigned
+            TagNumber::Application(ApplicationTagNumber::UnsignedInt) => {
+                let count = decode_unsigned(count_tag.value, reader, buf)?;
+                i32::try_from(count)
+                    .map_err(|_| Error::InvalidValue("ReadRange count out of range"))?
+            }
+            _ => {
+                return Err(Error::TagNotSupported((
+                    "ReadRange count tag",
+                    count_tag.number,
+                )))
+            }
+        };
+        if count == 0 {
+            return Err(Error::InvalidValue("ReadRange count cannot be zero"));
+        }
+        Ok(count)
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
         // object_id
         encode_context_object_id(writer, Self::OBJECT_ID_TAG, &self.object_id);
@@ -555,20 +690,20 @@ impl ReadRange {
             ReadRangeRequestType::ByPosition(x) => {
                 encode_opening_tag(writer, Self::BY_POSITION_TAG);
                 encode_application_unsigned(writer, x.index as u64);
-                encode_application_signed(writer, x.count as i32);
+                encode_application_signed(writer, x.count);
                 encode_closing_tag(writer, Self::BY_POSITION_TAG);
             }
             ReadRangeRequestType::BySequence(x) => {
                 encode_opening_tag(writer, Self::BY_SEQUENCE_TAG);
                 encode_application_unsigned(writer, x.sequence_num as u64);
-                encode_application_signed(writer, x.count as i32);
+                encode_application_signed(writer, x.count);
                 encode_closing_tag(writer, Self::BY_SEQUENCE_TAG);
             }
             ReadRangeRequestType::ByTime(x) => {
                 encode_opening_tag(writer, Self::BY_TIME_TAG);
-                x.date.encode(writer);
-                x.time.encode(writer);
-                encode_application_signed(writer, x.count as i32);
+                x.date.encode_application(writer);
+                x.time.encode_application(writer);
+                encode_application_signed(writer, x.count);
                 encode_closing_tag(writer, Self::BY_TIME_TAG);
             }
             ReadRangeRequestType::All => {
@@ -577,3 +712,532 @@ impl ReadRange {
         }
     }
 }
+
+// owned copies of the ack types above so that trend data can outlive the receive buffer
+// and be serialized (e.g. to json) by a gateway
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedReadRangeAck {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: u32,
+    pub result_flags: Vec<bool>,
+    pub item_count: usize,
+    pub items: Vec<OwnedReadRangeItem>,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedReadRangeItem {
+    pub date: Date,
+    pub time: Time,
+    pub value: OwnedReadRangeValue,
+    pub status_flags: Option<Vec<bool>>,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OwnedReadRangeValue {
+    Status(Vec<bool>),
+    Bool(bool),
+    Real(f32),
+    Enum(u32),
+    Unsigned(u32),
+    Signed(i32),
+    Bits(Vec<bool>),
+    Null,
+    Error(PropertyAccessError),
+    Delta(f32),
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&ReadRangeAck<'a>> for OwnedReadRangeAck {
+    fn from(value: &ReadRangeAck<'a>) -> Self {
+        Self {
+            object_id: value.object_id,
+            property_id: value.property_id,
+            array_index: value.array_index,
+            result_flags: bits_to_vec(&value.result_flags),
+            item_count: value.item_count,
+            items: value.item_data.items.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&ReadRangeItem<'a>> for OwnedReadRangeItem {
+    fn from(value: &ReadRangeItem<'a>) -> Self {
+        Self {
+            date: value.date.clone(),
+            time: value.time.clone(),
+            value: (&value.value).into(),
+            status_flags: value.status_flags.as_ref().map(bits_to_vec),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&ReadRangeValue<'a>> for OwnedReadRangeValue {
+    fn from(value: &ReadRangeValue<'a>) -> Self {
+        match value {
+            ReadRangeValue::Status(x) => Self::Status(bits_to_vec(x)),
+            ReadRangeValue::Bool(x) => Self::Bool(*x),
+            ReadRangeValue::Real(x) => Self::Real(*x),
+            ReadRangeValue::Enum(x) => Self::Enum(*x),
+            ReadRangeValue::Unsigned(x) => Self::Unsigned(*x),
+            ReadRangeValue::Signed(x) => Self::Signed(*x),
+            ReadRangeValue::Bits(x) => Self::Bits(bits_to_vec(x)),
+            ReadRangeValue::Null => Self::Null,
+            ReadRangeValue::Error(x) => Self::Error(x.clone()),
+            ReadRangeValue::Delta(x) => Self::Delta(*x),
+        }
+    }
+}
+
+// bit 0 first
+#[cfg(feature = "alloc")]
+fn bits_to_vec(bits: &BitString) -> Vec<bool> {
+    (0..bits.len()).map(|i| bits.get(i)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+        property_id::PropertyId,
+        spec::BACNET_ARRAY_ALL,
+    };
+
+    use crate::{
+        application_protocol::primitives::data_value::BitStringBuilder,
+        application_protocol::primitives::data_value::{BitString, Date, Time},
+        common::spec::Status,
+    };
+
+    use super::PropertyAccessError;
+    use super::{
+        ReadRange, ReadRangeByPosition, ReadRangeByTime, ReadRangeItem, ReadRangeItems,
+        ReadRangeRequestType, ReadRangeValue,
+    };
+    use crate::common::{
+        error::Error,
+        spec::{ErrorClass, ErrorCode},
+    };
+
+    #[test]
+    fn decode_all() {
+        let object_id = ObjectId::new(ObjectType::ObjectTrendlog, 1);
+        let request = ReadRange::new(
+            object_id,
+            PropertyId::PropLogBuffer,
+            ReadRangeRequestType::All,
+        );
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        let decoded = ReadRange::decode(&mut reader, &buf).unwrap();
+        assert_eq!(decoded.object_id, object_id);
+        assert_eq!(decoded.property_id, PropertyId::PropLogBuffer);
+        assert_eq!(decoded.array_index, BACNET_ARRAY_ALL);
+        assert!(matches!(decoded.request_type, ReadRangeRequestType::All));
+    }
+
+    #[test]
+    fn by_time_with_wildcard_time() {
+        // everything logged from the start of 2024-02-29 onwards
+        let by_time = ReadRangeByTime {
+            date: Date {
+                year: 2024,
+                month: 2,
+                day: 29,
+                wday: 4,
+            },
+            time: Time::any(),
+            count: 100,
+        };
+        let request = ReadRange::new(
+            ObjectId::new(ObjectType::ObjectTrendlog, 1),
+            PropertyId::PropLogBuffer,
+            ReadRangeRequestType::ByTime(by_time.clone()),
+        );
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        let buf = writer.to_bytes();
+        #[rustfmt::skip]
+        let expected = [
+            0x0c, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83,
+            0x7e, 0xa4, 0x7c, 0x02, 0x1d, 0x04, 0xb4, 0xff, 0xff, 0xff, 0xff, 0x31, 0x64, 0x7f,
+        ];
+        assert_eq!(buf, expected);
+
+        let mut reader = Reader::new_with_len(buf.len());
+        let decoded = ReadRange::decode(&mut reader, buf).unwrap();
+        assert_eq!(decoded.request_type, ReadRangeRequestType::ByTime(by_time));
+    }
+
+    #[test]
+    fn decode_counts() {
+        // TrendLog:1 Log_Buffer by position from index 100, count -20 (backwards)
+        let buf = [
+            0x0c, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83, 0x3e, 0x21, 0x64, 0x31, 0xec, 0x3f,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let decoded = ReadRange::decode(&mut reader, &buf).unwrap();
+        assert_eq!(
+            decoded.request_type,
+            ReadRangeRequestType::ByPosition(ReadRangeByPosition {
+                index: 100,
+                count: -20
+            })
+        );
+
+        // a count sent as an unsigned is always forwards
+        let buf = [
+            0x0c, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83, 0x3e, 0x21, 0x64, 0x21, 0x14, 0x3f,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let decoded = ReadRange::decode(&mut reader, &buf).unwrap();
+        assert_eq!(
+            decoded.request_type,
+            ReadRangeRequestType::ByPosition(ReadRangeByPosition {
+                index: 100,
+                count: 20
+            })
+        );
+
+        // a count of zero is not allowed
+        let buf = [
+            0x0c, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83, 0x3e, 0x21, 0x64, 0x31, 0x00, 0x3f,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let result = ReadRange::decode(&mut reader, &buf);
+        assert!(matches!(result, Err(Error::InvalidValue(_))));
+    }
+
+    #[test]
+    fn decode_items_without_status_flags() {
+        let date = Date {
+            year: 2024,
+            month: 1,
+            day: 2,
+            wday: 2,
+        };
+        let time = Time {
+            hour: 3,
+            minute: 4,
+            second: 5,
+            hundredths: 0,
+        };
+        let without_status = ReadRangeItem {
+            date: date.clone(),
+            time: time.clone(),
+            value: ReadRangeValue::Bool(true),
+            status_flags: None,
+        };
+        let with_status = ReadRangeItem {
+            date,
+            time,
+            value: ReadRangeValue::Real(1.5),
+            status_flags: Some(BitString::Status(Status::new(0b1000_0000))),
+        };
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        without_status.encode(&mut writer);
+        with_status.encode(&mut writer);
+        let buf = writer.to_bytes();
+
+        let items = ReadRangeItems::decode(buf).unwrap();
+        #[cfg(feature = "alloc")]
+        let mut it = items.items.clone().into_iter();
+        #[cfg(not(feature = "alloc"))]
+        let mut it = items.into_iter().map(|x| x.unwrap());
+
+        let item = it.next().unwrap();
+        assert!(matches!(item.value, ReadRangeValue::Bool(true)));
+        assert!(item.status_flags.is_none());
+
+        let item = it.next().unwrap();
+        assert!(matches!(item.value, ReadRangeValue::Real(x) if x == 1.5));
+        match item.status_flags {
+            Some(BitString::Status(status)) => assert!(status.in_alarm()),
+            x => panic!("expected status flags, got {:?}", x),
+        }
+
+        assert!(it.next().is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_decoded_items() {
+        let item = ReadRangeItem {
+            date: Date {
+                year: 2024,
+                month: 1,
+                day: 2,
+                wday: 2,
+            },
+            time: Time {
+                hour: 3,
+                minute: 4,
+                second: 5,
+                hundredths: 0,
+            },
+            value: ReadRangeValue::Real(1.5),
+            status_flags: None,
+        };
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        item.encode(&mut writer);
+        item.encode(&mut writer);
+        let buf = writer.to_bytes();
+
+        // the json is the same with or without alloc
+        let items = ReadRangeItems::decode(buf).unwrap();
+        let json = serde_json::to_value(&items).unwrap();
+        let list = json["items"].as_array().unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[1]["value"], serde_json::json!({ "Real": 1.5 }));
+        assert_eq!(list[1]["time"]["minute"], 4);
+
+        // a truncated buffer fails to serialize rather than dropping items
+        #[cfg(not(feature = "alloc"))]
+        {
+            let items = ReadRangeItems::new_from_buf(&buf[..buf.len() - 1]);
+            assert!(serde_json::to_value(&items).is_err());
+        }
+    }
+
+    #[test]
+    fn encode_decode_mixed_value_types() {
+        let item = |value| ReadRangeItem {
+            date: Date {
+                year: 2024,
+                month: 1,
+                day: 2,
+                wday: 2,
+            },
+            time: Time {
+                hour: 3,
+                minute: 4,
+                second: 5,
+                hundredths: 0,
+            },
+            value,
+            status_flags: None,
+        };
+        // log-interrupted
+        let log_status = BitStringBuilder::from_bits(&[false, false, true]).unwrap();
+
+        let mut buf = [0; 256];
+        let mut writer = Writer::new(&mut buf);
+        item(ReadRangeValue::Enum(3)).encode(&mut writer);
+        item(ReadRangeValue::Unsigned(70000)).encode(&mut writer);
+        item(ReadRangeValue::Signed(-300)).encode(&mut writer);
+        item(ReadRangeValue::Null).encode(&mut writer);
+        item(ReadRangeValue::Status(log_status.build())).encode(&mut writer);
+        let buf = writer.to_bytes();
+
+        let items = ReadRangeItems::decode(buf).unwrap();
+        #[cfg(feature = "alloc")]
+        let mut it = items.items.clone().into_iter();
+        #[cfg(not(feature = "alloc"))]
+        let mut it = items.into_iter().map(|x| x.unwrap());
+
+        assert!(matches!(it.next().unwrap().value, ReadRangeValue::Enum(3)));
+        assert!(matches!(
+            it.next().unwrap().value,
+            ReadRangeValue::Unsigned(70000)
+        ));
+        assert!(matches!(
+            it.next().unwrap().value,
+            ReadRangeValue::Signed(-300)
+        ));
+        assert!(matches!(it.next().unwrap().value, ReadRangeValue::Null));
+        match it.next().unwrap().value {
+            ReadRangeValue::Status(BitString::Custom(x)) => {
+                assert!(x.iter_set_bits().eq([2]));
+            }
+            x => panic!("expected log status, got {:?}", x),
+        }
+        assert!(it.next().is_none());
+    }
+
+    #[test]
+    fn encode_decode_bits_item() {
+        let bits = BitStringBuilder::from_bits(&[true, false, true]).unwrap();
+        let item = ReadRangeItem {
+            date: Date {
+                year: 2024,
+                month: 1,
+                day: 2,
+                wday: 2,
+            },
+            time: Time {
+                hour: 3,
+                minute: 4,
+                second: 5,
+                hundredths: 0,
+            },
+            value: ReadRangeValue::Bits(bits.build()),
+            status_flags: Some(BitString::Status(Status::new(0))),
+        };
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        item.encode(&mut writer);
+        let buf = writer.to_bytes();
+
+        let mut reader = Reader::new_with_len(buf.len());
+        let decoded = ReadRangeItem::decode(&mut reader, buf).unwrap();
+        assert!(reader.eof());
+        let ReadRangeValue::Bits(BitString::Custom(bits)) = decoded.value else {
+            panic!("expected a custom bit string, got {:?}", decoded.value);
+        };
+        assert_eq!(bits.bit_len(), 3);
+        assert!(bits.is_set(0));
+        assert!(!bits.is_set(1));
+        assert!(bits.is_set(2));
+        assert!(matches!(decoded.status_flags, Some(BitString::Status(_))));
+    }
+
+    #[test]
+    fn encode_decode_failure_and_time_change() {
+        let item = |value| ReadRangeItem {
+            date: Date {
+                year: 2024,
+                month: 1,
+                day: 2,
+                wday: 2,
+            },
+            time: Time {
+                hour: 3,
+                minute: 4,
+                second: 5,
+                hundredths: 0,
+            },
+            value,
+            status_flags: None,
+        };
+        let failure = PropertyAccessError {
+            error_class: ErrorClass::Device,
+            error_code: ErrorCode::CommunicationDisabled,
+        };
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        item(ReadRangeValue::Error(failure)).encode(&mut writer);
+        item(ReadRangeValue::Delta(-3600.0)).encode(&mut writer);
+        let buf = writer.to_bytes();
+
+        let items = ReadRangeItems::decode(buf).unwrap();
+        #[cfg(feature = "alloc")]
+        let mut it = items.items.clone().into_iter();
+        #[cfg(not(feature = "alloc"))]
+        let mut it = items.into_iter().map(|x| x.unwrap());
+
+        match it.next().unwrap().value {
+            ReadRangeValue::Error(error) => {
+                assert_eq!(error.error_class, ErrorClass::Device);
+                assert_eq!(error.error_code, ErrorCode::CommunicationDisabled);
+            }
+            x => panic!("expected a failure, got {:?}", x),
+        }
+        assert!(matches!(
+            it.next().unwrap().value,
+            ReadRangeValue::Delta(x) if x == -3600.0
+        ));
+        assert!(it.next().is_none());
+
+        // any-value log records are not supported
+        let buf = [
+            0x0e, 0xa4, 0x7c, 0x01, 0x02, 0x02, 0xb4, 0x03, 0x04, 0x05, 0x00, 0x0f, 0x1e, 0xae,
+            0x21, 0x01, 0xaf, 0x1f,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let result = ReadRangeItem::decode(&mut reader, &buf);
+        assert!(matches!(result, Err(Error::Unimplemented(_))));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn convert_to_owned() {
+        use super::{OwnedReadRangeAck, OwnedReadRangeValue, ReadRangeAck};
+        use crate::common::spec::LogBufferResult;
+        use alloc::vec;
+
+        let item = ReadRangeItem {
+            date: Date {
+                year: 2024,
+                month: 1,
+                day: 2,
+                wday: 2,
+            },
+            time: Time {
+                hour: 3,
+                minute: 4,
+                second: 5,
+                hundredths: 0,
+            },
+            value: ReadRangeValue::Real(20.5),
+            status_flags: Some(BitString::Status(Status::new(0b0100_0000))),
+        };
+        let ack = ReadRangeAck {
+            object_id: ObjectId::new(ObjectType::ObjectTrendlog, 1),
+            property_id: PropertyId::PropLogBuffer,
+            array_index: BACNET_ARRAY_ALL,
+            result_flags: BitString::LogBufferResult(LogBufferResult::new(0b1100_0000)),
+            item_count: 1,
+            item_data: ReadRangeItems::new(vec![item]),
+        };
+
+        // decode from the wire so that the owned copy cannot borrow the buffer
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        ack.encode(&mut writer);
+        let buf = &writer.to_bytes()[1..]; // skip service choice
+        let mut reader = Reader::new_with_len(buf.len());
+        let decoded = ReadRangeAck::decode(&mut reader, buf).unwrap();
+
+        let owned = OwnedReadRangeAck::from(&decoded);
+        assert_eq!(owned.object_id, ack.object_id);
+        assert_eq!(owned.result_flags, vec![true, true, false]);
+        assert_eq!(owned.items.len(), 1);
+        assert!(matches!(owned.items[0].value, OwnedReadRangeValue::Real(x) if x == 20.5));
+        assert_eq!(
+            owned.items[0].status_flags,
+            Some(vec![false, true, false, false])
+        );
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_value(&owned).unwrap();
+            assert_eq!(json["result_flags"], serde_json::json!([true, true, false]));
+            assert_eq!(json["item_count"], 1);
+            assert_eq!(
+                json["items"][0]["value"],
+                serde_json::json!({ "Real": 20.5 })
+            );
+            assert_eq!(
+                json["items"][0]["status_flags"],
+                serde_json::json!([false, true, false, false])
+            );
+
+            let round_trip: OwnedReadRangeAck = serde_json::from_value(json).unwrap();
+            assert_eq!(round_trip.object_id, ack.object_id);
+            assert!(matches!(round_trip.items[0].value, OwnedReadRangeValue::Real(x) if x == 20.5));
+        }
+    }
+}
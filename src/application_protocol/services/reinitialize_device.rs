@@ -0,0 +1,74 @@
+use crate::{
+    application_protocol::primitives::data_value::CharacterString,
+    common::{helper::encode_context_unsigned, io::Writer},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u32)]
+pub enum ReinitializedState {
+    ColdStart = 0,
+    WarmStart = 1,
+    StartBackup = 2,
+    EndBackup = 3,
+    StartRestore = 4,
+    EndRestore = 5,
+    AbortRestore = 6,
+    ActivateChanges = 7,
+}
+
+// restarts a device or controls a backup / restore procedure (the reply is a SimpleAck)
+// a wrong password is reported by the device as an Error PDU (security / password-failure)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReinitializeDevice<'a> {
+    pub state: ReinitializedState,
+    pub password: Option<CharacterString<'a>>,
+}
+
+impl<'a> ReinitializeDevice<'a> {
+    const TAG_STATE: u8 = 0;
+    const TAG_PASSWORD: u8 = 1;
+
+    pub fn new(state: ReinitializedState, password: Option<CharacterString<'a>>) -> Self {
+        Self { state, password }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        // enumerated values are encoded like unsigned values
+        encode_context_unsigned(writer, Self::TAG_STATE, self.state as u32);
+        if let Some(password) = &self.password {
+            password.encode_context(Self::TAG_PASSWORD, writer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::primitives::data_value::CharacterString, common::io::Writer,
+    };
+
+    use super::{ReinitializeDevice, ReinitializedState};
+
+    #[test]
+    fn encode_reinitialize_device() {
+        let request = ReinitializeDevice::new(ReinitializedState::WarmStart, None);
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), [0x09, 0x01]);
+
+        let password = CharacterString::new("secret");
+        let request = ReinitializeDevice::new(ReinitializedState::ColdStart, Some(password));
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        #[rustfmt::skip]
+        let expected = [
+            0x09, 0x00,
+            0x1d, 0x07, 0x00, b's', b'e', b'c', b'r', b'e', b't',
+        ];
+        assert_eq!(writer.to_bytes(), &expected);
+    }
+}
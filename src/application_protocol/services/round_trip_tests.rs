@@ -0,0 +1,266 @@
+// every encodable service is encoded then decoded again and must come back unchanged
+// add a case here when a service gains both an encode and a decode
+// NOTE: the encoders push the service choice byte but the decoders expect it to have been read already
+
+use core::fmt::Debug;
+
+use crate::{
+    application_protocol::{
+        primitives::data_value::{ApplicationDataValueWrite, CharacterString, Date, Time},
+        services::{
+            atomic_read_file::{
+                AtomicReadFile, AtomicReadFileAck, AtomicReadFileData, FileAccessMethod,
+                RecordData, StreamData,
+            },
+            read_property::ReadProperty,
+            read_range::{
+                ReadRange, ReadRangeByPosition, ReadRangeBySequence, ReadRangeByTime,
+                ReadRangeRequestType,
+            },
+            who_has::{WhoHas, WhoHasObject},
+            who_is::WhoIs,
+            write_property::WriteProperty,
+        },
+    },
+    common::{
+        error::Error,
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+        property_id::PropertyId,
+        spec::BACNET_MAX_INSTANCE,
+    },
+};
+
+fn assert_round_trip<T, E, D>(value: &T, encode: E, decode: D, skip: usize)
+where
+    T: PartialEq + Debug,
+    E: Fn(&T, &mut Writer),
+    D: Fn(&mut Reader, &[u8]) -> Result<T, Error>,
+{
+    let mut buf = [0; 256];
+    let mut writer = Writer::new(&mut buf);
+    encode(value, &mut writer);
+    let buf = writer.to_bytes();
+
+    let mut reader = Reader::new_with_len(buf.len());
+    reader.index = skip;
+    let decoded = decode(&mut reader, buf).unwrap();
+    assert_eq!(&decoded, value, "encoded as {:02x?}", buf);
+    assert!(reader.eof(), "trailing bytes in {:02x?}", buf);
+}
+
+// for values that cannot be compared directly (e.g. they borrow the decode buffer)
+// the decoded value is encoded again and must produce the same bytes
+fn assert_same_encoding(expected: &[u8], encode: impl FnOnce(&mut Writer)) {
+    let mut buf = [0; 256];
+    let mut writer = Writer::new(&mut buf);
+    encode(&mut writer);
+    assert_eq!(writer.to_bytes(), expected);
+}
+
+fn read_property_round_trip(value: ReadProperty) {
+    assert_round_trip(&value, ReadProperty::encode, ReadProperty::decode, 0);
+}
+
+fn read_range_round_trip(value: ReadRange) {
+    assert_round_trip(&value, ReadRange::encode, ReadRange::decode, 0);
+}
+
+fn who_is_round_trip(value: WhoIs) {
+    // skip the service choice
+    assert_round_trip(&value, WhoIs::encode, WhoIs::decode, 1);
+}
+
+fn who_has_round_trip(value: WhoHas) {
+    let mut buf = [0; 256];
+    let mut writer = Writer::new(&mut buf);
+    value.encode(&mut writer);
+    let buf = writer.to_bytes();
+
+    let mut reader = Reader::new_with_len(buf.len());
+    reader.index = 1; // skip the service choice
+    let decoded = WhoHas::decode(&mut reader, buf).unwrap();
+    assert!(reader.eof(), "trailing bytes in {:02x?}", buf);
+    assert_eq!(decoded.device_instance_low, value.device_instance_low);
+    assert_eq!(decoded.device_instance_high, value.device_instance_high);
+    assert_same_encoding(buf, |writer| decoded.encode(writer));
+}
+
+fn atomic_read_file_round_trip(value: AtomicReadFile) {
+    assert_round_trip(&value, AtomicReadFile::encode, AtomicReadFile::decode, 0);
+}
+
+fn atomic_read_file_ack_round_trip(value: AtomicReadFileAck) {
+    let mut buf = [0; 256];
+    let mut writer = Writer::new(&mut buf);
+    value.encode(&mut writer);
+    let buf = writer.to_bytes();
+
+    let mut reader = Reader::new_with_len(buf.len());
+    reader.index = 1; // skip the service choice
+    let decoded = AtomicReadFileAck::decode(&mut reader, buf).unwrap();
+    assert!(reader.eof(), "trailing bytes in {:02x?}", buf);
+    assert_eq!(decoded.end_of_file, value.end_of_file);
+    assert_same_encoding(buf, |writer| decoded.encode(writer));
+}
+
+fn write_property_round_trip(value: WriteProperty) {
+    let mut buf = [0; 256];
+    let mut writer = Writer::new(&mut buf);
+    value.encode(&mut writer);
+    let buf = writer.to_bytes();
+
+    let mut reader = Reader::new_with_len(buf.len());
+    let decoded = WriteProperty::decode(&mut reader, buf).unwrap();
+    assert!(reader.eof(), "trailing bytes in {:02x?}", buf);
+    assert_eq!(decoded.object_id, value.object_id);
+    assert_eq!(decoded.property_id, value.property_id);
+    assert_eq!(decoded.array_index, value.array_index);
+    // the lowest priority is the default so it decodes as no priority
+    assert_eq!(decoded.priority, value.priority.filter(|x| *x != 16));
+    assert_same_encoding(buf, |writer| decoded.encode(writer));
+}
+
+#[test]
+fn read_property() {
+    let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+    read_property_round_trip(ReadProperty::new(object_id, PropertyId::PropPresentValue));
+
+    // large instance numbers and array indices need the longest encodings
+    let object_id = ObjectId::new(ObjectType::ObjectDevice, BACNET_MAX_INSTANCE);
+    let mut request = ReadProperty::new(object_id, PropertyId::PropPriorityArray);
+    request.array_index = 16;
+    read_property_round_trip(request.clone());
+    request.array_index = 0x12345;
+    read_property_round_trip(request);
+}
+
+#[test]
+fn read_range() {
+    let object_id = ObjectId::new(ObjectType::ObjectTrendlog, 7);
+    let property_id = PropertyId::PropLogBuffer;
+
+    read_range_round_trip(ReadRange::new(
+        object_id,
+        property_id,
+        ReadRangeRequestType::All,
+    ));
+    read_range_round_trip(ReadRange::new(
+        object_id,
+        property_id,
+        ReadRangeRequestType::ByPosition(ReadRangeByPosition {
+            index: 1,
+            count: 300,
+        }),
+    ));
+    read_range_round_trip(ReadRange::new(
+        object_id,
+        property_id,
+        ReadRangeRequestType::BySequence(ReadRangeBySequence {
+            sequence_num: 70000,
+            count: -10,
+        }),
+    ));
+    read_range_round_trip(ReadRange::new(
+        object_id,
+        property_id,
+        ReadRangeRequestType::ByTime(ReadRangeByTime {
+            date: Date {
+                year: 2024,
+                month: 3,
+                day: 15,
+                wday: 5,
+            },
+            time: Time {
+                hour: 13,
+                minute: 30,
+                second: 0,
+                hundredths: 0,
+            },
+            count: -5,
+        }),
+    ));
+}
+
+#[test]
+fn who_is() {
+    who_is_round_trip(WhoIs::new());
+    who_is_round_trip(WhoIs::for_instance(1234).unwrap());
+    who_is_round_trip(WhoIs::for_range(0, BACNET_MAX_INSTANCE).unwrap());
+}
+
+#[test]
+fn who_has() {
+    let object_id = ObjectId::new(ObjectType::ObjectAnalogValue, 12);
+    who_has_round_trip(WhoHas::new(WhoHasObject::ObjectId(object_id)));
+    who_has_round_trip(WhoHas::for_range(
+        0,
+        BACNET_MAX_INSTANCE,
+        WhoHasObject::ObjectName(CharacterString::new("Zone Temp")),
+    ));
+}
+
+#[test]
+fn atomic_read_file() {
+    let object_id = ObjectId::new(ObjectType::ObjectFile, 2);
+    atomic_read_file_round_trip(AtomicReadFile::new(
+        object_id,
+        FileAccessMethod::Stream {
+            file_start_position: 0,
+            requested_octet_count: 1476,
+        },
+    ));
+    atomic_read_file_round_trip(AtomicReadFile::new(
+        object_id,
+        FileAccessMethod::Record {
+            file_start_record: -1,
+            requested_record_count: 70000,
+        },
+    ));
+}
+
+#[test]
+fn atomic_read_file_ack() {
+    atomic_read_file_ack_round_trip(AtomicReadFileAck {
+        end_of_file: false,
+        data: AtomicReadFileData::Stream(StreamData::new(128, b"hello world")),
+    });
+    atomic_read_file_ack_round_trip(AtomicReadFileAck {
+        end_of_file: true,
+        data: AtomicReadFileData::Stream(StreamData::new(0, &[])),
+    });
+
+    // two records "ab" and "c", each an application tagged octet string
+    let records = [0x62, b'a', b'b', 0x61, b'c'];
+    atomic_read_file_ack_round_trip(AtomicReadFileAck {
+        end_of_file: true,
+        data: AtomicReadFileData::Record(RecordData::new_from_buf(5, 2, &records)),
+    });
+}
+
+#[test]
+fn write_property() {
+    let object_id = ObjectId::new(ObjectType::ObjectAnalogValue, 3);
+    write_property_round_trip(WriteProperty::new(
+        object_id,
+        PropertyId::PropPresentValue,
+        Some(8),
+        None,
+        ApplicationDataValueWrite::Real(21.5),
+    ));
+    write_property_round_trip(WriteProperty::new(
+        object_id,
+        PropertyId::PropObjectName,
+        None,
+        None,
+        ApplicationDataValueWrite::CharacterString(CharacterString::new("Setpoint")),
+    ));
+    write_property_round_trip(WriteProperty::new(
+        object_id,
+        PropertyId::PropPriorityArray,
+        Some(1),
+        Some(16),
+        ApplicationDataValueWrite::Null,
+    ));
+    write_property_round_trip(WriteProperty::relinquish(object_id, 16).unwrap());
+}
@@ -3,12 +3,10 @@ use crate::{
         primitives::data_value::{Date, Time},
         unconfirmed::UnconfirmedServiceChoice,
     },
-    common::{
-        io::Writer,
-        tag::{ApplicationTagNumber, Tag, TagNumber},
-    },
+    common::io::Writer,
 };
 
+// the local date and time of the sender
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct TimeSynchronization {
@@ -19,15 +17,86 @@ pub struct TimeSynchronization {
 impl TimeSynchronization {
     pub fn encode(&self, writer: &mut Writer) {
         writer.push(UnconfirmedServiceChoice::TimeSynchronization as u8);
+        encode_date_time(writer, &self.date, &self.time);
+    }
+}
+
+// the date and time are sent in UTC and the device applies its own UTC offset (and daylight saving)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct UtcTimeSynchronization {
+    pub date: Date,
+    pub time: Time,
+}
 
-        // date
-        let tag = Tag::new(TagNumber::Application(ApplicationTagNumber::Date), 4);
-        tag.encode(writer);
-        self.date.encode(writer);
+impl UtcTimeSynchronization {
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(UnconfirmedServiceChoice::UtcTimeSynchronization as u8);
+        encode_date_time(writer, &self.date, &self.time);
+    }
+}
+
+fn encode_date_time(writer: &mut Writer, date: &Date, time: &Time) {
+    date.encode_application(writer);
+    time.encode_application(writer);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::primitives::data_value::{Date, Time},
+        common::io::Writer,
+    };
+
+    use super::{TimeSynchronization, UtcTimeSynchronization};
+
+    // Friday 2024-03-15 13:45:30.00
+    const DATE: Date = Date {
+        year: 2024,
+        month: 3,
+        day: 15,
+        wday: 5,
+    };
+    const TIME: Time = Time {
+        hour: 13,
+        minute: 45,
+        second: 30,
+        hundredths: 0,
+    };
+
+    #[test]
+    fn encode_time_synchronization() {
+        let request = TimeSynchronization {
+            date: DATE,
+            time: TIME,
+        };
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        #[rustfmt::skip]
+        let expected = [
+            0x06,
+            0xa4, 0x7c, 0x03, 0x0f, 0x05,
+            0xb4, 0x0d, 0x2d, 0x1e, 0x00,
+        ];
+        assert_eq!(writer.to_bytes(), &expected);
+    }
 
-        // time
-        let tag = Tag::new(TagNumber::Application(ApplicationTagNumber::Time), 4);
-        tag.encode(writer);
-        self.time.encode(writer);
+    #[test]
+    fn encode_utc_time_synchronization() {
+        let request = UtcTimeSynchronization {
+            date: DATE,
+            time: TIME,
+        };
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        #[rustfmt::skip]
+        let expected = [
+            0x09,
+            0xa4, 0x7c, 0x03, 0x0f, 0x05,
+            0xb4, 0x0d, 0x2d, 0x1e, 0x00,
+        ];
+        assert_eq!(writer.to_bytes(), &expected);
     }
 }
@@ -0,0 +1,278 @@
+use crate::{
+    application_protocol::{
+        application_pdu::ApplicationPdu,
+        primitives::data_value::CharacterString,
+        unconfirmed::{UnconfirmedRequest, UnconfirmedServiceChoice},
+    },
+    common::{
+        error::Error,
+        helper::{
+            decode_optional_context_tag, decode_unsigned, encode_application_object_id,
+            encode_context_object_id, encode_context_unsigned,
+        },
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+    network_protocol::{data_link::DataLink, network_pdu::NetworkMessage},
+};
+
+// the object to look for, either by id or by name
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum WhoHasObject<'a> {
+    ObjectId(ObjectId),
+    ObjectName(CharacterString<'a>),
+}
+
+// asks which device holds an object (devices that have it respond with an IHave)
+// when both limits are omitted every device on the network is expected to respond
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WhoHas<'a> {
+    pub device_instance_low: Option<u32>,
+    pub device_instance_high: Option<u32>,
+    pub object: WhoHasObject<'a>,
+}
+
+impl<'a> WhoHas<'a> {
+    const TAG_LOW_LIMIT: u8 = 0;
+    const TAG_HIGH_LIMIT: u8 = 1;
+    const TAG_OBJECT_ID: u8 = 2;
+    const TAG_OBJECT_NAME: u8 = 3;
+
+    pub fn new(object: WhoHasObject<'a>) -> Self {
+        Self {
+            device_instance_low: None,
+            device_instance_high: None,
+            object,
+        }
+    }
+
+    // only devices with an instance between low and high (inclusive) will respond
+    pub fn for_range(low: u32, high: u32, object: WhoHasObject<'a>) -> Self {
+        Self {
+            device_instance_low: Some(low),
+            device_instance_high: Some(high),
+            object,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(UnconfirmedServiceChoice::WhoHas as u8);
+
+        // the limits are only valid as a pair
+        if let (Some(low), Some(high)) = (self.device_instance_low, self.device_instance_high) {
+            encode_context_unsigned(writer, Self::TAG_LOW_LIMIT, low);
+            encode_context_unsigned(writer, Self::TAG_HIGH_LIMIT, high);
+        }
+
+        match &self.object {
+            WhoHasObject::ObjectId(object_id) => {
+                encode_context_object_id(writer, Self::TAG_OBJECT_ID, object_id)
+            }
+            WhoHasObject::ObjectName(object_name) => {
+                object_name.encode_context(Self::TAG_OBJECT_NAME, writer)
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let (device_instance_low, device_instance_high) =
+            match decode_optional_context_tag(reader, buf, Self::TAG_LOW_LIMIT)? {
+                Some(tag) => {
+                    let low = decode_unsigned(tag.value, reader, buf)? as u32;
+                    let tag = Tag::decode_expected(
+                        reader,
+                        buf,
+                        TagNumber::ContextSpecific(Self::TAG_HIGH_LIMIT),
+                        "WhoHas decode device_instance_high",
+                    )?;
+                    let high = decode_unsigned(tag.value, reader, buf)? as u32;
+                    (Some(low), Some(high))
+                }
+                None => (None, None),
+            };
+
+        let tag = Tag::decode(reader, buf)?;
+        let object = match tag.number {
+            TagNumber::ContextSpecific(Self::TAG_OBJECT_ID) => {
+                WhoHasObject::ObjectId(ObjectId::decode(tag.value, reader, buf)?)
+            }
+            TagNumber::ContextSpecific(Self::TAG_OBJECT_NAME) => {
+                WhoHasObject::ObjectName(CharacterString::decode(tag.value, reader, buf)?)
+            }
+            number => return Err(Error::TagNotSupported(("WhoHas decode object", number))),
+        };
+
+        Ok(Self {
+            device_instance_low,
+            device_instance_high,
+            object,
+        })
+    }
+}
+
+// the reply to a WhoHas, the object is held by the device with device_id
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct IHave<'a> {
+    pub device_id: ObjectId,
+    pub object_id: ObjectId,
+    pub object_name: CharacterString<'a>,
+}
+
+impl<'a> TryFrom<DataLink<'a>> for IHave<'a> {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        match value.into_npdu() {
+            Some(x) => match x.network_message {
+                NetworkMessage::Apdu(ApplicationPdu::UnconfirmedRequest(
+                    UnconfirmedRequest::IHave(i_have),
+                )) => Ok(i_have),
+                _ => Err(Error::ConvertDataLink("npdu message is not an apdu i_have")),
+            },
+            _ => Err(Error::ConvertDataLink("no npdu defined in message")),
+        }
+    }
+}
+
+impl<'a> IHave<'a> {
+    pub fn new(device_id: ObjectId, object_id: ObjectId, object_name: CharacterString<'a>) -> Self {
+        Self {
+            device_id,
+            object_id,
+            object_name,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(UnconfirmedServiceChoice::IHave as u8);
+        encode_application_object_id(writer, &self.device_id);
+        encode_application_object_id(writer, &self.object_id);
+        self.object_name.encode_application(writer);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::ObjectId),
+            "IHave decode device_id",
+        )?;
+        let device_id = ObjectId::decode(tag.value, reader, buf)?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::ObjectId),
+            "IHave decode object_id",
+        )?;
+        let object_id = ObjectId::decode(tag.value, reader, buf)?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::CharacterString),
+            "IHave decode object_name",
+        )?;
+        let object_name = CharacterString::decode(tag.value, reader, buf)?;
+
+        Ok(Self {
+            device_id,
+            object_id,
+            object_name,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::primitives::data_value::CharacterString,
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+        },
+        network_protocol::data_link::DataLink,
+    };
+
+    use super::{IHave, WhoHas, WhoHasObject};
+
+    #[test]
+    fn who_has_object_id() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 5);
+        let who_has = WhoHas::for_range(10, 20, WhoHasObject::ObjectId(object_id));
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        who_has.encode(&mut writer);
+        #[rustfmt::skip]
+        let expected = [
+            0x07,
+            0x09, 0x0a, 0x19, 0x14,
+            0x2c, 0x00, 0x00, 0x00, 0x05,
+        ];
+        assert_eq!(writer.to_bytes(), &expected);
+
+        // skip the service choice
+        let mut reader = Reader::new_with_len(expected.len());
+        reader.index = 1;
+        let decoded = WhoHas::decode(&mut reader, &expected).unwrap();
+        assert_eq!(decoded.device_instance_low, Some(10));
+        assert_eq!(decoded.device_instance_high, Some(20));
+        assert!(matches!(decoded.object, WhoHasObject::ObjectId(x) if x == object_id));
+    }
+
+    #[test]
+    fn who_has_object_name() {
+        let who_has = WhoHas::new(WhoHasObject::ObjectName(CharacterString::new("OAT")));
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        who_has.encode(&mut writer);
+        let expected = [0x07, 0x3c, 0x00, b'O', b'A', b'T'];
+        assert_eq!(writer.to_bytes(), &expected);
+
+        let mut reader = Reader::new_with_len(expected.len());
+        reader.index = 1;
+        let decoded = WhoHas::decode(&mut reader, &expected).unwrap();
+        assert_eq!(decoded.device_instance_low, None);
+        assert_eq!(decoded.device_instance_high, None);
+        match decoded.object {
+            WhoHasObject::ObjectName(x) => assert_eq!(x.as_str(), "OAT"),
+            x => panic!("expected object name, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn decode_i_have() {
+        // broadcast i_have from device 1234 for AnalogInput:5 named OAT
+        #[rustfmt::skip]
+        let buf = [
+            0x81, 0x0b, 0x00, 0x17, 0x01, 0x00,
+            0x10, 0x01,
+            0xc4, 0x02, 0x00, 0x04, 0xd2,
+            0xc4, 0x00, 0x00, 0x00, 0x05,
+            0x74, 0x00, b'O', b'A', b'T',
+        ];
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, &buf).unwrap();
+        let i_have: IHave = message.try_into().unwrap();
+        assert_eq!(
+            i_have.device_id,
+            ObjectId::new(ObjectType::ObjectDevice, 1234)
+        );
+        assert_eq!(
+            i_have.object_id,
+            ObjectId::new(ObjectType::ObjectAnalogInput, 5)
+        );
+        assert_eq!(i_have.object_name.as_str(), "OAT");
+
+        let mut out = [0; 16];
+        let mut writer = Writer::new(&mut out);
+        i_have.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &buf[7..]);
+    }
+}
@@ -1,18 +1,183 @@
 use crate::{
     application_protocol::unconfirmed::UnconfirmedServiceChoice,
-    common::io::{Reader, Writer},
+    common::{
+        error::Error,
+        helper::{decode_unsigned, encode_context_unsigned},
+        io::{Reader, Writer},
+        spec::{BACNET_MAX_INSTANCE, UNCONFIGURED_DEVICE_INSTANCE},
+        tag::{Tag, TagNumber},
+    },
 };
 
-#[derive(Debug, Clone)]
+// when both limits are omitted every device on the network is expected to respond
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct WhoIs {}
+pub struct WhoIs {
+    pub device_instance_low: Option<u32>,
+    pub device_instance_high: Option<u32>,
+}
 
 impl WhoIs {
+    const TAG_LOW_LIMIT: u8 = 0;
+    const TAG_HIGH_LIMIT: u8 = 1;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // only devices with an instance between low and high (inclusive) will respond
+    // NOTE: unconfigured devices (UNCONFIGURED_DEVICE_INSTANCE) cannot be reliably targeted with a
+    // range, use a WhoIs without limits to discover them
+    pub fn for_range(low: u32, high: u32) -> Result<Self, Error> {
+        if high > BACNET_MAX_INSTANCE {
+            return Err(Error::InvalidValue(
+                "WhoIs device instance limit is larger than the max instance",
+            ));
+        }
+        if low > high {
+            return Err(Error::InvalidValue(
+                "WhoIs device instance low limit is larger than the high limit",
+            ));
+        }
+        Ok(Self {
+            device_instance_low: Some(low),
+            device_instance_high: Some(high),
+        })
+    }
+
+    // targets exactly one device, devices with any other instance will not respond
+    pub fn for_instance(instance: u32) -> Result<Self, Error> {
+        Self::for_range(instance, instance)
+    }
+
+    // returns true if a device with this instance is expected to respond to the WhoIs
+    // an unconfigured device only responds when no limits are given
+    // the limits are only valid as a pair so no device responds when just one of them is given
+    pub fn matches(&self, device_instance: u32) -> bool {
+        match (self.device_instance_low, self.device_instance_high) {
+            (None, None) => true,
+            (Some(_), Some(_)) if device_instance == UNCONFIGURED_DEVICE_INSTANCE => false,
+            (Some(low), Some(high)) => device_instance >= low && device_instance <= high,
+            _ => false,
+        }
+    }
+
+    // NOTE: a single limit is encoded as given (rather than widening the WhoIs to every device)
+    // so the receiver rejects it as malformed
     pub fn encode(&self, writer: &mut Writer) {
-        writer.push(UnconfirmedServiceChoice::WhoIs as u8)
+        writer.push(UnconfirmedServiceChoice::WhoIs as u8);
+
+        if let Some(low) = self.device_instance_low {
+            encode_context_unsigned(writer, Self::TAG_LOW_LIMIT, low);
+        }
+        if let Some(high) = self.device_instance_high {
+            encode_context_unsigned(writer, Self::TAG_HIGH_LIMIT, high);
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        if reader.eof() {
+            return Ok(Self::default());
+        }
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_LOW_LIMIT),
+            "WhoIs decode device_instance_low",
+        )?;
+        let low = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_HIGH_LIMIT),
+            "WhoIs decode device_instance_high",
+        )?;
+        let high = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        Ok(Self {
+            device_instance_low: Some(low),
+            device_instance_high: Some(high),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        io::{Reader, Writer},
+        spec::{BACNET_MAX_INSTANCE, UNCONFIGURED_DEVICE_INSTANCE},
+    };
+
+    use super::WhoIs;
+
+    #[test]
+    fn for_instance() {
+        let who_is = WhoIs::for_instance(1234).unwrap();
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        who_is.encode(&mut writer);
+        let len = writer.index;
+        assert_eq!(&buf[..len], &[8, 0x0a, 0x04, 0xd2, 0x1a, 0x04, 0xd2]);
+
+        // skip the service choice
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = WhoIs::decode(&mut reader, &buf).unwrap();
+        assert_eq!(decoded.device_instance_low, Some(1234));
+        assert_eq!(decoded.device_instance_high, Some(1234));
+        assert!(decoded.matches(1234));
+        assert!(!decoded.matches(1233));
+        assert!(!decoded.matches(1235));
+    }
+
+    #[test]
+    fn no_limits() {
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        WhoIs::new().encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &[8]);
+
+        let mut reader = Reader::new_with_len(1);
+        reader.index = 1;
+        let decoded = WhoIs::decode(&mut reader, &buf).unwrap();
+        assert!(decoded.matches(0));
+        assert!(decoded.matches(4194303));
+    }
+
+    #[test]
+    fn limits_are_only_valid_as_a_pair() {
+        let who_is = WhoIs {
+            device_instance_low: Some(10),
+            device_instance_high: None,
+        };
+        assert!(!who_is.matches(10));
+
+        // a low limit without a high limit is not widened to every device but is malformed
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        who_is.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &[8, 0x09, 0x0a]);
+
+        let buf = [8, 0x09, 0x0a];
+        let mut reader = Reader::new_with_len(buf.len());
+        reader.index = 1;
+        assert!(WhoIs::decode(&mut reader, &buf).is_err());
+    }
+
+    #[test]
+    fn invalid_ranges_are_errors() {
+        assert!(WhoIs::for_range(20, 10).is_err());
+        assert!(WhoIs::for_range(0, BACNET_MAX_INSTANCE + 1).is_err());
+        assert!(WhoIs::for_instance(BACNET_MAX_INSTANCE + 1).is_err());
     }
 
-    pub fn decode(_reader: &mut Reader, _buf: &[u8]) -> Self {
-        Self {}
+    #[test]
+    fn unconfigured_device_only_matches_without_limits() {
+        let who_is = WhoIs::for_range(0, UNCONFIGURED_DEVICE_INSTANCE).unwrap();
+        assert!(who_is.matches(UNCONFIGURED_DEVICE_INSTANCE - 1));
+        assert!(!who_is.matches(UNCONFIGURED_DEVICE_INSTANCE));
+        assert!(WhoIs::new().matches(UNCONFIGURED_DEVICE_INSTANCE));
     }
 }
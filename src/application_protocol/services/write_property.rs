@@ -1,11 +1,11 @@
 use crate::{
-    application_protocol::primitives::data_value::ApplicationDataValueWrite,
+    application_protocol::primitives::data_value::{ApplicationDataValueWrite, Enumerated},
     common::{
         error::Error,
         helper::{
-            decode_context_object_id, decode_context_property_id, decode_unsigned,
-            encode_closing_tag, encode_context_enumerated, encode_context_object_id,
-            encode_context_unsigned, encode_opening_tag,
+            decode_context_object_id, decode_context_property_id, decode_optional_context_tag,
+            decode_unsigned, encode_closing_tag, encode_context_enumerated,
+            encode_context_object_id, encode_context_unsigned, encode_opening_tag,
         },
         io::{Reader, Writer},
         object_id::ObjectId,
@@ -31,6 +31,7 @@ impl<'a> WriteProperty<'a> {
     const TAG_ARRAY_INDEX: u8 = 2;
     const TAG_VALUE: u8 = 3;
     const TAG_PRIORITY: u8 = 4;
+    const HIGHEST_PRIORITY: u8 = 1;
     const LOWEST_PRIORITY: u8 = 16;
 
     pub fn new(
@@ -49,6 +50,43 @@ impl<'a> WriteProperty<'a> {
         }
     }
 
+    // writes a raw enumerated value at the lowest priority
+    pub fn write_enum(object_id: ObjectId, property_id: PropertyId, value: u32) -> Self {
+        Self::write_enumerated(object_id, property_id, Enumerated::Unknown(value))
+    }
+
+    // writes a known enumeration (e.g. EngineeringUnits or EventState) at the lowest priority
+    pub fn write_enumerated(
+        object_id: ObjectId,
+        property_id: PropertyId,
+        value: impl Into<Enumerated>,
+    ) -> Self {
+        let value = ApplicationDataValueWrite::Enumerated(value.into());
+        Self::new(object_id, property_id, None, None, value)
+    }
+
+    // releases the command (e.g. an operator override) at this priority by writing Null to the present value
+    // the object falls back to the next highest priority command or its Relinquish_Default
+    pub fn relinquish(object_id: ObjectId, priority: u8) -> Result<Self, Error> {
+        let priority = Self::check_priority(priority as u64)?;
+        Ok(Self::new(
+            object_id,
+            PropertyId::PropPresentValue,
+            Some(priority),
+            None,
+            ApplicationDataValueWrite::Null,
+        ))
+    }
+
+    // command priorities run from 1 (highest) to 16 (lowest)
+    pub(crate) fn check_priority(priority: u64) -> Result<u8, Error> {
+        if (Self::HIGHEST_PRIORITY as u64..=Self::LOWEST_PRIORITY as u64).contains(&priority) {
+            Ok(priority as u8)
+        } else {
+            Err(Error::InvalidValue("priority must be between 1 and 16"))
+        }
+    }
+
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
         let object_id = decode_context_object_id(
@@ -90,14 +128,11 @@ impl<'a> WriteProperty<'a> {
             "WriteProperty decode value",
         )?;
 
-        // priority
-        let tag = Tag::decode_expected(
-            reader,
-            buf,
-            TagNumber::ContextSpecific(Self::TAG_PRIORITY),
-            "WriteProperty decode priority",
-        )?;
-        let priority = tag.value as u8;
+        // priority (optional, a missing priority means the lowest priority)
+        let priority = match decode_optional_context_tag(reader, buf, Self::TAG_PRIORITY)? {
+            Some(tag) => Self::check_priority(decode_unsigned(tag.value, reader, buf)?)?,
+            None => Self::LOWEST_PRIORITY,
+        };
         let priority = if priority == Self::LOWEST_PRIORITY {
             None
         } else {
@@ -138,3 +173,182 @@ impl<'a> WriteProperty<'a> {
         encode_context_unsigned(writer, Self::TAG_PRIORITY, priority);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu,
+            confirmed::{ConfirmedRequest, ConfirmedRequestService},
+            primitives::data_value::{ApplicationDataValueWrite, CharacterString},
+        },
+        common::{
+            error::Error,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::EngineeringUnits,
+        },
+        network_protocol::{data_link::DataLink, network_pdu::NetworkMessage},
+    };
+
+    use super::WriteProperty;
+
+    #[test]
+    fn write_units() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogValue, 1);
+        let request = WriteProperty::write_enumerated(
+            object_id,
+            PropertyId::PropUnits,
+            EngineeringUnits::DegreesCelsius,
+        );
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+
+        // value is wrapped in context tag 3 and the units are an application tagged enumerated
+        assert_eq!(
+            writer.to_bytes(),
+            &[0x0c, 0x00, 0x80, 0x00, 0x01, 0x19, 0x75, 0x3e, 0x91, 0x3e, 0x3f, 0x49, 0x10]
+        );
+
+        let mut writer = Writer::new(&mut buf);
+        WriteProperty::write_enum(object_id, PropertyId::PropUnits, 62).encode(&mut writer);
+        assert_eq!(&writer.to_bytes()[7..11], &[0x3e, 0x91, 0x3e, 0x3f]);
+    }
+
+    #[test]
+    fn decode_with_and_without_priority() {
+        // AnalogValue:1 Present_Value = 21.5 at priority 8
+        let buf = [
+            0x0c, 0x00, 0x80, 0x00, 0x01, 0x19, 0x55, 0x3e, 0x44, 0x41, 0xac, 0x00, 0x00, 0x3f,
+            0x49, 0x08,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let request = WriteProperty::decode(&mut reader, &buf).unwrap();
+        assert_eq!(request.property_id, PropertyId::PropPresentValue);
+        assert_eq!(request.priority, Some(8));
+        assert_eq!(request.array_index, None);
+        assert!(matches!(request.value, ApplicationDataValueWrite::Real(x) if x == 21.5));
+
+        // the priority is optional
+        let buf = &buf[..14];
+        let mut reader = Reader::new_with_len(buf.len());
+        let request = WriteProperty::decode(&mut reader, buf).unwrap();
+        assert_eq!(request.priority, None);
+        assert!(reader.eof());
+    }
+
+    #[test]
+    fn decode_priority_out_of_range_is_error() {
+        // AnalogValue:1 Present_Value = 21.5 at priority 0
+        let buf = [
+            0x0c, 0x00, 0x80, 0x00, 0x01, 0x19, 0x55, 0x3e, 0x44, 0x41, 0xac, 0x00, 0x00, 0x3f,
+            0x49, 0x00,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let result = WriteProperty::decode(&mut reader, &buf);
+        assert!(matches!(result, Err(Error::InvalidValue(_))));
+
+        // priority 264 must not be truncated to 8
+        let buf = [
+            0x0c, 0x00, 0x80, 0x00, 0x01, 0x19, 0x55, 0x3e, 0x44, 0x41, 0xac, 0x00, 0x00, 0x3f,
+            0x4a, 0x01, 0x08,
+        ];
+        let mut reader = Reader::new_with_len(buf.len());
+        let result = WriteProperty::decode(&mut reader, &buf);
+        assert!(matches!(result, Err(Error::InvalidValue(_))));
+    }
+
+    #[test]
+    fn send_as_confirmed_request() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogValue, 1);
+        let value = ApplicationDataValueWrite::Real(21.5);
+        let request = WriteProperty::new(
+            object_id,
+            PropertyId::PropPresentValue,
+            Some(8),
+            None,
+            value,
+        );
+        let service = ConfirmedRequestService::WriteProperty(request);
+        let data_link = DataLink::new_confirmed_req(ConfirmedRequest::new(3, service));
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        data_link.encode(&mut writer);
+        let buf = writer.to_bytes();
+
+        let mut reader = Reader::default();
+        let data_link = DataLink::decode(&mut reader, buf).unwrap();
+        let request = match data_link.into_npdu().unwrap().network_message {
+            NetworkMessage::Apdu(ApplicationPdu::ConfirmedRequest(ConfirmedRequest {
+                invoke_id: 3,
+                service: ConfirmedRequestService::WriteProperty(request),
+                ..
+            })) => request,
+            x => panic!("expected a WriteProperty request: {:?}", x),
+        };
+        assert_eq!(request.object_id, object_id);
+        assert_eq!(request.priority, Some(8));
+        assert!(matches!(request.value, ApplicationDataValueWrite::Real(x) if x == 21.5));
+    }
+
+    #[test]
+    fn write_object_name() {
+        // rename AnalogValue:1 to "Zone Temp"
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogValue, 1);
+        let value = ApplicationDataValueWrite::CharacterString(CharacterString::new("Zone Temp"));
+        let request = WriteProperty::new(object_id, PropertyId::PropObjectName, None, None, value);
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        let buf = writer.to_bytes();
+        #[rustfmt::skip]
+        let expected = [
+            0x0c, 0x00, 0x80, 0x00, 0x01,
+            0x19, 0x4d,
+            0x3e, 0x75, 0x0a, 0x00, b'Z', b'o', b'n', b'e', b' ', b'T', b'e', b'm', b'p', 0x3f,
+            0x49, 0x10, // lowest priority when none is given
+        ];
+        assert_eq!(buf, expected);
+
+        let mut reader = Reader::new_with_len(buf.len());
+        let request = WriteProperty::decode(&mut reader, buf).unwrap();
+        assert!(matches!(
+            request.value,
+            ApplicationDataValueWrite::CharacterString(x) if x.inner == "Zone Temp"
+        ));
+    }
+
+    #[test]
+    fn relinquish_priority() {
+        // release the manual operator (priority 8) override of AnalogOutput:1
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogOutput, 1);
+        let request = WriteProperty::relinquish(object_id, 8).unwrap();
+
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        let buf = writer.to_bytes();
+        #[rustfmt::skip]
+        let expected = [
+            0x0c, 0x00, 0x40, 0x00, 0x01,
+            0x19, 0x55,
+            0x3e, 0x00, 0x3f, // null is application tag 0 with no content
+            0x49, 0x08,
+        ];
+        assert_eq!(buf, expected);
+
+        let mut reader = Reader::new_with_len(buf.len());
+        let request = WriteProperty::decode(&mut reader, buf).unwrap();
+        assert!(matches!(request.value, ApplicationDataValueWrite::Null));
+        assert_eq!(request.priority, Some(8));
+        assert!(reader.eof());
+
+        // there is nothing to relinquish outside of the 16 priorities
+        assert!(WriteProperty::relinquish(object_id, 0).is_err());
+        assert!(WriteProperty::relinquish(object_id, 17).is_err());
+    }
+}
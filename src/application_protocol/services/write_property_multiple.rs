@@ -0,0 +1,221 @@
+use crate::{
+    application_protocol::{
+        primitives::data_value::ApplicationDataValueWrite, services::write_property::WriteProperty,
+    },
+    common::{
+        error::Error,
+        helper::{
+            encode_closing_tag, encode_context_enumerated, encode_context_object_id,
+            encode_context_unsigned, encode_opening_tag,
+        },
+        io::Writer,
+        object_id::ObjectId,
+        property_id::PropertyId,
+    },
+};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+// writes several properties of several objects in one request (the reply is a SimpleAck)
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WritePropertyMultiple<'a> {
+    pub specs: &'a [WriteAccessSpecification<'a>],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WritePropertyMultiple<'a> {
+    pub specs: Vec<WriteAccessSpecification<'a>>,
+}
+
+// the properties to write for a single object
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WriteAccessSpecification<'a> {
+    pub object_id: ObjectId,
+    pub values: &'a [WritePropertyValue<'a>],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WriteAccessSpecification<'a> {
+    pub object_id: ObjectId,
+    pub values: Vec<WritePropertyValue<'a>>,
+}
+
+// a BACnetPropertyValue, the priority is only used for commandable properties (e.g. Present_Value)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WritePropertyValue<'a> {
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub value: ApplicationDataValueWrite<'a>,
+    pub priority: Option<u8>, // 1-16, use with_priority to have it checked
+}
+
+impl<'a> WritePropertyMultiple<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(specs: &'a [WriteAccessSpecification<'a>]) -> Self {
+        Self { specs }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(specs: Vec<WriteAccessSpecification<'a>>) -> Self {
+        Self { specs }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        for spec in self.specs.iter() {
+            spec.encode(writer);
+        }
+    }
+}
+
+impl<'a> WriteAccessSpecification<'a> {
+    const TAG_OBJECT_ID: u8 = 0;
+    const TAG_VALUES: u8 = 1;
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(object_id: ObjectId, values: &'a [WritePropertyValue<'a>]) -> Self {
+        Self { object_id, values }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(object_id: ObjectId, values: Vec<WritePropertyValue<'a>>) -> Self {
+        Self { object_id, values }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+        encode_opening_tag(writer, Self::TAG_VALUES);
+        for value in self.values.iter() {
+            value.encode(writer);
+        }
+        encode_closing_tag(writer, Self::TAG_VALUES);
+    }
+}
+
+impl<'a> WritePropertyValue<'a> {
+    const TAG_PROPERTY_ID: u8 = 0;
+    const TAG_ARRAY_INDEX: u8 = 1;
+    const TAG_VALUE: u8 = 2;
+    const TAG_PRIORITY: u8 = 3;
+
+    pub fn new(property_id: PropertyId, value: ApplicationDataValueWrite<'a>) -> Self {
+        Self {
+            property_id,
+            array_index: None,
+            value,
+            priority: None,
+        }
+    }
+
+    // writes the value at a command priority from 1 (highest) to 16 (lowest)
+    pub fn with_priority(mut self, priority: u8) -> Result<Self, Error> {
+        self.priority = Some(WriteProperty::check_priority(priority as u64)?);
+        Ok(self)
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        // property_id
+        encode_context_enumerated(writer, Self::TAG_PROPERTY_ID, &self.property_id);
+
+        // array_index
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::TAG_ARRAY_INDEX, array_index);
+        }
+
+        // value
+        encode_opening_tag(writer, Self::TAG_VALUE);
+        self.value.encode(writer);
+        encode_closing_tag(writer, Self::TAG_VALUE);
+
+        // priority 1-16 (16 being lowest priority)
+        if let Some(priority) = self.priority {
+            encode_context_unsigned(writer, Self::TAG_PRIORITY, priority as u32);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::primitives::data_value::ApplicationDataValueWrite,
+        common::{
+            error::Error,
+            io::Writer,
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+    };
+
+    use super::{WriteAccessSpecification, WritePropertyMultiple, WritePropertyValue};
+
+    #[test]
+    fn encode_two_setpoints() {
+        let setpoint = WritePropertyValue::new(
+            PropertyId::PropPresentValue,
+            ApplicationDataValueWrite::Real(21.5),
+        )
+        .with_priority(8)
+        .unwrap();
+        let enable = WritePropertyValue::new(
+            PropertyId::PropOutOfService,
+            ApplicationDataValueWrite::Boolean(true),
+        );
+
+        let av_1 = ObjectId::new(ObjectType::ObjectAnalogValue, 1);
+        let bv_2 = ObjectId::new(ObjectType::ObjectBinaryValue, 2);
+
+        #[cfg(feature = "alloc")]
+        let request = WritePropertyMultiple::new(alloc::vec![
+            WriteAccessSpecification::new(av_1, alloc::vec![setpoint]),
+            WriteAccessSpecification::new(bv_2, alloc::vec![enable]),
+        ]);
+
+        #[cfg(not(feature = "alloc"))]
+        let (setpoints, enables) = ([setpoint], [enable]);
+        #[cfg(not(feature = "alloc"))]
+        let specs = [
+            WriteAccessSpecification::new(av_1, &setpoints),
+            WriteAccessSpecification::new(bv_2, &enables),
+        ];
+        #[cfg(not(feature = "alloc"))]
+        let request = WritePropertyMultiple::new(&specs);
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+
+        #[rustfmt::skip]
+        let expected = [
+            // AnalogValue:1 { Present_Value = 21.5 @ 8 }
+            0x0c, 0x00, 0x80, 0x00, 0x01, 0x1e,
+            0x09, 0x55, 0x2e, 0x44, 0x41, 0xac, 0x00, 0x00, 0x2f, 0x39, 0x08,
+            0x1f,
+            // BinaryValue:2 { Out_Of_Service = true }
+            0x0c, 0x01, 0x40, 0x00, 0x02, 0x1e,
+            0x09, 0x51, 0x2e, 0x11, 0x2f,
+            0x1f,
+        ];
+        assert_eq!(writer.to_bytes(), &expected);
+    }
+
+    #[test]
+    fn priority_out_of_range_is_error() {
+        for priority in [0, 17] {
+            let value = WritePropertyValue::new(
+                PropertyId::PropPresentValue,
+                ApplicationDataValueWrite::Real(21.5),
+            )
+            .with_priority(priority);
+            assert!(matches!(value, Err(Error::InvalidValue(_))));
+        }
+    }
+}
@@ -2,7 +2,11 @@ use crate::{
     application_protocol::{
         application_pdu::ApduType,
         services::{
-            change_of_value::CovNotification, i_am::IAm, time_synchronization::TimeSynchronization,
+            change_of_value::CovNotification,
+            event_notification::EventNotification,
+            i_am::IAm,
+            time_synchronization::{TimeSynchronization, UtcTimeSynchronization},
+            who_has::{IHave, WhoHas},
             who_is::WhoIs,
         },
     },
@@ -19,6 +23,10 @@ pub enum UnconfirmedRequest<'a> {
     IAm(IAm),
     CovNotification(CovNotification<'a>),
     TimeSynchronization(TimeSynchronization),
+    UtcTimeSynchronization(UtcTimeSynchronization),
+    WhoHas(WhoHas<'a>),
+    IHave(IHave<'a>),
+    EventNotification(EventNotification<'a>),
 }
 
 impl<'a> UnconfirmedRequest<'a> {
@@ -28,8 +36,15 @@ impl<'a> UnconfirmedRequest<'a> {
         match &self {
             Self::IAm(payload) => payload.encode(writer),
             Self::WhoIs(payload) => payload.encode(writer),
-            Self::CovNotification(_) => todo!(),
+            Self::CovNotification(payload) => payload.encode(writer),
             Self::TimeSynchronization(payload) => payload.encode(writer),
+            Self::UtcTimeSynchronization(payload) => payload.encode(writer),
+            Self::WhoHas(payload) => payload.encode(writer),
+            Self::IHave(payload) => payload.encode(writer),
+            Self::EventNotification(payload) => {
+                writer.push(UnconfirmedServiceChoice::EventNotification as u8);
+                payload.encode(writer)
+            }
         }
     }
 
@@ -45,13 +60,25 @@ impl<'a> UnconfirmedRequest<'a> {
                 Ok(Self::IAm(apdu))
             }
             UnconfirmedServiceChoice::WhoIs => {
-                let apdu = WhoIs::decode(reader, buf);
+                let apdu = WhoIs::decode(reader, buf)?;
                 Ok(Self::WhoIs(apdu))
             }
             UnconfirmedServiceChoice::CovNotification => {
                 let apdu = CovNotification::decode(reader, buf)?;
                 Ok(Self::CovNotification(apdu))
             }
+            UnconfirmedServiceChoice::EventNotification => {
+                let apdu = EventNotification::decode(reader, buf)?;
+                Ok(Self::EventNotification(apdu))
+            }
+            UnconfirmedServiceChoice::WhoHas => {
+                let apdu = WhoHas::decode(reader, buf)?;
+                Ok(Self::WhoHas(apdu))
+            }
+            UnconfirmedServiceChoice::IHave => {
+                let apdu = IHave::decode(reader, buf)?;
+                Ok(Self::IHave(apdu))
+            }
             x => Err(Error::Unimplemented(
                 Unimplemented::UnconfirmedServiceChoice(x),
             )),
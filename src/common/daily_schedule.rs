@@ -11,9 +11,11 @@ use crate::common::{
 use {crate::common::spooky::Phantom, alloc::vec::Vec};
 
 // note that Debug is implemented manually here because of the reader in time value iter
+// without an allocator the schedule can be serialized but not deserialized
 #[cfg(not(feature = "alloc"))]
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct WeeklySchedule<'a> {
     pub monday: TimeValueList<'a>,
     pub tuesday: TimeValueList<'a>,
@@ -27,6 +29,7 @@ pub struct WeeklySchedule<'a> {
 #[cfg(feature = "alloc")]
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WeeklySchedule<'a> {
     pub monday: Vec<TimeValue>,
     pub tuesday: Vec<TimeValue>,
@@ -35,6 +38,7 @@ pub struct WeeklySchedule<'a> {
     pub friday: Vec<TimeValue>,
     pub saturday: Vec<TimeValue>,
     pub sunday: Vec<TimeValue>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
     _phantom: &'a Phantom,
 }
 
@@ -73,6 +77,10 @@ impl<'a> WeeklySchedule<'a> {
         encode_day(writer, self.sunday.iter());
     }
 
+    pub fn encode_day(writer: &mut Writer, day: &[TimeValue]) {
+        encode_day(writer, day.iter());
+    }
+
     // due to the fact that WeeklySchedule contains an arbitrary number of TimeValue pairs we need to return an iterator
     // because we cannot use an allocator
     pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
@@ -89,7 +97,8 @@ impl<'a> WeeklySchedule<'a> {
         ))
     }
 
-    fn decode_day(reader: &mut Reader, buf: &[u8]) -> Result<Vec<TimeValue>, Error> {
+    // a single day, e.g. the value of Weekly_Schedule[1] (monday)
+    pub fn decode_day(reader: &mut Reader, buf: &[u8]) -> Result<Vec<TimeValue>, Error> {
         let (body_buf, _tag_num) = get_tagged_body(reader, buf)?;
         let mut inner_reader = Reader::new_with_len(body_buf.len());
         let mut time_values = Vec::new();
@@ -154,6 +163,15 @@ impl<'a> WeeklySchedule<'a> {
             sunday,
         })
     }
+
+    // a single day, e.g. the value of Weekly_Schedule[1] (monday)
+    pub fn decode_day(reader: &mut Reader, buf: &'a [u8]) -> Result<TimeValueList<'a>, Error> {
+        TimeValueList::decode(reader, buf)
+    }
+
+    pub fn encode_day(writer: &mut Writer, day: &TimeValueList) {
+        day.encode(writer);
+    }
 }
 
 // note that Debug is not implemented here because if does not add value
@@ -197,6 +215,15 @@ impl<'a> TimeValueList<'a> {
     }
 }
 
+// serializes the same way as the Vec<TimeValue> used for each day when alloc is enabled
+#[cfg(all(feature = "serde", not(feature = "alloc")))]
+impl serde::Serialize for TimeValueList<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let time_values = self.time_values.iter().cloned().map(Ok).chain(self);
+        crate::common::helper::serialize_decoded(serializer, time_values)
+    }
+}
+
 impl<'a> IntoIterator for &'_ TimeValueList<'a> {
     type Item = Result<TimeValue, Error>;
     type IntoIter = TimeValueIter<'a>;
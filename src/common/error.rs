@@ -6,9 +6,15 @@ use crate::{
     common::tag::{ApplicationTagNumber, Tag, TagNumber},
 };
 
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+// non_exhaustive because the variants depend on the enabled features (Context needs alloc)
+// so a match that is exhaustive in one build would not compile in another
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum Error {
     Length((&'static str, u32)),
     InvalidValue(&'static str),
@@ -22,6 +28,70 @@ pub enum Error {
     TagValueInvalid((&'static str, Tag, u32)),
     ReaderEof(usize),
     ConvertDataLink(&'static str),
+    // an error from a nested decoder along with the outer field it was decoding (see with_context)
+    #[cfg(feature = "alloc")]
+    Context(ErrorContext),
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorContext {
+    pub context: &'static str,
+    pub inner: Box<Error>,
+}
+
+// implemented by hand because the derive cannot handle the recursive type
+#[cfg(all(feature = "alloc", feature = "defmt"))]
+impl defmt::Format for ErrorContext {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(fmt, "{}: ", self.context);
+        self.inner.as_ref().format(fmt);
+    }
+}
+
+impl Error {
+    // wraps the error with the name of the field being decoded so that a failure deep inside a nested
+    // structure reports a path (e.g. ReadPropertyMultipleAck -> ObjectWithResults -> PropertyResult)
+    // without alloc there is nowhere to keep the path so the error is returned unchanged
+    pub fn with_context(self, context: &'static str) -> Self {
+        #[cfg(feature = "alloc")]
+        {
+            Self::Context(ErrorContext {
+                context,
+                inner: Box::new(self),
+            })
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        {
+            let _ = context;
+            self
+        }
+    }
+
+    // the original error with all context removed
+    pub fn root(&self) -> &Error {
+        match self {
+            #[cfg(feature = "alloc")]
+            Self::Context(x) => x.inner.root(),
+            x => x,
+        }
+    }
+
+    // the context added with with_context, outermost first
+    pub fn context_path(&self) -> impl Iterator<Item = &'static str> + '_ {
+        core::iter::successors(Some(self), |x| match x {
+            #[cfg(feature = "alloc")]
+            Self::Context(x) => Some(x.inner.as_ref()),
+            _ => None,
+        })
+        .filter_map(|x| match x {
+            #[cfg(feature = "alloc")]
+            Self::Context(x) => Some(x.context),
+            _ => None,
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
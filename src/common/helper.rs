@@ -6,12 +6,33 @@ use crate::common::{
     tag::{ApplicationTagNumber, Tag, TagNumber},
 };
 
+// the number of nested opening tags allowed inside a tagged body before we give up
+// this stops a crafted frame from making us scan a huge number of unbalanced tags
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 32;
+
 // reads and checks the opening tag number passed in
 pub fn get_tagged_body_for_tag<'a>(
     reader: &mut Reader,
     buf: &'a [u8],
     expected_tag_number: u8,
     context: &'static str,
+) -> Result<&'a [u8], Error> {
+    get_tagged_body_for_tag_with_max_depth(
+        reader,
+        buf,
+        expected_tag_number,
+        context,
+        DEFAULT_MAX_NESTING_DEPTH,
+    )
+}
+
+// same as get_tagged_body_for_tag but with a custom limit on nested opening tags
+pub fn get_tagged_body_for_tag_with_max_depth<'a>(
+    reader: &mut Reader,
+    buf: &'a [u8],
+    expected_tag_number: u8,
+    context: &'static str,
+    max_depth: usize,
 ) -> Result<&'a [u8], Error> {
     Tag::decode_expected(
         reader,
@@ -20,7 +41,35 @@ pub fn get_tagged_body_for_tag<'a>(
         context,
     )?;
 
-    get_tagged_body_internal(reader, buf, expected_tag_number)
+    get_tagged_body_internal(reader, buf, expected_tag_number, max_depth)
+}
+
+// reads the opening tag, decodes the body with f and then reads the matching closing tag
+// the value returned by f is passed back to the caller
+pub fn with_context_tag<'a, T, F>(
+    reader: &mut Reader,
+    buf: &'a [u8],
+    tag_number: u8,
+    context: &'static str,
+    f: F,
+) -> Result<T, Error>
+where
+    F: FnOnce(&mut Reader, &'a [u8]) -> Result<T, Error>,
+{
+    Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::ContextSpecificOpening(tag_number),
+        context,
+    )?;
+    let value = f(reader, buf)?;
+    Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::ContextSpecificClosing(tag_number),
+        context,
+    )?;
+    Ok(value)
 }
 
 // This gives you the bytes that begin after the opening tag and end before the closing tag
@@ -31,7 +80,7 @@ pub fn get_tagged_body<'a>(reader: &mut Reader, buf: &'a [u8]) -> Result<(&'a [u
         x => return Err(Error::ExpectedOpeningTag(x)),
     };
 
-    let buf = get_tagged_body_internal(reader, buf, tag_number)?;
+    let buf = get_tagged_body_internal(reader, buf, tag_number, DEFAULT_MAX_NESTING_DEPTH)?;
     Ok((buf, tag_number))
 }
 
@@ -39,12 +88,29 @@ fn get_tagged_body_internal<'a>(
     reader: &mut Reader,
     buf: &'a [u8],
     opening_tag_number: u8,
+    max_depth: usize,
 ) -> Result<&'a [u8], Error> {
     let index = reader.index;
     let mut counter = 0;
+    let mut depth = 0; // all nested opening tags, not just the ones matching ours
     loop {
+        // a missing closing tag will eventually run the reader out of bytes
         let tag = Tag::decode(reader, buf)?;
 
+        match tag.number {
+            TagNumber::ContextSpecificOpening(_) => {
+                depth += 1;
+                if depth > max_depth {
+                    return Err(Error::Length((
+                        "tagged body nested too deeply",
+                        depth as u32,
+                    )));
+                }
+            }
+            TagNumber::ContextSpecificClosing(_) => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+
         // keep track of nested tags and when we reach our last closing tag then we are done
         match tag.number {
             TagNumber::ContextSpecificOpening(x) if x == opening_tag_number => counter += 1,
@@ -57,12 +123,19 @@ fn get_tagged_body_internal<'a>(
                     counter -= 1;
                 }
             }
+            TagNumber::ContextSpecificOpening(_) | TagNumber::ContextSpecificClosing(_) => {
+                // no value to skip
+            }
             TagNumber::Application(ApplicationTagNumber::Boolean) => {
                 // tag value is not a length for bool
             }
             _ => {
                 // skip past value and read next tag
-                reader.index += tag.value as usize;
+                let len = tag.value as usize;
+                if reader.index + len > reader.end {
+                    return Err(Error::ReaderEof(reader.index + len));
+                }
+                reader.index += len;
             }
         }
     }
@@ -82,7 +155,7 @@ pub fn encode_u16(writer: &mut Writer, value: u16) {
 
 pub fn encode_u24(writer: &mut Writer, value: u32) {
     let slice = &value.to_be_bytes();
-    writer.extend_from_slice(&slice[..3]);
+    writer.extend_from_slice(&slice[1..]);
 }
 
 pub fn encode_u32(writer: &mut Writer, value: u32) {
@@ -115,6 +188,27 @@ pub fn decode_context_object_id(
     Ok(object_id)
 }
 
+// reads the next tag only if it is the expected context tag, otherwise the reader is left untouched
+// this is used for optional fields
+pub fn decode_optional_context_tag(
+    reader: &mut Reader,
+    buf: &[u8],
+    expected_tag_num: u8,
+) -> Result<Option<Tag>, Error> {
+    if reader.eof() {
+        return Ok(None);
+    }
+
+    let index = reader.index;
+    let tag = Tag::decode(reader, buf)?;
+    if tag.number == TagNumber::ContextSpecific(expected_tag_num) {
+        Ok(Some(tag))
+    } else {
+        reader.index = index;
+        Ok(None)
+    }
+}
+
 pub fn encode_context_bool(writer: &mut Writer, tag_number: u8, value: bool) {
     const LEN: u32 = 1; // 1 byte
     let tag = Tag::new(TagNumber::ContextSpecific(tag_number), LEN);
@@ -171,7 +265,7 @@ pub fn decode_context_property_id(
 }
 
 pub fn encode_context_enumerated(writer: &mut Writer, tag_number: u8, property_id: &PropertyId) {
-    let value = *property_id as u32;
+    let value = u32::from(*property_id);
     let len = get_len_u64(value as u64);
 
     let tag = Tag::new(TagNumber::ContextSpecific(tag_number), len);
@@ -179,6 +273,7 @@ pub fn encode_context_enumerated(writer: &mut Writer, tag_number: u8, property_i
     encode_unsigned(writer, len, value as u64);
 }
 
+// unsigned values must be encoded using the minimum number of octets (no leading zero bytes)
 pub fn encode_application_unsigned(writer: &mut Writer, value: u64) {
     let len = get_len_u64(value);
     Tag::new(
@@ -215,6 +310,87 @@ pub fn encode_application_signed(writer: &mut Writer, value: i32) {
     encode_signed(writer, len, value);
 }
 
+pub fn encode_context_signed(writer: &mut Writer, tag_number: u8, value: i32) {
+    let mut len = get_len_i32(value);
+    len = if len == 3 { 4 } else { len }; // same as encode_application_signed
+    Tag::new(TagNumber::ContextSpecific(tag_number), len).encode(writer);
+    encode_signed(writer, len, value);
+}
+
+pub fn decode_context_unsigned(
+    reader: &mut Reader,
+    buf: &[u8],
+    expected_tag_number: u8,
+    context: &'static str,
+) -> Result<u32, Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::ContextSpecific(expected_tag_number),
+        context,
+    )?;
+    Ok(decode_unsigned(tag.value, reader, buf)? as u32)
+}
+
+pub fn encode_context_real(writer: &mut Writer, tag_number: u8, value: f32) {
+    Tag::new(TagNumber::ContextSpecific(tag_number), 4).encode(writer);
+    writer.extend_from_slice(&value.to_be_bytes());
+}
+
+pub fn decode_context_real(
+    reader: &mut Reader,
+    buf: &[u8],
+    expected_tag_number: u8,
+    context: &'static str,
+) -> Result<f32, Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::ContextSpecific(expected_tag_number),
+        context,
+    )?;
+    decode_real(&tag, reader, buf, context)
+}
+
+// the tag has already been read
+pub fn decode_real(
+    tag: &Tag,
+    reader: &mut Reader,
+    buf: &[u8],
+    context: &'static str,
+) -> Result<f32, Error> {
+    tag.expect_value(context, 4)?;
+    Ok(f32::from_be_bytes(reader.read_bytes(buf)?))
+}
+
+pub fn decode_application_unsigned(
+    reader: &mut Reader,
+    buf: &[u8],
+    context: &'static str,
+) -> Result<u32, Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+        context,
+    )?;
+    Ok(decode_unsigned(tag.value, reader, buf)? as u32)
+}
+
+pub fn decode_application_signed(
+    reader: &mut Reader,
+    buf: &[u8],
+    context: &'static str,
+) -> Result<i32, Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::Application(ApplicationTagNumber::SignedInt),
+        context,
+    )?;
+    decode_signed(tag.value, reader, buf)
+}
+
 pub fn get_len_u32(value: u32) -> u32 {
     if value < 0x100 {
         1
@@ -290,13 +466,13 @@ pub fn _decode_u32(len: u32, reader: &mut Reader, buf: &[u8]) -> Result<u32, Err
 
 pub fn decode_signed(len: u32, reader: &mut Reader, buf: &[u8]) -> Result<i32, Error> {
     let value = match len {
-        1 => reader.read_byte(buf)? as i32,
-        2 => u16::from_be_bytes(reader.read_bytes(buf)?) as i32,
+        1 => reader.read_byte(buf)? as i8 as i32,
+        2 => i16::from_be_bytes(reader.read_bytes(buf)?) as i32,
         3 => {
             let bytes: [u8; 3] = reader.read_bytes(buf)?;
             let mut tmp: [u8; 4] = [0; 4];
             tmp[1..].copy_from_slice(&bytes);
-            i32::from_be_bytes(tmp)
+            i32::from_be_bytes(tmp) << 8 >> 8 // sign extend
         }
         4 => i32::from_be_bytes(reader.read_bytes(buf)?),
         x => return Err(Error::Length(("signed len must be between 1 and 4", x))),
@@ -324,3 +500,145 @@ pub fn encode_signed(writer: &mut Writer, len: u32, value: i32) {
         _ => unreachable!(),
     }
 }
+
+// serializes a list that is still held as an undecoded buffer (no allocator)
+// the items are decoded one at a time and a decode error fails the serialization
+#[cfg(all(feature = "serde", not(feature = "alloc")))]
+pub(crate) fn serialize_decoded<S, T>(
+    serializer: S,
+    items: impl Iterator<Item = Result<T, Error>>,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: serde::Serialize,
+{
+    use serde::ser::{Error as _, SerializeSeq};
+
+    let mut seq = serializer.serialize_seq(None)?;
+    for item in items {
+        let item = item.map_err(|e| S::Error::custom(format_args!("{:?}", e)))?;
+        seq.serialize_element(&item)?;
+    }
+    seq.end()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        io::{Reader, Writer},
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    };
+
+    use super::{
+        decode_unsigned, encode_application_unsigned, get_tagged_body_for_tag,
+        get_tagged_body_for_tag_with_max_depth, with_context_tag, DEFAULT_MAX_NESTING_DEPTH,
+    };
+    use crate::common::error::Error;
+
+    fn encode_decode_unsigned(value: u64, expected_len: u32) {
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        encode_application_unsigned(&mut writer, value);
+        let len = writer.index;
+        assert_eq!(len, 1 + expected_len as usize);
+
+        let mut reader = Reader::new_with_len(len);
+        let tag = Tag::decode(&mut reader, &buf).unwrap();
+        assert_eq!(
+            tag.number,
+            TagNumber::Application(ApplicationTagNumber::UnsignedInt)
+        );
+        assert_eq!(tag.value, expected_len);
+        let decoded = decode_unsigned(tag.value, &mut reader, &buf).unwrap();
+        assert_eq!(decoded, value);
+        assert!(reader.eof());
+    }
+
+    #[test]
+    fn unsigned_minimum_length() {
+        encode_decode_unsigned(0, 1);
+        encode_decode_unsigned(255, 1);
+        encode_decode_unsigned(256, 2);
+        encode_decode_unsigned(65535, 2);
+        encode_decode_unsigned(65536, 3);
+        encode_decode_unsigned(16777215, 3);
+        encode_decode_unsigned(16777216, 4);
+        encode_decode_unsigned(u32::MAX as u64, 4);
+    }
+
+    #[test]
+    fn unsigned_no_leading_zeros() {
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        encode_application_unsigned(&mut writer, 256);
+        assert_eq!(writer.to_bytes(), &[0x22, 0x01, 0x00]);
+    }
+
+    #[test]
+    fn tagged_body_missing_closing_tag() {
+        // opening tag 1 followed by an unsigned and no closing tag
+        let buf = [0x1e, 0x21, 0x05];
+        let mut reader = Reader::new_with_len(buf.len());
+        let result = get_tagged_body_for_tag(&mut reader, &buf, 1, "test");
+        assert!(matches!(result, Err(Error::ReaderEof(_))));
+    }
+
+    #[test]
+    fn tagged_body_value_past_end() {
+        // unsigned claims 4 bytes but only 1 follows
+        let buf = [0x1e, 0x24, 0x05];
+        let mut reader = Reader::new_with_len(buf.len());
+        let result = get_tagged_body_for_tag(&mut reader, &buf, 1, "test");
+        assert!(matches!(result, Err(Error::ReaderEof(_))));
+    }
+
+    #[test]
+    fn tagged_body_nested_too_deeply() {
+        // opening tag 1 repeated and never closed
+        let buf = [0x1e; DEFAULT_MAX_NESTING_DEPTH + 2];
+        let mut reader = Reader::new_with_len(buf.len());
+        let result = get_tagged_body_for_tag(&mut reader, &buf, 1, "test");
+        assert!(matches!(result, Err(Error::Length(_))));
+    }
+
+    #[test]
+    fn tagged_body_custom_max_depth() {
+        // opening 1, opening 2, closing 2, closing 1
+        let buf = [0x1e, 0x2e, 0x2f, 0x1f];
+
+        let mut reader = Reader::new_with_len(buf.len());
+        let body = get_tagged_body_for_tag_with_max_depth(&mut reader, &buf, 1, "test", 1).unwrap();
+        assert_eq!(body, &[0x2e, 0x2f]);
+        assert!(reader.eof());
+
+        let mut reader = Reader::new_with_len(buf.len());
+        let result = get_tagged_body_for_tag_with_max_depth(&mut reader, &buf, 1, "test", 0);
+        assert!(matches!(result, Err(Error::Length(_))));
+    }
+
+    #[test]
+    fn with_context_tag_decodes_wrapped_value() {
+        // [2] { unsigned 42 } followed by another byte
+        let buf = [0x2e, 0x21, 0x2a, 0x2f, 0xff];
+        let mut reader = Reader::new_with_len(buf.len());
+        let value = with_context_tag(&mut reader, &buf, 2, "test", |reader, buf| {
+            let tag = Tag::decode(reader, buf)?;
+            decode_unsigned(tag.value, reader, buf)
+        })
+        .unwrap();
+        assert_eq!(value, 42);
+        assert_eq!(reader.index, 4);
+    }
+
+    #[test]
+    fn with_context_tag_mismatched_closing_tag() {
+        // [2] { unsigned 42 [3] closing
+        let buf = [0x2e, 0x21, 0x2a, 0x3f];
+        let mut reader = Reader::new_with_len(buf.len());
+        let result = with_context_tag(&mut reader, &buf, 2, "test", |reader, buf| {
+            let tag = Tag::decode(reader, buf)?;
+            decode_unsigned(tag.value, reader, buf)
+        });
+        assert!(matches!(result, Err(Error::ExpectedTag(_))));
+    }
+}
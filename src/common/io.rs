@@ -1,29 +1,97 @@
 use crate::common::error::Error;
 
+// writing past the end of the buffer does not panic, instead nothing more is written and the
+// overflow is reported by try_to_bytes (so encoders do not need to check every write)
 pub struct Writer<'a> {
     pub buf: &'a mut [u8],
     pub index: usize,
+    required: usize, // the length the buffer would need to be to hold everything written
+    error: Option<Error>,
 }
 
 impl<'a> Writer<'a> {
     pub fn new(buf: &'a mut [u8]) -> Self {
-        Self { buf, index: 0 }
+        Self {
+            buf,
+            index: 0,
+            required: 0,
+            error: None,
+        }
+    }
+
+    // records a value that cannot be encoded (e.g. a mandatory field is missing)
+    // the first error is kept and reported by try_to_bytes
+    pub fn fail(&mut self, error: Error) {
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
     }
 
     pub fn push(&mut self, item: u8) {
-        self.buf[self.index] = item;
-        self.index += 1;
+        let _ = self.try_push(item);
     }
 
     pub fn extend_from_slice(&mut self, src: &[u8]) {
-        assert!(src.len() <= self.buf.len() - self.index);
-        self.buf[self.index..self.index + src.len()].copy_from_slice(src);
-        self.index += src.len();
+        let _ = self.try_extend_from_slice(src);
     }
 
+    pub fn try_push(&mut self, item: u8) -> Result<(), Error> {
+        self.try_extend_from_slice(&[item])
+    }
+
+    // once the buffer has overflowed every subsequent write fails too
+    pub fn try_extend_from_slice(&mut self, src: &[u8]) -> Result<(), Error> {
+        self.required += src.len();
+        if self.required > self.buf.len() {
+            return Err(self.overflow_error());
+        }
+        self.buf[self.index..self.required].copy_from_slice(src);
+        self.index = self.required;
+        Ok(())
+    }
+
+    // the number of bytes written so far (e.g. save it to patch a length field in later)
+    pub fn position(&self) -> usize {
+        self.index
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.index
+    }
+
+    // overwrites two bytes that have already been written (e.g. a length placeholder)
+    // nothing is patched if the bytes were never written because the buffer overflowed
+    pub fn patch_u16_at(&mut self, offset: usize, value: u16) {
+        if let Some(dst) = self.buf[..self.index].get_mut(offset..offset + 2) {
+            dst.copy_from_slice(&value.to_be_bytes());
+        }
+    }
+
+    pub fn is_overflow(&self) -> bool {
+        self.required > self.index
+    }
+
+    // NOTE: these bytes are truncated if the buffer overflowed, see try_to_bytes
     pub fn to_bytes(&self) -> &[u8] {
         &self.buf[..self.index]
     }
+
+    pub fn try_to_bytes(&self) -> Result<&[u8], Error> {
+        if let Some(error) = &self.error {
+            return Err(error.clone());
+        }
+        if self.is_overflow() {
+            return Err(self.overflow_error());
+        }
+        Ok(self.to_bytes())
+    }
+
+    fn overflow_error(&self) -> Error {
+        Error::Length((
+            "writer buffer too small for the encoded length",
+            self.required as u32,
+        ))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -46,36 +114,40 @@ impl Reader {
         self.end = len;
     }
 
+    // NOTE: end may be larger than the buffer (see Reader::default) so reads are checked against both
     pub fn read_byte(&mut self, buf: &[u8]) -> Result<u8, Error> {
-        if self.eof() {
-            Err(Error::ReaderEof(self.end))
-        } else {
-            let byte = buf[self.index];
-            self.index += 1;
-            Ok(byte)
+        match buf.get(self.index) {
+            Some(byte) if !self.eof() => {
+                self.index += 1;
+                Ok(*byte)
+            }
+            _ => Err(Error::ReaderEof(self.index + 1)),
         }
     }
 
     pub fn read_bytes<const COUNT: usize>(&mut self, buf: &[u8]) -> Result<[u8; COUNT], Error> {
-        if self.index + COUNT > self.end {
-            Err(Error::ReaderEof(self.index + COUNT))
-        } else {
-            let mut tmp: [u8; COUNT] = [0; COUNT];
-            tmp.copy_from_slice(&buf[self.index..self.index + COUNT]);
-            self.index += COUNT;
-            Ok(tmp)
-        }
+        let slice = self.read_slice(COUNT, buf)?;
+        let mut tmp: [u8; COUNT] = [0; COUNT];
+        tmp.copy_from_slice(slice);
+        Ok(tmp)
     }
 
     pub fn read_slice<'a>(&mut self, len: usize, buf: &'a [u8]) -> Result<&'a [u8], Error> {
-        if self.index + len > self.end {
-            Err(Error::ReaderEof(self.index + len))
+        let end = self.index.saturating_add(len);
+        if end > self.end || end > buf.len() {
+            Err(Error::ReaderEof(end))
         } else {
-            let slice = &buf[self.index..self.index + len];
-            self.index += len;
+            let slice = &buf[self.index..end];
+            self.index = end;
             Ok(slice)
         }
     }
+
+    // reads everything up to the end of the reader (e.g. a list that takes up the rest of a message)
+    pub fn read_remaining<'a>(&mut self, buf: &'a [u8]) -> Result<&'a [u8], Error> {
+        let len = self.end.saturating_sub(self.index);
+        self.read_slice(len, buf)
+    }
 }
 
 impl Default for Reader {
@@ -86,3 +158,83 @@ impl Default for Reader {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::common::error::Error;
+
+    use super::{Reader, Writer};
+
+    #[test]
+    fn patch_length_placeholder() {
+        let mut buf = [0; 8];
+        let mut writer = Writer::new(&mut buf);
+        writer.push(0x81);
+        let len_position = writer.position();
+        writer.extend_from_slice(&[0, 0]);
+        writer.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(writer.remaining(), 2);
+        writer.patch_u16_at(len_position, writer.position() as u16);
+        assert_eq!(writer.to_bytes(), &[0x81, 0x00, 0x06, 1, 2, 3]);
+
+        // only bytes already written can be patched
+        writer.patch_u16_at(5, 0xffff);
+        assert_eq!(writer.to_bytes(), &[0x81, 0x00, 0x06, 1, 2, 3]);
+    }
+
+    #[test]
+    fn write_past_short_buffer() {
+        let mut buf = [0; 4];
+        let mut writer = Writer::new(&mut buf);
+        writer.push(1);
+        assert!(writer.try_extend_from_slice(&[2, 3]).is_ok());
+        assert_eq!(writer.try_to_bytes().unwrap(), &[1, 2, 3]);
+
+        assert!(matches!(
+            writer.try_extend_from_slice(&[4, 5]),
+            Err(Error::Length((_, 5)))
+        ));
+        assert!(writer.is_overflow());
+
+        // nothing more is written even if it would fit
+        writer.push(6);
+        assert!(writer.try_push(7).is_err());
+        assert_eq!(writer.to_bytes(), &[1, 2, 3]);
+        assert!(matches!(writer.try_to_bytes(), Err(Error::Length((_, 7)))));
+    }
+
+    #[test]
+    fn read_past_short_buffer() {
+        // the default reader has no idea how long the buffer is
+        let buf = [1, 2, 3];
+        let mut reader = Reader::default();
+        assert_eq!(reader.read_bytes::<2>(&buf).unwrap(), [1, 2]);
+        assert!(matches!(
+            reader.read_bytes::<2>(&buf),
+            Err(Error::ReaderEof(_))
+        ));
+        assert!(matches!(
+            reader.read_slice(usize::MAX, &buf),
+            Err(Error::ReaderEof(_))
+        ));
+        assert_eq!(reader.read_byte(&buf).unwrap(), 3);
+        assert!(matches!(reader.read_byte(&buf), Err(Error::ReaderEof(_))));
+    }
+
+    #[test]
+    fn read_remaining() {
+        let buf = [1, 2, 3, 4];
+        let mut reader = Reader::new_with_len(3);
+        assert_eq!(reader.read_byte(&buf).unwrap(), 1);
+        assert_eq!(reader.read_remaining(&buf).unwrap(), &[2, 3]);
+        assert!(reader.eof());
+        assert!(reader.read_remaining(&buf).unwrap().is_empty());
+
+        // the default reader never runs past the buffer
+        let mut reader = Reader::default();
+        assert!(matches!(
+            reader.read_remaining(&buf),
+            Err(Error::ReaderEof(_))
+        ));
+    }
+}
@@ -2,7 +2,9 @@ use crate::common::{
     error::Error,
     helper::decode_unsigned,
     io::{Reader, Writer},
-    spec::{BACNET_INSTANCE_BITS, BACNET_MAX_INSTANCE, BACNET_MAX_OBJECT},
+    spec::{
+        BACNET_INSTANCE_BITS, BACNET_MAX_INSTANCE, BACNET_MAX_OBJECT, UNCONFIGURED_DEVICE_INSTANCE,
+    },
 };
 
 // NOTE: Copy is derived for usage convenience
@@ -21,8 +23,19 @@ impl ObjectId {
         Self { object_type, id }
     }
 
+    // the instance number is only 22 bits long
+    pub fn instance(&self) -> u32 {
+        self.id & BACNET_MAX_INSTANCE
+    }
+
+    // a device that has not been given an instance number yet, these should not be put in an
+    // address binding cache because many devices can share this id
+    pub fn is_unconfigured_device(&self) -> bool {
+        self.object_type == ObjectType::ObjectDevice && self.id == UNCONFIGURED_DEVICE_INSTANCE
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
-        let value = ((self.object_type as u32 & BACNET_MAX_OBJECT) << BACNET_INSTANCE_BITS)
+        let value = ((u32::from(self.object_type) & BACNET_MAX_OBJECT) << BACNET_INSTANCE_BITS)
             | (self.id & BACNET_MAX_INSTANCE);
         writer.extend_from_slice(&value.to_be_bytes());
     }
@@ -41,7 +54,7 @@ impl ObjectId {
 // NOTE that copy is derived for usage convenience
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde_repr::Deserialize_repr))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u32)]
 pub enum ObjectType {
     ObjectAnalogInput = 0,
@@ -104,79 +117,209 @@ pub enum ObjectType {
     // Enumerated values 0-127 are reserved for definition by ASHRAE.
     // Enumerated values 128-1023 may be used by others subject to
     // the procedures and constraints described in Clause 23.
-    // do the max range inside of enum so that
-    // compilers will allocate adequate sized datatype for enum
-    // which is used to store decoding
-    Reserved = 57,
-    Proprietary = 128,
+    // the original value is kept so that it can be encoded again
+    Reserved(u16),
+    Proprietary(u16),
     Invalid = 1024,
 }
 
+impl From<u16> for ObjectType {
+    fn from(value: u16) -> Self {
+        match value {
+            0 => Self::ObjectAnalogInput,
+            1 => Self::ObjectAnalogOutput,
+            2 => Self::ObjectAnalogValue,
+            3 => Self::ObjectBinaryInput,
+            4 => Self::ObjectBinaryOutput,
+            5 => Self::ObjectBinaryValue,
+            6 => Self::ObjectCalendar,
+            7 => Self::ObjectCommand,
+            8 => Self::ObjectDevice,
+            9 => Self::ObjectEventEnrollment,
+            10 => Self::ObjectFile,
+            11 => Self::ObjectGroup,
+            12 => Self::ObjectLoop,
+            13 => Self::ObjectMultiStateInput,
+            14 => Self::ObjectMultiStateOutput,
+            15 => Self::ObjectNotificationClass,
+            16 => Self::ObjectProgram,
+            17 => Self::ObjectSchedule,
+            18 => Self::ObjectAveraging,
+            19 => Self::ObjectMultiStateValue,
+            20 => Self::ObjectTrendlog,
+            21 => Self::ObjectLifeSafetyPoint,
+            22 => Self::ObjectLifeSafetyZone,
+            23 => Self::ObjectAccumulator,
+            24 => Self::ObjectPulseConverter,
+            25 => Self::ObjectEventLog,
+            26 => Self::ObjectGlobalGroup,
+            27 => Self::ObjectTrendLogMultiple,
+            28 => Self::ObjectLoadControl,
+            29 => Self::ObjectStructuredView,
+            30 => Self::ObjectAccessDoor,
+            31 => Self::ObjectTimer,
+            32 => Self::ObjectAccessCredential,
+            33 => Self::ObjectAccessPoint,
+            34 => Self::ObjectAccessRights,
+            35 => Self::ObjectAccessUser,
+            36 => Self::ObjectAccessZone,
+            37 => Self::ObjectCredentialDataInput,
+            38 => Self::ObjectNetworkSecurity,
+            39 => Self::ObjectBitstringValue,
+            40 => Self::ObjectCharacterstringValue,
+            41 => Self::ObjectDatePatternValue,
+            42 => Self::ObjectDateValue,
+            43 => Self::ObjectDatetimePatternValue,
+            44 => Self::ObjectDatetimeValue,
+            45 => Self::ObjectIntegerValue,
+            46 => Self::ObjectLargeAnalogValue,
+            47 => Self::ObjectOctetstringValue,
+            48 => Self::ObjectPositiveIntegerValue,
+            49 => Self::ObjectTimePatternValue,
+            50 => Self::ObjectTimeValue,
+            51 => Self::ObjectNotificationForwarder,
+            52 => Self::ObjectAlertEnrollment,
+            53 => Self::ObjectChannel,
+            54 => Self::ObjectLightingOutput,
+            55 => Self::ObjectBinaryLightingOutput,
+            56 => Self::ObjectNetworkPort,
+            57..=127 => Self::Reserved(value),
+            128..=1023 => Self::Proprietary(value),
+            _ => Self::Invalid,
+        }
+    }
+}
+
 impl TryFrom<u32> for ObjectType {
     type Error = u32;
 
+    // object types are only 10 bits long
     fn try_from(value: u32) -> Result<Self, u32> {
+        if value > BACNET_MAX_OBJECT {
+            return Err(value);
+        }
+        Ok(Self::from(value as u16))
+    }
+}
+
+impl From<ObjectType> for u32 {
+    fn from(value: ObjectType) -> Self {
         match value {
-            0 => Ok(Self::ObjectAnalogInput),
-            1 => Ok(Self::ObjectAnalogOutput),
-            2 => Ok(Self::ObjectAnalogValue),
-            3 => Ok(Self::ObjectBinaryInput),
-            4 => Ok(Self::ObjectBinaryOutput),
-            5 => Ok(Self::ObjectBinaryValue),
-            6 => Ok(Self::ObjectCalendar),
-            7 => Ok(Self::ObjectCommand),
-            8 => Ok(Self::ObjectDevice),
-            9 => Ok(Self::ObjectEventEnrollment),
-            10 => Ok(Self::ObjectFile),
-            11 => Ok(Self::ObjectGroup),
-            12 => Ok(Self::ObjectLoop),
-            13 => Ok(Self::ObjectMultiStateInput),
-            14 => Ok(Self::ObjectMultiStateOutput),
-            15 => Ok(Self::ObjectNotificationClass),
-            16 => Ok(Self::ObjectProgram),
-            17 => Ok(Self::ObjectSchedule),
-            18 => Ok(Self::ObjectAveraging),
-            19 => Ok(Self::ObjectMultiStateValue),
-            20 => Ok(Self::ObjectTrendlog),
-            21 => Ok(Self::ObjectLifeSafetyPoint),
-            22 => Ok(Self::ObjectLifeSafetyZone),
-            23 => Ok(Self::ObjectAccumulator),
-            24 => Ok(Self::ObjectPulseConverter),
-            25 => Ok(Self::ObjectEventLog),
-            26 => Ok(Self::ObjectGlobalGroup),
-            27 => Ok(Self::ObjectTrendLogMultiple),
-            28 => Ok(Self::ObjectLoadControl),
-            29 => Ok(Self::ObjectStructuredView),
-            30 => Ok(Self::ObjectAccessDoor),
-            31 => Ok(Self::ObjectTimer),
-            32 => Ok(Self::ObjectAccessCredential),
-            33 => Ok(Self::ObjectAccessPoint),
-            34 => Ok(Self::ObjectAccessRights),
-            35 => Ok(Self::ObjectAccessUser),
-            36 => Ok(Self::ObjectAccessZone),
-            37 => Ok(Self::ObjectCredentialDataInput),
-            38 => Ok(Self::ObjectNetworkSecurity),
-            39 => Ok(Self::ObjectBitstringValue),
-            40 => Ok(Self::ObjectCharacterstringValue),
-            41 => Ok(Self::ObjectDatePatternValue),
-            42 => Ok(Self::ObjectDateValue),
-            43 => Ok(Self::ObjectDatetimePatternValue),
-            44 => Ok(Self::ObjectDatetimeValue),
-            45 => Ok(Self::ObjectIntegerValue),
-            46 => Ok(Self::ObjectLargeAnalogValue),
-            47 => Ok(Self::ObjectOctetstringValue),
-            48 => Ok(Self::ObjectPositiveIntegerValue),
-            49 => Ok(Self::ObjectTimePatternValue),
-            50 => Ok(Self::ObjectTimeValue),
-            51 => Ok(Self::ObjectNotificationForwarder),
-            52 => Ok(Self::ObjectAlertEnrollment),
-            53 => Ok(Self::ObjectChannel),
-            54 => Ok(Self::ObjectLightingOutput),
-            55 => Ok(Self::ObjectBinaryLightingOutput),
-            56 => Ok(Self::ObjectNetworkPort),
-            57..=127 => Ok(Self::Reserved),
-            128..=1023 => Ok(Self::Proprietary),
-            x => Err(x),
+            ObjectType::ObjectAnalogInput => 0,
+            ObjectType::ObjectAnalogOutput => 1,
+            ObjectType::ObjectAnalogValue => 2,
+            ObjectType::ObjectBinaryInput => 3,
+            ObjectType::ObjectBinaryOutput => 4,
+            ObjectType::ObjectBinaryValue => 5,
+            ObjectType::ObjectCalendar => 6,
+            ObjectType::ObjectCommand => 7,
+            ObjectType::ObjectDevice => 8,
+            ObjectType::ObjectEventEnrollment => 9,
+            ObjectType::ObjectFile => 10,
+            ObjectType::ObjectGroup => 11,
+            ObjectType::ObjectLoop => 12,
+            ObjectType::ObjectMultiStateInput => 13,
+            ObjectType::ObjectMultiStateOutput => 14,
+            ObjectType::ObjectNotificationClass => 15,
+            ObjectType::ObjectProgram => 16,
+            ObjectType::ObjectSchedule => 17,
+            ObjectType::ObjectAveraging => 18,
+            ObjectType::ObjectMultiStateValue => 19,
+            ObjectType::ObjectTrendlog => 20,
+            ObjectType::ObjectLifeSafetyPoint => 21,
+            ObjectType::ObjectLifeSafetyZone => 22,
+            ObjectType::ObjectAccumulator => 23,
+            ObjectType::ObjectPulseConverter => 24,
+            ObjectType::ObjectEventLog => 25,
+            ObjectType::ObjectGlobalGroup => 26,
+            ObjectType::ObjectTrendLogMultiple => 27,
+            ObjectType::ObjectLoadControl => 28,
+            ObjectType::ObjectStructuredView => 29,
+            ObjectType::ObjectAccessDoor => 30,
+            ObjectType::ObjectTimer => 31,
+            ObjectType::ObjectAccessCredential => 32,
+            ObjectType::ObjectAccessPoint => 33,
+            ObjectType::ObjectAccessRights => 34,
+            ObjectType::ObjectAccessUser => 35,
+            ObjectType::ObjectAccessZone => 36,
+            ObjectType::ObjectCredentialDataInput => 37,
+            ObjectType::ObjectNetworkSecurity => 38,
+            ObjectType::ObjectBitstringValue => 39,
+            ObjectType::ObjectCharacterstringValue => 40,
+            ObjectType::ObjectDatePatternValue => 41,
+            ObjectType::ObjectDateValue => 42,
+            ObjectType::ObjectDatetimePatternValue => 43,
+            ObjectType::ObjectDatetimeValue => 44,
+            ObjectType::ObjectIntegerValue => 45,
+            ObjectType::ObjectLargeAnalogValue => 46,
+            ObjectType::ObjectOctetstringValue => 47,
+            ObjectType::ObjectPositiveIntegerValue => 48,
+            ObjectType::ObjectTimePatternValue => 49,
+            ObjectType::ObjectTimeValue => 50,
+            ObjectType::ObjectNotificationForwarder => 51,
+            ObjectType::ObjectAlertEnrollment => 52,
+            ObjectType::ObjectChannel => 53,
+            ObjectType::ObjectLightingOutput => 54,
+            ObjectType::ObjectBinaryLightingOutput => 55,
+            ObjectType::ObjectNetworkPort => 56,
+            ObjectType::Reserved(x) => x as u32,
+            ObjectType::Proprietary(x) => x as u32,
+            ObjectType::Invalid => 1024,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        io::{Reader, Writer},
+        spec::{BACNET_MAX_INSTANCE, UNCONFIGURED_DEVICE_INSTANCE},
+    };
+
+    use super::{ObjectId, ObjectType};
+
+    #[test]
+    fn object_type_round_trip() {
+        for value in 0..=1023u16 {
+            let object_type = ObjectType::from(value);
+            assert_ne!(object_type, ObjectType::Invalid);
+            assert_eq!(u32::from(object_type), value as u32);
+        }
+        assert_eq!(ObjectType::from(8), ObjectType::ObjectDevice);
+        assert_eq!(ObjectType::from(100), ObjectType::Reserved(100));
+        assert_eq!(ObjectType::from(1024), ObjectType::Invalid);
+        assert_eq!(ObjectType::try_from(1024u32), Err(1024));
+    }
+
+    #[test]
+    fn proprietary_object_id_round_trip() {
+        // vendor object type 513, instance 7
+        let buf = [0x80, 0x40, 0x00, 0x07];
+        let mut reader = Reader::new_with_len(buf.len());
+        let object_id = ObjectId::decode(4, &mut reader, &buf).unwrap();
+        assert_eq!(object_id.object_type, ObjectType::Proprietary(513));
+        assert_eq!(object_id.instance(), 7);
+
+        let mut out = [0; 4];
+        let mut writer = Writer::new(&mut out);
+        object_id.encode(&mut writer);
+        assert_eq!(out, buf);
+    }
+
+    #[test]
+    fn instance_is_22_bits() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, u32::MAX);
+        assert_eq!(object_id.instance(), BACNET_MAX_INSTANCE);
+    }
+
+    #[test]
+    fn unconfigured_device() {
+        let unconfigured = ObjectId::new(ObjectType::ObjectDevice, UNCONFIGURED_DEVICE_INSTANCE);
+        assert!(unconfigured.is_unconfigured_device());
+        assert!(!ObjectId::new(ObjectType::ObjectDevice, 20088).is_unconfigured_device());
+        assert!(
+            !ObjectId::new(ObjectType::ObjectAnalogInput, UNCONFIGURED_DEVICE_INSTANCE)
+                .is_unconfigured_device()
+        );
+    }
+}
@@ -3,6 +3,7 @@ use crate::common::error::Error;
 pub const BACNET_MAX_OBJECT: u32 = 0x3FF;
 pub const BACNET_INSTANCE_BITS: u32 = 22;
 pub const BACNET_MAX_INSTANCE: u32 = 0x3FFFFF;
+pub const UNCONFIGURED_DEVICE_INSTANCE: u32 = BACNET_MAX_INSTANCE; // device has not been commissioned yet
 pub const MAX_BITSTRING_BYTES: u32 = 15;
 pub const BACNET_ARRAY_ALL: u32 = 0xFFFFFFFF;
 pub const BACNET_NO_PRIORITY: u32 = 0;
@@ -110,6 +111,22 @@ impl TryFrom<u32> for ErrorClass {
     }
 }
 
+impl From<&ErrorClass> for u32 {
+    fn from(value: &ErrorClass) -> Self {
+        match value {
+            ErrorClass::Device => 0,
+            ErrorClass::Object => 1,
+            ErrorClass::Property => 2,
+            ErrorClass::Resources => 3,
+            ErrorClass::Security => 4,
+            ErrorClass::Services => 5,
+            ErrorClass::Vt => 6,
+            ErrorClass::Communication => 7,
+            ErrorClass::Proprietary(x) => *x as u32,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -564,9 +581,226 @@ impl TryFrom<u32> for ErrorCode {
     }
 }
 
+impl From<&ErrorCode> for u32 {
+    fn from(value: &ErrorCode) -> Self {
+        match value {
+            ErrorCode::Other => 0,
+            ErrorCode::DeviceBusy => 3,
+            ErrorCode::ConfigurationInProgress => 2,
+            ErrorCode::OperationalProblem => 25,
+            ErrorCode::DynamicCreationNotSupported => 4,
+            ErrorCode::NoObjectsOfSpecifiedType => 17,
+            ErrorCode::ObjectDeletionNotPermitted => 23,
+            ErrorCode::ObjectIdentifierAlreadyExists => 24,
+            ErrorCode::ReadAccessDenied => 27,
+            ErrorCode::UnknownObject => 31,
+            ErrorCode::UnsupportedObjectType => 36,
+            ErrorCode::CharacterSetNotSupported => 41,
+            ErrorCode::DatatypeNotSupported => 47,
+            ErrorCode::InconsistentSelectionCriterion => 8,
+            ErrorCode::InvalidArrayIndex => 42,
+            ErrorCode::InvalidDataType => 9,
+            ErrorCode::NotCovProperty => 44,
+            ErrorCode::OptionalFunctionalityNotSupported => 45,
+            ErrorCode::PropertyIsNotAnArray => 50,
+            ErrorCode::UnknownProperty => 32,
+            ErrorCode::ValueOutOfRange => 37,
+            ErrorCode::WriteAccessDenied => 40,
+            ErrorCode::NoSpaceForObject => 18,
+            ErrorCode::NoSpaceToAddListElement => 19,
+            ErrorCode::NoSpaceToWriteProperty => 20,
+            ErrorCode::AuthenticationFailed => 1,
+            ErrorCode::IncompatibleSecurityLevels => 6,
+            ErrorCode::InvalidOperatorName => 12,
+            ErrorCode::KeyGenerationError => 15,
+            ErrorCode::PasswordFailure => 26,
+            ErrorCode::SecurityNotSupported => 28,
+            ErrorCode::Timeout => 30,
+            ErrorCode::CovSubscriptionFailed => 43,
+            ErrorCode::DuplicateName => 48,
+            ErrorCode::DuplicateObjectId => 49,
+            ErrorCode::FileAccessDenied => 5,
+            ErrorCode::InconsistentParameters => 7,
+            ErrorCode::InvalidConfigurationData => 46,
+            ErrorCode::InvalidFileAccessMethod => 10,
+            ErrorCode::InvalidFileStartPosition => 11,
+            ErrorCode::InvalidParameterDataType => 13,
+            ErrorCode::InvalidTimeStamp => 14,
+            ErrorCode::MissingRequiredParameter => 16,
+            ErrorCode::PropertyIsNotAList => 22,
+            ErrorCode::ServiceRequestDenied => 29,
+            ErrorCode::UnknownVtClass => 34,
+            ErrorCode::UnknownVtSession => 35,
+            ErrorCode::NoVtSessionsAvailable => 21,
+            ErrorCode::VtSessionAlreadyClosed => 38,
+            ErrorCode::VtSessionTerminationFailure => 39,
+            ErrorCode::Reserved1 => 33,
+            ErrorCode::AbortBufferOverflow => 51,
+            ErrorCode::AbortInvalidApduInThisState => 52,
+            ErrorCode::AbortPreemptedByHigherPriorityTask => 53,
+            ErrorCode::AbortSegmentationNotSupported => 54,
+            ErrorCode::AbortProprietary => 55,
+            ErrorCode::AbortOther => 56,
+            ErrorCode::InvalidTag => 57,
+            ErrorCode::NetworkDown => 58,
+            ErrorCode::RejectBufferOverflow => 59,
+            ErrorCode::RejectInconsistentParameters => 60,
+            ErrorCode::RejectInvalidParameterDataType => 61,
+            ErrorCode::RejectInvalidTag => 62,
+            ErrorCode::RejectMissingRequiredParameter => 63,
+            ErrorCode::RejectParameterOutOfRange => 64,
+            ErrorCode::RejectTooManyArguments => 65,
+            ErrorCode::RejectUndefinedEnumeration => 66,
+            ErrorCode::RejectUnrecognizedService => 67,
+            ErrorCode::RejectProprietary => 68,
+            ErrorCode::RejectOther => 69,
+            ErrorCode::UnknownDevice => 70,
+            ErrorCode::UnknownRoute => 71,
+            ErrorCode::ValueNotInitialized => 72,
+            ErrorCode::InvalidEventState => 73,
+            ErrorCode::NoAlarmConfigured => 74,
+            ErrorCode::LogBufferFull => 75,
+            ErrorCode::LoggedValuePurged => 76,
+            ErrorCode::NoPropertySpecified => 77,
+            ErrorCode::NotConfiguredForTriggeredLogging => 78,
+            ErrorCode::UnknownSubscription => 79,
+            ErrorCode::ParameterOutOfRange => 80,
+            ErrorCode::ListElementNotFound => 81,
+            ErrorCode::Busy => 82,
+            ErrorCode::CommunicationDisabled => 83,
+            ErrorCode::Success => 84,
+            ErrorCode::AccessDenied => 85,
+            ErrorCode::BadDestinationAddress => 86,
+            ErrorCode::BadDestinationDeviceId => 87,
+            ErrorCode::BadSignature => 88,
+            ErrorCode::BadSourceAddress => 89,
+            ErrorCode::BadTimestamp => 90,
+            ErrorCode::CannotUseKey => 91,
+            ErrorCode::CannotVerifyMessageId => 92,
+            ErrorCode::CorrectKeyRevision => 93,
+            ErrorCode::DestinationDeviceIdRequired => 94,
+            ErrorCode::DuplicateMessage => 95,
+            ErrorCode::EncryptionNotConfigured => 96,
+            ErrorCode::EncryptionRequired => 97,
+            ErrorCode::IncorrectKey => 98,
+            ErrorCode::InvalidKeyData => 99,
+            ErrorCode::KeyUpdateInProgress => 100,
+            ErrorCode::MalformedMessage => 101,
+            ErrorCode::NotKeyServer => 102,
+            ErrorCode::SecurityNotConfigured => 103,
+            ErrorCode::SourceSecurityRequired => 104,
+            ErrorCode::TooManyKeys => 105,
+            ErrorCode::UnknownAuthenticationType => 106,
+            ErrorCode::UnknownKey => 107,
+            ErrorCode::UnknownKeyRevision => 108,
+            ErrorCode::UnknownSourceMessage => 109,
+            ErrorCode::NotRouterToDnet => 110,
+            ErrorCode::RouterBusy => 111,
+            ErrorCode::UnknownNetworkMessage => 112,
+            ErrorCode::MessageTooLong => 113,
+            ErrorCode::SecurityError => 114,
+            ErrorCode::AddressingError => 115,
+            ErrorCode::WriteBdtFailed => 116,
+            ErrorCode::ReadBdtFailed => 117,
+            ErrorCode::RegisterForeignDeviceFailed => 118,
+            ErrorCode::ReadFdtFailed => 119,
+            ErrorCode::DeleteFdtEntryFailed => 120,
+            ErrorCode::DistributeBroadcastFailed => 121,
+            ErrorCode::UnknownFileSize => 122,
+            ErrorCode::AbortApduTooLong => 123,
+            ErrorCode::AbortApplicationExceededReplyTime => 124,
+            ErrorCode::AbortOutOfResources => 125,
+            ErrorCode::AbortTsmTimeout => 126,
+            ErrorCode::AbortWindowSizeOutOfRange => 127,
+            ErrorCode::FileFull => 128,
+            ErrorCode::InconsistentConfiguration => 129,
+            ErrorCode::InconsistentObjectType => 130,
+            ErrorCode::InternalError => 131,
+            ErrorCode::NotConfigured => 132,
+            ErrorCode::OutOfMemory => 133,
+            ErrorCode::ValueTooLong => 134,
+            ErrorCode::AbortInsufficientSecurity => 135,
+            ErrorCode::AbortSecurityError => 136,
+            ErrorCode::DuplicateEntry => 137,
+            ErrorCode::InvalidValueInThisState => 138,
+            ErrorCode::InvalidOperationInThisState => 139,
+            ErrorCode::ListItemNotNumbered => 140,
+            ErrorCode::ListItemNotTimestamped => 141,
+            ErrorCode::InvalidDataEncoding => 142,
+            ErrorCode::BvlcFunctionUnknown => 143,
+            ErrorCode::BvlcProprietaryFunctionUnknown => 144,
+            ErrorCode::HeaderEncodingError => 145,
+            ErrorCode::HeaderNotUnderstood => 146,
+            ErrorCode::MessageIncomplete => 147,
+            ErrorCode::NotABacnetScHub => 148,
+            ErrorCode::PayloadExpected => 149,
+            ErrorCode::UnexpectedData => 150,
+            ErrorCode::NodeDuplicateVmac => 151,
+            ErrorCode::HttpUnexpectedResponseCode => 152,
+            ErrorCode::HttpNoUpgrade => 153,
+            ErrorCode::HttpResourceNotLocal => 154,
+            ErrorCode::HttpProxyAuthenticationFailed => 155,
+            ErrorCode::HttpResponseTimeout => 156,
+            ErrorCode::HttpResponseSyntaxError => 157,
+            ErrorCode::HttpResponseValueError => 158,
+            ErrorCode::HttpResponseMissingHeader => 159,
+            ErrorCode::HttpWebsocketHeaderError => 160,
+            ErrorCode::HttpUpgradeRequired => 161,
+            ErrorCode::HttpUpgradeError => 162,
+            ErrorCode::HttpTemporaryUnavailable => 163,
+            ErrorCode::HttpNotAServer => 164,
+            ErrorCode::HttpError => 165,
+            ErrorCode::WebsocketSchemeNotSupported => 166,
+            ErrorCode::WebsocketUnknownControlMessage => 167,
+            ErrorCode::WebsocketCloseError => 168,
+            ErrorCode::WebsocketClosedByPeer => 169,
+            ErrorCode::WebsocketEndpointLeaves => 170,
+            ErrorCode::WebsocketProtocolError => 171,
+            ErrorCode::WebsocketDataNotAccepted => 172,
+            ErrorCode::WebsocketClosedAbnormally => 173,
+            ErrorCode::WebsocketDataInconsistent => 174,
+            ErrorCode::WebsocketDataAgainstPolicy => 175,
+            ErrorCode::WebsocketFrameTooLong => 176,
+            ErrorCode::WebsocketExtensionMissing => 177,
+            ErrorCode::WebsocketRequestUnavailable => 178,
+            ErrorCode::WebsocketError => 179,
+            ErrorCode::TlsClientCertificateError => 180,
+            ErrorCode::TlsServerCertificateError => 181,
+            ErrorCode::TlsClientAuthenticationFailed => 182,
+            ErrorCode::TlsServerAuthenticationFailed => 183,
+            ErrorCode::TlsClientCertificateExpired => 184,
+            ErrorCode::TlsServerCertificateExpired => 185,
+            ErrorCode::TlsClientCertificateRevoked => 186,
+            ErrorCode::TlsServerCertificateRevoked => 187,
+            ErrorCode::TlsError => 188,
+            ErrorCode::DnsUnavailable => 189,
+            ErrorCode::DnsNameResolutionFailed => 190,
+            ErrorCode::DnsResolverFailure => 191,
+            ErrorCode::DnsError => 192,
+            ErrorCode::TcpConnectTimeout => 193,
+            ErrorCode::TcpConnectionRefused => 194,
+            ErrorCode::TcpClosedByLocal => 195,
+            ErrorCode::TcpClosedOther => 196,
+            ErrorCode::TcpError => 197,
+            ErrorCode::IpAddressNotReachable => 198,
+            ErrorCode::IpError => 199,
+            ErrorCode::CertificateExpired => 200,
+            ErrorCode::CertificateInvalid => 201,
+            ErrorCode::CertificateMalformed => 202,
+            ErrorCode::CertificateRevoked => 203,
+            ErrorCode::UnknownSecurityKey => 204,
+            ErrorCode::ReferencedPortInError => 205,
+            ErrorCode::Proprietary(x) => *x as u32,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-#[cfg_attr(feature = "serde", derive(serde_repr::Serialize_repr, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_repr::Serialize_repr, serde_repr::Deserialize_repr)
+)]
 #[repr(u16)]
 pub enum EngineeringUnits {
     // Enumerated values 0-255 are reserved for definition by ASHRAE.
@@ -1353,7 +1587,7 @@ pub enum StatusFlags {
     OutOfService = 0b0001_0000,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Status {
@@ -1365,6 +1599,29 @@ impl Status {
         Self { inner }
     }
 
+    // e.g. the Status_Flags of an object in a server response
+    pub const fn from_flags(
+        in_alarm: bool,
+        fault: bool,
+        overridden: bool,
+        out_of_service: bool,
+    ) -> Self {
+        let mut inner = 0;
+        if in_alarm {
+            inner |= StatusFlags::InAlarm as u8;
+        }
+        if fault {
+            inner |= StatusFlags::Fault as u8;
+        }
+        if overridden {
+            inner |= StatusFlags::Overridden as u8;
+        }
+        if out_of_service {
+            inner |= StatusFlags::OutOfService as u8;
+        }
+        Self { inner }
+    }
+
     pub const fn in_alarm(&self) -> bool {
         self.inner & StatusFlags::InAlarm as u8 == StatusFlags::InAlarm as u8
     }
@@ -1407,6 +1664,40 @@ impl LogBufferResult {
     }
 }
 
+#[repr(u8)]
+pub enum EventTransitionFlags {
+    ToOffnormal = 0b1000_0000,
+    ToFault = 0b0100_0000,
+    ToNormal = 0b0010_0000,
+}
+
+// BACnetEventTransitionBits (e.g. the Acked_Transitions and Event_Enable properties)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventTransitionBits {
+    pub inner: u8,
+}
+
+impl EventTransitionBits {
+    pub fn new(inner: u8) -> Self {
+        Self { inner }
+    }
+
+    pub const fn to_offnormal(&self) -> bool {
+        self.inner & EventTransitionFlags::ToOffnormal as u8
+            == EventTransitionFlags::ToOffnormal as u8
+    }
+
+    pub const fn to_fault(&self) -> bool {
+        self.inner & EventTransitionFlags::ToFault as u8 == EventTransitionFlags::ToFault as u8
+    }
+
+    pub const fn to_normal(&self) -> bool {
+        self.inner & EventTransitionFlags::ToNormal as u8 == EventTransitionFlags::ToNormal as u8
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -1435,6 +1726,7 @@ pub enum EventState {
     OffNormal = 2,
     HighLimit = 3,
     LowLimit = 4,
+    LifeSafetyAlarm = 5,
 }
 
 impl TryFrom<u32> for EventState {
@@ -1447,11 +1739,101 @@ impl TryFrom<u32> for EventState {
             2 => Ok(Self::OffNormal),
             3 => Ok(Self::HighLimit),
             4 => Ok(Self::LowLimit),
+            5 => Ok(Self::LifeSafetyAlarm),
+            x => Err(x),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u16)]
+pub enum EventType {
+    ChangeOfBitstring = 0,
+    ChangeOfState = 1,
+    ChangeOfValue = 2,
+    CommandFailure = 3,
+    FloatingLimit = 4,
+    OutOfRange = 5,
+    ChangeOfLifeSafety = 8,
+    Extended = 9,
+    BufferReady = 10,
+    UnsignedRange = 11,
+    AccessEvent = 13,
+    DoubleOutOfRange = 14,
+    SignedOutOfRange = 15,
+    UnsignedOutOfRange = 16,
+    ChangeOfCharacterstring = 17,
+    ChangeOfStatusFlags = 18,
+    ChangeOfReliability = 19,
+    None = 20,
+    ChangeOfDiscreteValue = 21,
+    ChangeOfTimer = 22,
+    // codes 64 and above
+    Proprietary(u16),
+}
+
+impl TryFrom<u32> for EventType {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::ChangeOfBitstring),
+            1 => Ok(Self::ChangeOfState),
+            2 => Ok(Self::ChangeOfValue),
+            3 => Ok(Self::CommandFailure),
+            4 => Ok(Self::FloatingLimit),
+            5 => Ok(Self::OutOfRange),
+            8 => Ok(Self::ChangeOfLifeSafety),
+            9 => Ok(Self::Extended),
+            10 => Ok(Self::BufferReady),
+            11 => Ok(Self::UnsignedRange),
+            13 => Ok(Self::AccessEvent),
+            14 => Ok(Self::DoubleOutOfRange),
+            15 => Ok(Self::SignedOutOfRange),
+            16 => Ok(Self::UnsignedOutOfRange),
+            17 => Ok(Self::ChangeOfCharacterstring),
+            18 => Ok(Self::ChangeOfStatusFlags),
+            19 => Ok(Self::ChangeOfReliability),
+            20 => Ok(Self::None),
+            21 => Ok(Self::ChangeOfDiscreteValue),
+            22 => Ok(Self::ChangeOfTimer),
+            // codes 64 and above
+            x if x > 63 && x <= u16::MAX as u32 => Ok(Self::Proprietary(x as u16)),
             x => Err(x),
         }
     }
 }
 
+impl From<&EventType> for u32 {
+    fn from(value: &EventType) -> Self {
+        match value {
+            EventType::ChangeOfBitstring => 0,
+            EventType::ChangeOfState => 1,
+            EventType::ChangeOfValue => 2,
+            EventType::CommandFailure => 3,
+            EventType::FloatingLimit => 4,
+            EventType::OutOfRange => 5,
+            EventType::ChangeOfLifeSafety => 8,
+            EventType::Extended => 9,
+            EventType::BufferReady => 10,
+            EventType::UnsignedRange => 11,
+            EventType::AccessEvent => 13,
+            EventType::DoubleOutOfRange => 14,
+            EventType::SignedOutOfRange => 15,
+            EventType::UnsignedOutOfRange => 16,
+            EventType::ChangeOfCharacterstring => 17,
+            EventType::ChangeOfStatusFlags => 18,
+            EventType::ChangeOfReliability => 19,
+            EventType::None => 20,
+            EventType::ChangeOfDiscreteValue => 21,
+            EventType::ChangeOfTimer => 22,
+            EventType::Proprietary(x) => *x as u32,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
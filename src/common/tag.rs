@@ -140,14 +140,16 @@ impl Tag {
             if self.value <= 253 {
                 buf[len] = self.value as u8;
                 len += 1;
-            } else if self.value < u16::MAX as u32 {
-                buf[len] = self.value as u8;
+            } else if self.value <= u16::MAX as u32 {
+                // tagged as u16
+                buf[len] = 254;
                 len += 1;
                 let tmp = u16::to_be_bytes(self.value as u16);
                 buf[len..len + tmp.len()].copy_from_slice(&tmp);
                 len += tmp.len();
             } else {
-                buf[len] = self.value as u8;
+                // tagged as u32
+                buf[len] = 255;
                 len += 1;
                 let tmp = u32::to_be_bytes(self.value);
                 buf[len..len + tmp.len()].copy_from_slice(&tmp);
@@ -236,19 +238,19 @@ fn decode_tag_number(reader: &mut Reader, buf: &[u8]) -> Result<(TagNumber, u8),
     let byte0 = reader.read_byte(buf)?;
 
     let value = if is_context_specific(byte0) {
-        // context specific tag num
-        if is_extended_tag_number(byte0) {
-            let num = reader.read_byte(buf)?;
-            (TagNumber::ContextSpecific(num), byte0)
+        // context specific tag num (numbers above 14 follow in the next byte)
+        let num = if is_extended_tag_number(byte0) {
+            reader.read_byte(buf)?
         } else {
-            let num = byte0 >> 4;
-            if is_opening_tag(byte0) {
-                (TagNumber::ContextSpecificOpening(num), 0)
-            } else if is_closing_tag(byte0) {
-                (TagNumber::ContextSpecificClosing(num), 0)
-            } else {
-                (TagNumber::ContextSpecific(num), byte0)
-            }
+            byte0 >> 4
+        };
+
+        if is_opening_tag(byte0) {
+            (TagNumber::ContextSpecificOpening(num), 0)
+        } else if is_closing_tag(byte0) {
+            (TagNumber::ContextSpecificClosing(num), 0)
+        } else {
+            (TagNumber::ContextSpecific(num), byte0)
         }
     } else {
         // application tag num
@@ -278,3 +280,58 @@ fn is_opening_tag(byte0: u8) -> bool {
 fn is_closing_tag(byte0: u8) -> bool {
     byte0 & 0x07 == 0x07
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::common::io::{Reader, Writer};
+
+    use super::{ApplicationTagNumber, Tag, TagNumber};
+
+    fn octet_string(len: u32) -> Tag {
+        Tag::new(
+            TagNumber::Application(ApplicationTagNumber::OctetString),
+            len,
+        )
+    }
+
+    fn round_trip(tag: Tag, expected: &[u8]) {
+        let mut buf = [0; 8];
+        let mut writer = Writer::new(&mut buf);
+        tag.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), expected);
+
+        let mut reader = Reader::new_with_len(expected.len());
+        let decoded = Tag::decode(&mut reader, expected).unwrap();
+        assert_eq!(decoded.number, tag.number);
+        assert_eq!(decoded.value, tag.value);
+        assert!(reader.eof());
+    }
+
+    #[test]
+    fn extended_tag_numbers() {
+        for num in [15, 100, 254] {
+            let tag = Tag::new(TagNumber::ContextSpecific(num), 2);
+            round_trip(tag, &[0xfa, num]);
+
+            // the extended value follows the extended tag number
+            let tag = Tag::new(TagNumber::ContextSpecific(num), 200);
+            round_trip(tag, &[0xfd, num, 0xc8]);
+            let tag = Tag::new(TagNumber::ContextSpecific(num), 300);
+            round_trip(tag, &[0xfd, num, 0xfe, 0x01, 0x2c]);
+
+            let tag = Tag::new(TagNumber::ContextSpecificOpening(num), 0);
+            round_trip(tag, &[0xfe, num]);
+            let tag = Tag::new(TagNumber::ContextSpecificClosing(num), 0);
+            round_trip(tag, &[0xff, num]);
+        }
+    }
+
+    #[test]
+    fn extended_values() {
+        round_trip(octet_string(253), &[0x65, 0xfd]);
+        round_trip(octet_string(254), &[0x65, 0xfe, 0x00, 0xfe]);
+        round_trip(octet_string(255), &[0x65, 0xfe, 0x00, 0xff]);
+        round_trip(octet_string(65535), &[0x65, 0xfe, 0xff, 0xff]);
+        round_trip(octet_string(70000), &[0x65, 0xff, 0x00, 0x01, 0x11, 0x70]);
+    }
+}
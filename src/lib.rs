@@ -27,3 +27,6 @@ pub mod simple;
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
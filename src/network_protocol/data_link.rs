@@ -1,18 +1,469 @@
 use crate::{
-    application_protocol::{application_pdu::ApplicationPdu, confirmed::ConfirmedRequest},
+    application_protocol::{
+        application_pdu::ApplicationPdu, confirmed::ConfirmedRequest,
+        unconfirmed::UnconfirmedRequest,
+    },
     common::{
         error::Error,
         io::{Reader, Writer},
     },
-    network_protocol::network_pdu::{MessagePriority, NetworkMessage, NetworkPdu},
+    network_protocol::network_pdu::{
+        DestinationAddress, MessagePriority, NetworkMessage, NetworkPdu,
+    },
 };
 
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
 // Bacnet Virtual Link Control
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct DataLink<'a> {
     pub function: DataLinkFunction,
-    pub npdu: Option<NetworkPdu<'a>>,
+    // replaces the npdu field of version 0.4, read the npdu with npdu() or into_npdu()
+    pub payload: DataLinkPayload<'a>,
+}
+
+// everything after the bvlc header, an npdu or a bvlc message but never both
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DataLinkPayload<'a> {
+    None, // e.g. a Read-Broadcast-Distribution-Table request
+    Npdu(NetworkPdu<'a>),
+    Bvlc(BvlcMessage<'a>),
+    // the npdu forwarded by a BBMD was originally sent from this address
+    ForwardedNpdu {
+        original_source: BipAddress,
+        npdu: NetworkPdu<'a>,
+    },
+}
+
+// the payload of the bvlc functions that are not npdus
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BvlcMessage<'a> {
+    Result(BvlcResultCode),
+    WriteBroadcastDistributionTable(BroadcastDistributionTable<'a>),
+    ReadBroadcastDistributionTableAck(BroadcastDistributionTable<'a>),
+    RegisterForeignDevice { ttl_seconds: u16 },
+    ReadForeignDeviceTableAck(ForeignDeviceTable<'a>),
+    DeleteForeignDeviceTableEntry { address: BipAddress },
+}
+
+impl<'a> BvlcMessage<'a> {
+    // each message is only carried by one bvll function
+    pub fn function(&self) -> DataLinkFunction {
+        match self {
+            Self::Result(_) => DataLinkFunction::Result,
+            Self::WriteBroadcastDistributionTable(_) => {
+                DataLinkFunction::WriteBroadcastDistributionTable
+            }
+            Self::ReadBroadcastDistributionTableAck(_) => {
+                DataLinkFunction::ReadBroadcastDistTableAck
+            }
+            Self::RegisterForeignDevice { .. } => DataLinkFunction::RegisterForeignDevice,
+            Self::ReadForeignDeviceTableAck(_) => DataLinkFunction::ReadForeignDeviceTableAck,
+            Self::DeleteForeignDeviceTableEntry { .. } => {
+                DataLinkFunction::DeleteForeignDeviceTableEntry
+            }
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Result(code) => writer.extend_from_slice(&u16::from(code).to_be_bytes()),
+            Self::WriteBroadcastDistributionTable(table)
+            | Self::ReadBroadcastDistributionTableAck(table) => table.encode(writer),
+            Self::RegisterForeignDevice { ttl_seconds } => {
+                writer.extend_from_slice(&ttl_seconds.to_be_bytes())
+            }
+            Self::ReadForeignDeviceTableAck(table) => table.encode(writer),
+            Self::DeleteForeignDeviceTableEntry { address } => address.encode(writer),
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(
+        function: &DataLinkFunction,
+        reader: &mut Reader,
+        buf: &'a [u8],
+    ) -> Result<Self, Error> {
+        match function {
+            DataLinkFunction::Result => {
+                let code = u16::from_be_bytes(reader.read_bytes(buf)?);
+                Ok(Self::Result(code.into()))
+            }
+            DataLinkFunction::WriteBroadcastDistributionTable => {
+                let table = BroadcastDistributionTable::decode(reader, buf)?;
+                Ok(Self::WriteBroadcastDistributionTable(table))
+            }
+            DataLinkFunction::ReadBroadcastDistTableAck => {
+                let table = BroadcastDistributionTable::decode(reader, buf)?;
+                Ok(Self::ReadBroadcastDistributionTableAck(table))
+            }
+            DataLinkFunction::RegisterForeignDevice => {
+                let ttl_seconds = u16::from_be_bytes(reader.read_bytes(buf)?);
+                Ok(Self::RegisterForeignDevice { ttl_seconds })
+            }
+            DataLinkFunction::ReadForeignDeviceTableAck => {
+                let table = ForeignDeviceTable::decode(reader, buf)?;
+                Ok(Self::ReadForeignDeviceTableAck(table))
+            }
+            DataLinkFunction::DeleteForeignDeviceTableEntry => {
+                let address = BipAddress::decode(reader, buf)?;
+                Ok(Self::DeleteForeignDeviceTableEntry { address })
+            }
+            _ => Err(Error::InvalidValue(
+                "bvll function does not carry a bvlc message",
+            )),
+        }
+    }
+}
+
+// a BACnet/IP address (B/IP)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BipAddress {
+    pub ip: [u8; 4],
+    pub port: u16,
+}
+
+impl BipAddress {
+    pub fn new(ip: [u8; 4], port: u16) -> Self {
+        Self { ip, port }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.extend_from_slice(&self.ip);
+        writer.extend_from_slice(&self.port.to_be_bytes());
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let ip = reader.read_bytes(buf)?;
+        let port = u16::from_be_bytes(reader.read_bytes(buf)?);
+        Ok(Self { ip, port })
+    }
+}
+
+// an entry in the broadcast distribution table (BDT) of a BBMD
+// broadcasts are forwarded to the address formed from the ip of the peer BBMD and its broadcast mask
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BdtEntry {
+    pub address: BipAddress,
+    pub broadcast_mask: [u8; 4],
+}
+
+impl BdtEntry {
+    pub fn new(address: BipAddress, broadcast_mask: [u8; 4]) -> Self {
+        Self {
+            address,
+            broadcast_mask,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        self.address.encode(writer);
+        writer.extend_from_slice(&self.broadcast_mask);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let address = BipAddress::decode(reader, buf)?;
+        let broadcast_mask = reader.read_bytes(buf)?;
+        Ok(Self {
+            address,
+            broadcast_mask,
+        })
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+const BDT_ENTRY_LEN: usize = 10;
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BroadcastDistributionTable<'a> {
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BroadcastDistributionTable<'a> {
+    pub entries: Vec<BdtEntry>,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> BroadcastDistributionTable<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new_from_buf(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(entries: Vec<BdtEntry>) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            entries,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        #[cfg(not(feature = "alloc"))]
+        writer.extend_from_slice(self.buf);
+
+        #[cfg(feature = "alloc")]
+        for entry in self.entries.iter() {
+            entry.encode(writer);
+        }
+    }
+
+    // the list of entries takes up the rest of the bvlc message
+    #[cfg(not(feature = "alloc"))]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let buf = reader.read_remaining(buf)?;
+        if !buf.len().is_multiple_of(BDT_ENTRY_LEN) {
+            return Err(Error::Length((
+                "broadcast distribution table is not a whole number of entries",
+                buf.len() as u32,
+            )));
+        }
+        Ok(Self::new_from_buf(buf))
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let mut entries = Vec::new();
+        while !reader.eof() {
+            let entry = BdtEntry::decode(reader, buf)?;
+            entries.push(entry);
+        }
+        Ok(Self::new(entries))
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ BroadcastDistributionTable<'a> {
+    type Item = Result<BdtEntry, Error>;
+    type IntoIter = BdtEntryIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        BdtEntryIter::new(self.buf)
+    }
+}
+
+pub struct BdtEntryIter<'a> {
+    reader: Reader,
+    buf: &'a [u8],
+}
+
+impl<'a> BdtEntryIter<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self {
+            reader: Reader::new_with_len(buf.len()),
+            buf,
+        }
+    }
+}
+
+impl<'a> Iterator for BdtEntryIter<'a> {
+    type Item = Result<BdtEntry, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.eof() {
+            return None;
+        }
+
+        Some(BdtEntry::decode(&mut self.reader, self.buf))
+    }
+}
+
+// an entry in the foreign device table (FDT) of a BBMD
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FdtEntry {
+    pub address: BipAddress,
+    // the time to live the foreign device registered with
+    pub ttl_seconds: u16,
+    // the time left before the BBMD purges the entry (includes a 30 second grace period)
+    pub remaining_seconds: u16,
+}
+
+impl FdtEntry {
+    pub fn new(address: BipAddress, ttl_seconds: u16, remaining_seconds: u16) -> Self {
+        Self {
+            address,
+            ttl_seconds,
+            remaining_seconds,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        self.address.encode(writer);
+        writer.extend_from_slice(&self.ttl_seconds.to_be_bytes());
+        writer.extend_from_slice(&self.remaining_seconds.to_be_bytes());
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let address = BipAddress::decode(reader, buf)?;
+        let ttl_seconds = u16::from_be_bytes(reader.read_bytes(buf)?);
+        let remaining_seconds = u16::from_be_bytes(reader.read_bytes(buf)?);
+        Ok(Self {
+            address,
+            ttl_seconds,
+            remaining_seconds,
+        })
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+const FDT_ENTRY_LEN: usize = 10;
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ForeignDeviceTable<'a> {
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ForeignDeviceTable<'a> {
+    pub entries: Vec<FdtEntry>,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> ForeignDeviceTable<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new_from_buf(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(entries: Vec<FdtEntry>) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            entries,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        #[cfg(not(feature = "alloc"))]
+        writer.extend_from_slice(self.buf);
+
+        #[cfg(feature = "alloc")]
+        for entry in self.entries.iter() {
+            entry.encode(writer);
+        }
+    }
+
+    // the list of entries takes up the rest of the bvlc message
+    #[cfg(not(feature = "alloc"))]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let buf = reader.read_remaining(buf)?;
+        if !buf.len().is_multiple_of(FDT_ENTRY_LEN) {
+            return Err(Error::Length((
+                "foreign device table is not a whole number of entries",
+                buf.len() as u32,
+            )));
+        }
+        Ok(Self::new_from_buf(buf))
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let mut entries = Vec::new();
+        while !reader.eof() {
+            let entry = FdtEntry::decode(reader, buf)?;
+            entries.push(entry);
+        }
+        Ok(Self::new(entries))
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ ForeignDeviceTable<'a> {
+    type Item = Result<FdtEntry, Error>;
+    type IntoIter = FdtEntryIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        FdtEntryIter::new(self.buf)
+    }
+}
+
+pub struct FdtEntryIter<'a> {
+    reader: Reader,
+    buf: &'a [u8],
+}
+
+impl<'a> FdtEntryIter<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self {
+            reader: Reader::new_with_len(buf.len()),
+            buf,
+        }
+    }
+}
+
+impl<'a> Iterator for FdtEntryIter<'a> {
+    type Item = Result<FdtEntry, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.eof() {
+            return None;
+        }
+
+        Some(FdtEntry::decode(&mut self.reader, self.buf))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BvlcResultCode {
+    SuccessfulCompletion,
+    WriteBroadcastDistributionTableNak,
+    ReadBroadcastDistributionTableNak,
+    RegisterForeignDeviceNak,
+    ReadForeignDeviceTableNak,
+    DeleteForeignDeviceTableEntryNak,
+    DistributeBroadcastToNetworkNak,
+    Reserved(u16),
+}
+
+impl From<u16> for BvlcResultCode {
+    fn from(value: u16) -> Self {
+        match value {
+            0x0000 => Self::SuccessfulCompletion,
+            0x0010 => Self::WriteBroadcastDistributionTableNak,
+            0x0020 => Self::ReadBroadcastDistributionTableNak,
+            0x0030 => Self::RegisterForeignDeviceNak,
+            0x0040 => Self::ReadForeignDeviceTableNak,
+            0x0050 => Self::DeleteForeignDeviceTableEntryNak,
+            0x0060 => Self::DistributeBroadcastToNetworkNak,
+            x => Self::Reserved(x),
+        }
+    }
+}
+
+impl From<&BvlcResultCode> for u16 {
+    fn from(value: &BvlcResultCode) -> Self {
+        match value {
+            BvlcResultCode::SuccessfulCompletion => 0x0000,
+            BvlcResultCode::WriteBroadcastDistributionTableNak => 0x0010,
+            BvlcResultCode::ReadBroadcastDistributionTableNak => 0x0020,
+            BvlcResultCode::RegisterForeignDeviceNak => 0x0030,
+            BvlcResultCode::ReadForeignDeviceTableNak => 0x0040,
+            BvlcResultCode::DeleteForeignDeviceTableEntryNak => 0x0050,
+            BvlcResultCode::DistributeBroadcastToNetworkNak => 0x0060,
+            BvlcResultCode::Reserved(x) => *x,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -62,7 +513,18 @@ impl<'a> DataLink<'a> {
     //    const BVLC_ORIGINAL_BROADCAST_NPDU: u8 = 11;
 
     pub fn new(function: DataLinkFunction, npdu: Option<NetworkPdu<'a>>) -> Self {
-        Self { function, npdu }
+        let payload = match npdu {
+            Some(npdu) => DataLinkPayload::Npdu(npdu),
+            None => DataLinkPayload::None,
+        };
+        Self { function, payload }
+    }
+
+    pub fn new_bvlc(message: BvlcMessage<'a>) -> Self {
+        Self {
+            function: message.function(),
+            payload: DataLinkPayload::Bvlc(message),
+        }
     }
 
     pub fn new_confirmed_req(req: ConfirmedRequest<'a>) -> Self {
@@ -72,23 +534,155 @@ impl<'a> DataLink<'a> {
         DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu))
     }
 
+    // sends the request to a device on a remote network (e.g. an MS/TP trunk) via the IP router for that network
+    // the datagram itself must still be sent to the router's IP address
+    pub fn new_routed_confirmed_req(req: ConfirmedRequest<'a>, dst: DestinationAddress) -> Self {
+        let apdu = ApplicationPdu::ConfirmedRequest(req);
+        let message = NetworkMessage::Apdu(apdu);
+        let npdu = NetworkPdu::new(None, Some(dst), true, MessagePriority::Normal, message);
+        DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu))
+    }
+
+    // broadcasts the request to every network (0xffff) e.g. for a WhoIs
+    pub fn new_broadcast_unconfirmed_req(req: UnconfirmedRequest<'a>) -> Self {
+        let apdu = ApplicationPdu::UnconfirmedRequest(req);
+        let dst = Some(DestinationAddress::new(0xffff, None));
+        let message = NetworkMessage::Apdu(apdu);
+        let npdu = NetworkPdu::new(None, dst, false, MessagePriority::Normal, message);
+        DataLink::new(DataLinkFunction::OriginalBroadcastNpdu, Some(npdu))
+    }
+
+    // a foreign device cannot broadcast on the remote subnet itself
+    // so it asks the BBMD it is registered with to broadcast the npdu for it
+    pub fn new_distribute_broadcast(npdu: NetworkPdu<'a>) -> Self {
+        DataLink::new(DataLinkFunction::DistributeBroadcastToNetwork, Some(npdu))
+    }
+
+    pub fn new_forwarded(original_source: BipAddress, npdu: NetworkPdu<'a>) -> Self {
+        Self {
+            function: DataLinkFunction::ForwardedNpdu,
+            payload: DataLinkPayload::ForwardedNpdu {
+                original_source,
+                npdu,
+            },
+        }
+    }
+
+    // registers this device with a BBMD so it receives broadcasts from outside its own subnet
+    // the registration must be renewed before the time to live expires
+    pub fn new_register_foreign_device(ttl_seconds: u16) -> Self {
+        Self::new_bvlc(BvlcMessage::RegisterForeignDevice { ttl_seconds })
+    }
+
+    // asks a BBMD for its broadcast distribution table
+    pub fn new_read_bdt() -> Self {
+        DataLink::new(DataLinkFunction::ReadBroadcastDistTable, None)
+    }
+
+    // replaces the broadcast distribution table of a BBMD
+    pub fn new_write_bdt(table: BroadcastDistributionTable<'a>) -> Self {
+        Self::new_bvlc(BvlcMessage::WriteBroadcastDistributionTable(table))
+    }
+
+    pub fn new_read_bdt_ack(table: BroadcastDistributionTable<'a>) -> Self {
+        Self::new_bvlc(BvlcMessage::ReadBroadcastDistributionTableAck(table))
+    }
+
+    // asks a BBMD for the foreign devices currently registered with it
+    pub fn new_read_fdt() -> Self {
+        DataLink::new(DataLinkFunction::ReadForeignDeviceTable, None)
+    }
+
+    pub fn new_read_fdt_ack(table: ForeignDeviceTable<'a>) -> Self {
+        Self::new_bvlc(BvlcMessage::ReadForeignDeviceTableAck(table))
+    }
+
+    // removes the registration of the foreign device at this address from a BBMD
+    pub fn new_delete_fdt_entry(address: BipAddress) -> Self {
+        Self::new_bvlc(BvlcMessage::DeleteForeignDeviceTableEntry { address })
+    }
+
+    pub fn new_result(result_code: BvlcResultCode) -> Self {
+        Self::new_bvlc(BvlcMessage::Result(result_code))
+    }
+
+    // the npdu carried by this message (including one forwarded by a BBMD)
+    pub fn npdu(&self) -> Option<&NetworkPdu<'a>> {
+        match &self.payload {
+            DataLinkPayload::Npdu(npdu) | DataLinkPayload::ForwardedNpdu { npdu, .. } => Some(npdu),
+            _ => None,
+        }
+    }
+
+    pub fn into_npdu(self) -> Option<NetworkPdu<'a>> {
+        match self.payload {
+            DataLinkPayload::Npdu(npdu) | DataLinkPayload::ForwardedNpdu { npdu, .. } => Some(npdu),
+            _ => None,
+        }
+    }
+
+    pub fn bvlc_message(&self) -> Option<&BvlcMessage<'a>> {
+        match &self.payload {
+            DataLinkPayload::Bvlc(message) => Some(message),
+            _ => None,
+        }
+    }
+
+    // the result code if this is a BVLC-Result (e.g. the reply to a foreign device registration)
+    pub fn result_code(&self) -> Option<&BvlcResultCode> {
+        match self.bvlc_message() {
+            Some(BvlcMessage::Result(code)) => Some(code),
+            _ => None,
+        }
+    }
+
+    // the table carried by a write request or a read ack
+    pub fn broadcast_distribution_table(&self) -> Option<&BroadcastDistributionTable<'a>> {
+        match self.bvlc_message() {
+            Some(BvlcMessage::WriteBroadcastDistributionTable(table))
+            | Some(BvlcMessage::ReadBroadcastDistributionTableAck(table)) => Some(table),
+            _ => None,
+        }
+    }
+
+    pub fn foreign_device_table(&self) -> Option<&ForeignDeviceTable<'a>> {
+        match self.bvlc_message() {
+            Some(BvlcMessage::ReadForeignDeviceTableAck(table)) => Some(table),
+            _ => None,
+        }
+    }
+
+    // the address a forwarded npdu was originally sent from (rather than the BBMD that forwarded it)
+    pub fn original_source(&self) -> Option<&BipAddress> {
+        match &self.payload {
+            DataLinkPayload::ForwardedNpdu {
+                original_source, ..
+            } => Some(original_source),
+            _ => None,
+        }
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
         writer.push(BVLL_TYPE_BACNET_IP);
         writer.push(self.function.clone() as u8);
-        match &self.function {
-            DataLinkFunction::OriginalBroadcastNpdu | DataLinkFunction::OriginalUnicastNpdu => {
-                writer.extend_from_slice(&[0, 0]); // length placeholder
-                self.npdu.as_ref().unwrap().encode(writer); // should be ok to unwrap here since it has already been checked
-                Self::update_len(writer);
+        writer.extend_from_slice(&[0, 0]); // length placeholder
+        match &self.payload {
+            DataLinkPayload::None => {}
+            DataLinkPayload::Npdu(npdu) => npdu.encode(writer),
+            DataLinkPayload::Bvlc(message) => message.encode(writer),
+            DataLinkPayload::ForwardedNpdu {
+                original_source,
+                npdu,
+            } => {
+                original_source.encode(writer);
+                npdu.encode(writer);
             }
-            _ => todo!(),
         }
+        Self::update_len(writer);
     }
 
     fn update_len(writer: &mut Writer) {
-        let len = writer.index as u16;
-        let src = len.to_be_bytes();
-        writer.buf[2..4].copy_from_slice(&src);
+        writer.patch_u16_at(2, writer.position() as u16);
     }
 
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
@@ -112,14 +706,395 @@ impl<'a> DataLink<'a> {
         }
         reader.set_len(len as usize);
 
-        let npdu = match function {
-            // see h_bbmd.c for all the types (only 2 are supported here)
-            DataLinkFunction::OriginalBroadcastNpdu | DataLinkFunction::OriginalUnicastNpdu => {
-                Some(NetworkPdu::decode(reader, buf)?)
+        let payload = match function {
+            // see h_bbmd.c for all the types
+            DataLinkFunction::OriginalBroadcastNpdu
+            | DataLinkFunction::OriginalUnicastNpdu
+            | DataLinkFunction::DistributeBroadcastToNetwork => {
+                DataLinkPayload::Npdu(NetworkPdu::decode(reader, buf)?)
             }
-            _ => None,
+            DataLinkFunction::ForwardedNpdu => {
+                let original_source = BipAddress::decode(reader, buf)?;
+                let npdu = NetworkPdu::decode(reader, buf)?;
+                DataLinkPayload::ForwardedNpdu {
+                    original_source,
+                    npdu,
+                }
+            }
+            DataLinkFunction::ReadBroadcastDistTable | DataLinkFunction::ReadForeignDeviceTable => {
+                DataLinkPayload::None
+            }
+            _ => DataLinkPayload::Bvlc(BvlcMessage::decode(&function, reader, buf)?),
         };
 
-        Ok(Self { function, npdu })
+        Ok(Self { function, payload })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu,
+            confirmed::{ConfirmedRequest, ConfirmedRequestService},
+            services::{read_property::ReadProperty, who_is::WhoIs},
+            unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+        network_protocol::network_pdu::{DestinationAddress, MacAddress, NetworkMessage},
+    };
+
+    use super::{BipAddress, BvlcMessage, BvlcResultCode, DataLink, DataLinkFunction};
+
+    // broadcast i_am from device 1234
+    const I_AM: [u8; 24] = [
+        0x81, 0x0b, 0x00, 0x18, 0x01, 0x20, 0xff, 0xff, 0x00, 0xff, 0x10, 0x00, 0xc4, 0x02, 0x00,
+        0x04, 0xd2, 0x22, 0x05, 0xc4, 0x91, 0x03, 0x21, 0x0f,
+    ];
+
+    // ReadProperty ack for Device:20088 Object_List[1]
+    const READ_PROPERTY_ACK: [u8; 25] = [
+        0x81, 0x0a, 0x00, 0x19, 0x01, 0x00, 0x30, 0x00, 0x0c, 0x0c, 0x02, 0x00, 0x4e, 0x78, 0x19,
+        0x4c, 0x29, 0x01, 0x3e, 0xc4, 0x02, 0x00, 0x4e, 0x78, 0x3f,
+    ];
+
+    // Error PDU for SubscribeCov: object / unknown-object
+    const ERROR: [u8; 13] = [
+        0x81, 0x0a, 0x00, 0x0d, 0x01, 0x00, 0x50, 0x01, 0x05, 0x91, 0x01, 0x91, 0x1f,
+    ];
+
+    fn decode_truncated(frame: &[u8], fix_len: bool) {
+        for len in 0..frame.len() {
+            let mut buf = [0; 32];
+            buf[..len].copy_from_slice(&frame[..len]);
+            if fix_len && len >= 4 {
+                // make the bvlc length agree with the truncated frame so the inner decoders are reached
+                buf[2..4].copy_from_slice(&(len as u16).to_be_bytes());
+            }
+            let buf = &buf[..len];
+
+            let mut reader = Reader::default();
+            let result = DataLink::decode(&mut reader, buf);
+            assert!(result.is_err(), "decoded {:02x?}", buf);
+        }
+
+        let mut reader = Reader::default();
+        assert!(DataLink::decode(&mut reader, frame).is_ok());
+    }
+
+    #[test]
+    fn truncated_frames_are_errors() {
+        for frame in [I_AM.as_slice(), &READ_PROPERTY_ACK, &ERROR] {
+            decode_truncated(frame, false);
+            decode_truncated(frame, true);
+        }
+    }
+
+    #[test]
+    fn register_foreign_device() {
+        let data_link = DataLink::new_register_foreign_device(300);
+        let mut buf = [0; 6];
+        let mut writer = Writer::new(&mut buf);
+        data_link.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), [0x81, 0x05, 0x00, 0x06, 0x01, 0x2c]);
+
+        let mut reader = Reader::default();
+        let decoded = DataLink::decode(&mut reader, &buf).unwrap();
+        assert!(matches!(
+            decoded.function,
+            DataLinkFunction::RegisterForeignDevice
+        ));
+        assert_eq!(
+            decoded.bvlc_message(),
+            Some(&BvlcMessage::RegisterForeignDevice { ttl_seconds: 300 })
+        );
+
+        // the BBMD refused the registration
+        let buf = [0x81, 0x00, 0x00, 0x06, 0x00, 0x30];
+        let mut reader = Reader::default();
+        let result = DataLink::decode(&mut reader, &buf).unwrap();
+        assert!(result.npdu().is_none());
+        assert_eq!(
+            result.result_code(),
+            Some(&BvlcResultCode::RegisterForeignDeviceNak)
+        );
+
+        let mut out = [0; 6];
+        let mut writer = Writer::new(&mut out);
+        DataLink::new_result(BvlcResultCode::RegisterForeignDeviceNak).encode(&mut writer);
+        assert_eq!(writer.to_bytes(), buf);
+    }
+
+    #[test]
+    fn distribute_broadcast_who_is() {
+        let who_is = UnconfirmedRequest::WhoIs(WhoIs::default());
+        let npdu = DataLink::new_broadcast_unconfirmed_req(who_is)
+            .into_npdu()
+            .unwrap();
+        let data_link = DataLink::new_distribute_broadcast(npdu);
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        data_link.encode(&mut writer);
+        assert_eq!(
+            writer.to_bytes(),
+            [0x81, 0x09, 0x00, 0x0c, 0x01, 0x20, 0xff, 0xff, 0x00, 0xff, 0x10, 0x08]
+        );
+
+        let buf = writer.to_bytes();
+        let mut reader = Reader::default();
+        let decoded = DataLink::decode(&mut reader, buf).unwrap();
+        assert!(matches!(
+            decoded.function,
+            DataLinkFunction::DistributeBroadcastToNetwork
+        ));
+        assert!(matches!(
+            decoded.into_npdu().unwrap().network_message,
+            NetworkMessage::Apdu(ApplicationPdu::UnconfirmedRequest(
+                UnconfirmedRequest::WhoIs(_)
+            ))
+        ));
+    }
+
+    #[test]
+    fn forwarded_i_am() {
+        // the broadcast I_AM forwarded by a BBMD, originally from 192.168.1.20:47808
+        let mut buf = [0; 30];
+        buf[..10].copy_from_slice(&[0x81, 0x04, 0x00, 0x1e, 192, 168, 1, 20, 0xba, 0xc0]);
+        buf[10..].copy_from_slice(&I_AM[4..]);
+
+        let mut reader = Reader::default();
+        let data_link = DataLink::decode(&mut reader, &buf).unwrap();
+        assert_eq!(
+            data_link.original_source(),
+            Some(&BipAddress::new([192, 168, 1, 20], 0xbac0))
+        );
+        let npdu = data_link.npdu().cloned().unwrap();
+        assert!(matches!(
+            npdu.network_message,
+            NetworkMessage::Apdu(ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::IAm(
+                _
+            )))
+        ));
+
+        let mut out = [0; 30];
+        let mut writer = Writer::new(&mut out);
+        DataLink::new_forwarded(*data_link.original_source().unwrap(), npdu).encode(&mut writer);
+        assert_eq!(writer.to_bytes(), buf);
+    }
+
+    #[test]
+    fn routed_request_to_mstp_device() {
+        // read the present value of AnalogInput:1 on MS/TP device 5 behind the router for network 2
+        let read_property = ReadProperty::new(
+            ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+            PropertyId::PropPresentValue,
+        );
+        let req = ConfirmedRequest::new(1, ConfirmedRequestService::ReadProperty(read_property));
+        let dst = DestinationAddress::new(2, Some(MacAddress::Mstp(5)));
+        let data_link = DataLink::new_routed_confirmed_req(req, dst);
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        data_link.encode(&mut writer);
+        let buf = writer.to_bytes();
+        assert_eq!(buf[4..11], [0x01, 0x24, 0x00, 0x02, 0x01, 0x05, 0xff]);
+
+        let mut reader = Reader::default();
+        let decoded = DataLink::decode(&mut reader, buf).unwrap();
+        let npdu = decoded.into_npdu().unwrap();
+        assert!(npdu.expect_reply);
+        assert!(npdu.src.is_none());
+        let dst = npdu.dst.unwrap();
+        assert_eq!(dst.network_address.net, 2);
+        assert_eq!(dst.network_address.addr, Some(MacAddress::Mstp(5)));
+        assert_eq!(dst.hop_count, 255);
+    }
+
+    #[test]
+    fn routed_reply_from_mstp_device() {
+        // the router adds the original source (network 2, MS/TP device 5) to the reply
+        let mut frame = [0; 29];
+        frame[..10].copy_from_slice(&[0x81, 0x0a, 0x00, 0x1d, 0x01, 0x08, 0x00, 0x02, 0x01, 0x05]);
+        frame[10..].copy_from_slice(&READ_PROPERTY_ACK[6..]);
+
+        let mut reader = Reader::default();
+        let data_link = DataLink::decode(&mut reader, &frame).unwrap();
+        let npdu = data_link.npdu().unwrap();
+        assert!(npdu.dst.is_none());
+        let src = npdu.src.as_ref().unwrap();
+        assert_eq!(src.net, 2);
+        assert_eq!(src.addr, Some(MacAddress::Mstp(5)));
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        data_link.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), frame);
+    }
+
+    #[test]
+    fn routed_reply_from_ipv6_device() {
+        // the original source is a BACnet/IPv6 device (16 byte address and port) on network 3
+        let mac = [
+            0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0xba, 0xc0,
+        ];
+        let mut frame = [0; 46];
+        frame[..9].copy_from_slice(&[0x81, 0x0a, 0x00, 0x2e, 0x01, 0x08, 0x00, 0x03, 0x12]);
+        frame[9..27].copy_from_slice(&mac);
+        frame[27..].copy_from_slice(&READ_PROPERTY_ACK[6..]);
+
+        let mut reader = Reader::default();
+        let data_link = DataLink::decode(&mut reader, &frame).unwrap();
+        let src = data_link.npdu().unwrap().src.as_ref().unwrap();
+        assert_eq!(src.net, 3);
+        match &src.addr {
+            Some(MacAddress::Other(addr)) => assert_eq!(addr.as_slice(), mac),
+            x => panic!("expected a raw mac address, got {:?}", x),
+        }
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        data_link.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), frame);
+    }
+
+    #[test]
+    fn read_bdt() {
+        let mut buf = [0; 8];
+        let mut writer = Writer::new(&mut buf);
+        DataLink::new_read_bdt().encode(&mut writer);
+        assert_eq!(writer.to_bytes(), [0x81, 0x02, 0x00, 0x04]);
+    }
+
+    #[test]
+    fn read_bdt_ack() {
+        // a BBMD with itself and one peer BBMD in its table
+        #[rustfmt::skip]
+        let buf = [
+            0x81, 0x03, 0x00, 0x18,
+            0xc0, 0xa8, 0x01, 0x0a, 0xba, 0xc0, 0xff, 0xff, 0xff, 0xff,
+            0x0a, 0x00, 0x00, 0x01, 0xba, 0xc0, 0xff, 0xff, 0xff, 0x00,
+        ];
+        let mut reader = Reader::default();
+        let data_link = DataLink::decode(&mut reader, &buf).unwrap();
+        assert!(data_link.npdu().is_none());
+        let table = data_link.broadcast_distribution_table().unwrap();
+
+        #[cfg(feature = "alloc")]
+        let mut entries = table.entries.clone().into_iter();
+        #[cfg(not(feature = "alloc"))]
+        let mut entries = table.into_iter().map(|x| x.unwrap());
+
+        let entry = entries.next().unwrap();
+        assert_eq!(entry.address, BipAddress::new([192, 168, 1, 10], 47808));
+        assert_eq!(entry.broadcast_mask, [255, 255, 255, 255]);
+        let entry = entries.next().unwrap();
+        assert_eq!(entry.address, BipAddress::new([10, 0, 0, 1], 47808));
+        assert_eq!(entry.broadcast_mask, [255, 255, 255, 0]);
+        assert!(entries.next().is_none());
+
+        let mut out = [0; 32];
+        let mut writer = Writer::new(&mut out);
+        data_link.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), buf);
+    }
+
+    #[test]
+    fn read_bdt_ack_with_partial_entry() {
+        let buf = [0x81, 0x03, 0x00, 0x08, 0xc0, 0xa8, 0x01, 0x0a];
+        let mut reader = Reader::default();
+        assert!(DataLink::decode(&mut reader, &buf).is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn write_bdt() {
+        use super::{BdtEntry, BroadcastDistributionTable};
+
+        let entry = BdtEntry::new(BipAddress::new([10, 0, 0, 1], 47808), [255, 255, 255, 255]);
+        let table = BroadcastDistributionTable::new(alloc::vec![entry]);
+        let data_link = DataLink::new_write_bdt(table);
+
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        data_link.encode(&mut writer);
+        #[rustfmt::skip]
+        let expected = [
+            0x81, 0x01, 0x00, 0x0e,
+            0x0a, 0x00, 0x00, 0x01, 0xba, 0xc0, 0xff, 0xff, 0xff, 0xff,
+        ];
+        assert_eq!(writer.to_bytes(), expected);
+
+        let mut reader = Reader::default();
+        let decoded = DataLink::decode(&mut reader, &expected).unwrap();
+        assert!(matches!(
+            decoded.function,
+            DataLinkFunction::WriteBroadcastDistributionTable
+        ));
+        assert_eq!(
+            decoded.broadcast_distribution_table().unwrap().entries,
+            [entry]
+        );
+    }
+
+    #[test]
+    fn read_fdt_ack() {
+        // captured from a BBMD with two registered foreign devices
+        #[rustfmt::skip]
+        let buf = [
+            0x81, 0x07, 0x00, 0x18,
+            0xc0, 0xa8, 0x02, 0x15, 0xba, 0xc0, 0x00, 0x3c, 0x00, 0x41,
+            0x0a, 0x01, 0x00, 0x07, 0xba, 0xc1, 0x01, 0x2c, 0x00, 0x1e,
+        ];
+        let mut reader = Reader::default();
+        let data_link = DataLink::decode(&mut reader, &buf).unwrap();
+        let table = data_link.foreign_device_table().unwrap();
+
+        #[cfg(feature = "alloc")]
+        let mut entries = table.entries.clone().into_iter();
+        #[cfg(not(feature = "alloc"))]
+        let mut entries = table.into_iter().map(|x| x.unwrap());
+
+        let entry = entries.next().unwrap();
+        assert_eq!(entry.address, BipAddress::new([192, 168, 2, 21], 47808));
+        assert_eq!(entry.ttl_seconds, 60);
+        assert_eq!(entry.remaining_seconds, 65);
+        let entry = entries.next().unwrap();
+        assert_eq!(entry.address, BipAddress::new([10, 1, 0, 7], 47809));
+        assert_eq!(entry.ttl_seconds, 300);
+        assert_eq!(entry.remaining_seconds, 30);
+        assert!(entries.next().is_none());
+
+        let mut out = [0; 32];
+        let mut writer = Writer::new(&mut out);
+        data_link.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), buf);
+
+        let mut writer = Writer::new(&mut out);
+        DataLink::new_read_fdt().encode(&mut writer);
+        assert_eq!(writer.to_bytes(), [0x81, 0x06, 0x00, 0x04]);
+    }
+
+    #[test]
+    fn delete_fdt_entry() {
+        let address = BipAddress::new([192, 168, 2, 21], 47808);
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        DataLink::new_delete_fdt_entry(address).encode(&mut writer);
+        let buf = writer.to_bytes();
+        assert_eq!(
+            buf,
+            [0x81, 0x08, 0x00, 0x0a, 0xc0, 0xa8, 0x02, 0x15, 0xba, 0xc0]
+        );
+
+        let mut reader = Reader::default();
+        let decoded = DataLink::decode(&mut reader, buf).unwrap();
+        assert_eq!(
+            decoded.bvlc_message(),
+            Some(&BvlcMessage::DeleteForeignDeviceTableEntry { address })
+        );
     }
 }
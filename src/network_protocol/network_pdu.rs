@@ -142,16 +142,16 @@ impl<'a> NetworkPdu<'a> {
         writer.push(Self::VERSION);
         writer.push(self.calculate_control());
 
-        if let Some(dst) = self.dst.as_ref() {
+        if let Some(dst) = self.routed_dst() {
             dst.network_address.encode(writer);
         }
 
-        if let Some(src) = self.src.as_ref() {
+        if let Some(src) = self.routed_src() {
             src.encode(writer);
         }
 
         // hop count comes after src
-        if let Some(dst) = self.dst.as_ref() {
+        if let Some(dst) = self.routed_dst() {
             writer.push(dst.hop_count);
         }
 
@@ -173,26 +173,14 @@ impl<'a> NetworkPdu<'a> {
             NetworkMessage::CustomMessageType(_) => ControlFlags::NetworkLayerMessage as u8,
         };
 
-        let has_destination = match self.dst.as_ref() {
-            Some(dst) => {
-                if dst.network_address.net > 0 {
-                    ControlFlags::HasDestination as u8
-                } else {
-                    0
-                }
-            }
-            None => 0,
+        let has_destination = match self.routed_dst() {
+            Some(_) => ControlFlags::HasDestination as u8,
+            _ => 0,
         };
 
-        let has_source = match self.src.as_ref() {
-            Some(src) => {
-                if src.net > 0 && src.net != 0xFFFF {
-                    ControlFlags::HasSource as u8
-                } else {
-                    0
-                }
-            }
-            None => 0,
+        let has_source = match self.routed_src() {
+            Some(_) => ControlFlags::HasSource as u8,
+            _ => 0,
         };
         let expecting_reply = if self.expect_reply {
             ControlFlags::ExpectingReply as u8
@@ -204,6 +192,28 @@ impl<'a> NetworkPdu<'a> {
         is_network_layer_message | has_destination | has_source | expecting_reply | message_priority
     }
 
+    // the destination is only encoded for a remote network (or 0xFFFF for a global broadcast)
+    fn routed_dst(&self) -> Option<&DestinationAddress> {
+        self.dst.as_ref().filter(|dst| dst.network_address.net > 0)
+    }
+
+    // the source is only encoded when a router forwards a message from a remote network
+    fn routed_src(&self) -> Option<&SourceAddress> {
+        self.src
+            .as_ref()
+            .filter(|src| src.net > 0 && src.net != 0xFFFF)
+    }
+
+    // The address of the device that sent this npdu.
+    // If the message was routed then the npdu carries the original source address,
+    // otherwise the device is on the local network and mac is the address the packet was received from
+    pub fn source_address(&self, mac: Addr) -> SourceAddress {
+        match &self.src {
+            Some(src) => src.clone(),
+            None => NetworkAddress::local(mac),
+        }
+    }
+
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
         // ignore version
@@ -261,7 +271,7 @@ impl<'a> NetworkPdu<'a> {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Addr {
     pub ipv4: [u8; 4],
@@ -269,14 +279,65 @@ pub struct Addr {
 }
 
 const IPV4_ADDR_LEN: u8 = 6;
+const MSTP_ADDR_LEN: u8 = 1;
+
+// the longest mac address we keep (a BACnet/IPv6 address and port)
+pub const MAX_MAC_LEN: usize = 18;
+
+// the mac address of a device on its own network
+// devices on a remote MS/TP network are reached through a router using their 1 byte MS/TP address
+// any other length (e.g. a BACnet/IPv6 or LonTalk address) is kept as raw bytes
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MacAddress {
+    Ip(Addr),
+    Mstp(u8),
+    Other(RawMacAddress),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RawMacAddress {
+    len: u8,
+    bytes: [u8; MAX_MAC_LEN],
+}
+
+impl RawMacAddress {
+    pub fn new(mac: &[u8]) -> Result<Self, Error> {
+        if mac.len() > MAX_MAC_LEN {
+            return Err(Error::Length((
+                "RawMacAddress mac len is too long",
+                mac.len() as u32,
+            )));
+        }
+
+        let mut bytes = [0; MAX_MAC_LEN];
+        bytes[..mac.len()].copy_from_slice(mac);
+        Ok(Self {
+            len: mac.len() as u8,
+            bytes,
+        })
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+}
+
+impl From<Addr> for MacAddress {
+    fn from(value: Addr) -> Self {
+        Self::Ip(value)
+    }
+}
 
 pub type SourceAddress = NetworkAddress;
 
-#[derive(Debug, Clone)]
+// a missing addr means a broadcast on network net
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct NetworkAddress {
     pub net: u16,
-    pub addr: Option<Addr>,
+    pub addr: Option<MacAddress>,
 }
 
 #[derive(Debug, Clone)]
@@ -287,7 +348,7 @@ pub struct DestinationAddress {
 }
 
 impl DestinationAddress {
-    pub fn new(net: u16, addr: Option<Addr>) -> Self {
+    pub fn new(net: u16, addr: Option<MacAddress>) -> Self {
         Self {
             network_address: NetworkAddress { net, addr },
             hop_count: 255,
@@ -296,14 +357,34 @@ impl DestinationAddress {
 }
 
 impl NetworkAddress {
+    pub fn new(net: u16, addr: Option<MacAddress>) -> Self {
+        Self { net, addr }
+    }
+
+    // a device on the local network (network number 0) reachable directly at addr
+    pub fn local(addr: Addr) -> Self {
+        Self {
+            net: 0,
+            addr: Some(MacAddress::Ip(addr)),
+        }
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
         writer.extend_from_slice(&self.net.to_be_bytes());
         match self.addr.as_ref() {
-            Some(addr) => {
+            Some(MacAddress::Ip(addr)) => {
                 writer.push(IPV4_ADDR_LEN);
                 writer.extend_from_slice(&addr.ipv4);
                 writer.extend_from_slice(&addr.port.to_be_bytes());
             }
+            Some(MacAddress::Mstp(addr)) => {
+                writer.push(MSTP_ADDR_LEN);
+                writer.push(*addr);
+            }
+            Some(MacAddress::Other(addr)) => {
+                writer.push(addr.len);
+                writer.extend_from_slice(addr.as_slice());
+            }
             None => writer.push(0),
         }
     }
@@ -318,14 +399,24 @@ impl NetworkAddress {
 
                 Ok(Self {
                     net,
-                    addr: Some(Addr { ipv4, port }),
+                    addr: Some(MacAddress::Ip(Addr { ipv4, port })),
+                })
+            }
+            MSTP_ADDR_LEN => {
+                let addr = reader.read_byte(buf)?;
+                Ok(Self {
+                    net,
+                    addr: Some(MacAddress::Mstp(addr)),
                 })
             }
             0 => Ok(Self { net, addr: None }),
-            x => Err(Error::Length((
-                "NetworkAddress decode ip len can only be 6 or 0",
-                x as u32,
-            ))),
+            len => {
+                let addr = RawMacAddress::new(reader.read_slice(len as usize, buf)?)?;
+                Ok(Self {
+                    net,
+                    addr: Some(MacAddress::Other(addr)),
+                })
+            }
         }
     }
 }
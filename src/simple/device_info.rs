@@ -0,0 +1,132 @@
+use crate::{
+    application_protocol::{
+        primitives::data_value::{ApplicationDataValue, CharacterString},
+        services::read_property_multiple::{
+            PropertyResult, PropertyValue, ReadPropertyMultipleAck,
+        },
+    },
+    common::{error::Error, property_id::PropertyId},
+};
+
+// the properties needed to identify a device, read together in a single ReadPropertyMultiple request
+pub const DEVICE_INFO_PROPERTIES: [PropertyId; 7] = [
+    PropertyId::PropObjectName,
+    PropertyId::PropVendorIdentifier,
+    PropertyId::PropVendorName,
+    PropertyId::PropModelName,
+    PropertyId::PropFirmwareRevision,
+    PropertyId::PropApplicationSoftwareVersion,
+    PropertyId::PropProtocolRevision,
+];
+
+// a property is None if the device returned an error for it (or did not return it at all)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeviceInfo<'a> {
+    pub object_name: Option<CharacterString<'a>>,
+    pub vendor_identifier: Option<u32>,
+    pub vendor_name: Option<CharacterString<'a>>,
+    pub model_name: Option<CharacterString<'a>>,
+    pub firmware_revision: Option<CharacterString<'a>>,
+    pub application_software_version: Option<CharacterString<'a>>,
+    pub protocol_revision: Option<u32>,
+}
+
+impl<'a> DeviceInfo<'a> {
+    pub fn from_ack(ack: &ReadPropertyMultipleAck<'a>) -> Result<Self, Error> {
+        let mut info = Self::default();
+
+        #[cfg(feature = "alloc")]
+        for object in ack.objects_with_results.iter() {
+            for result in object.property_results.iter() {
+                info.apply(result.clone());
+            }
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        for object in ack {
+            let object = object?;
+            for result in &object.property_results {
+                info.apply(result?);
+            }
+        }
+
+        Ok(info)
+    }
+
+    fn apply(&mut self, result: PropertyResult<'a>) {
+        let value = match result.value {
+            PropertyValue::PropValue(value) => value,
+            PropertyValue::PropObjectName(x) => {
+                #[cfg(feature = "alloc")]
+                let x = x.as_str();
+                self.object_name = Some(CharacterString::new(x));
+                return;
+            }
+            _ => return,
+        };
+
+        match (result.id, value) {
+            (PropertyId::PropVendorIdentifier, ApplicationDataValue::UnsignedInt(x)) => {
+                self.vendor_identifier = Some(x)
+            }
+            (PropertyId::PropVendorName, ApplicationDataValue::CharacterString(x)) => {
+                self.vendor_name = Some(x)
+            }
+            (PropertyId::PropModelName, ApplicationDataValue::CharacterString(x)) => {
+                self.model_name = Some(x)
+            }
+            (PropertyId::PropFirmwareRevision, ApplicationDataValue::CharacterString(x)) => {
+                self.firmware_revision = Some(x)
+            }
+            (
+                PropertyId::PropApplicationSoftwareVersion,
+                ApplicationDataValue::CharacterString(x),
+            ) => self.application_software_version = Some(x),
+            (PropertyId::PropProtocolRevision, ApplicationDataValue::UnsignedInt(x)) => {
+                self.protocol_revision = Some(x)
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::services::read_property_multiple::ReadPropertyMultipleAck,
+        common::io::Reader,
+    };
+
+    use super::DeviceInfo;
+
+    // device:20088 where the application software version could not be read
+    #[rustfmt::skip]
+    const DEVICE_INFO_ACK: [u8; 64] = [
+        0x0c, 0x02, 0x00, 0x4e, 0x78, 0x1e,
+        0x29, 0x4d, 0x4e, 0x74, 0x00, b'C', b't', b'l', 0x4f,
+        0x29, 0x78, 0x4e, 0x21, 0x05, 0x4f,
+        0x29, 0x79, 0x4e, 0x75, 0x05, 0x00, b'A', b'c', b'm', b'e', 0x4f,
+        0x29, 0x46, 0x4e, 0x73, 0x00, b'M', b'1', 0x4f,
+        0x29, 0x2c, 0x4e, 0x74, 0x00, b'1', b'.', b'0', 0x4f,
+        0x29, 0x0c, 0x5e, 0x91, 0x02, 0x91, 0x20, 0x5f,
+        0x29, 0x8b, 0x4e, 0x21, 0x0e, 0x4f,
+        0x1f,
+    ];
+
+    #[test]
+    fn parse_device_info_ack() {
+        let buf = &DEVICE_INFO_ACK;
+        let mut reader = Reader::new_with_len(buf.len());
+        let ack = ReadPropertyMultipleAck::decode(&mut reader, buf).unwrap();
+        let info = DeviceInfo::from_ack(&ack).unwrap();
+
+        assert_eq!(info.object_name.unwrap().as_str(), "Ctl");
+        assert_eq!(info.vendor_identifier, Some(5));
+        assert_eq!(info.vendor_name.unwrap().as_str(), "Acme");
+        assert_eq!(info.model_name.unwrap().as_str(), "M1");
+        assert_eq!(info.firmware_revision.unwrap().as_str(), "1.0");
+        assert!(info.application_software_version.is_none());
+        assert_eq!(info.protocol_revision, Some(14));
+    }
+}
@@ -1,10 +1,17 @@
 /// This module is meant to be a very basic way to interact with a BACnet IP network in a simple request / response manner
 /// It automatically links up requests with responses using an invoke_id which only really works when you send one request at a time.
+/// Messages received while waiting that are not a reply to the request (e.g. broadcasts or late replies) are skipped.
 /// If you intend to fire off many simultaneous requests then you should keep track of invoke_ids and handle congestion and packet ordering yourself.
 /// Your NetworkIo implementation is responsible for timeout detection for reads and writes.
 /// This is an async-first module but you can run it in a native blocking way if you like.
 ///   The `maybe_async` crate is used to avoid code duplication and completely stips away async code when the `is_sync` feature flag is set.
 /// If you are having trouble with the borrow checker try enabling the `alloc` feature to make BACnet objects fully owned
+pub mod device_info;
+#[cfg(feature = "transport")]
+pub mod transport;
+#[cfg(feature = "std")]
+pub mod udp;
+
 use core::{
     fmt::Debug,
     sync::atomic::{AtomicU8, Ordering},
@@ -16,28 +23,52 @@ use crate::{
     application_protocol::{
         application_pdu::ApplicationPdu,
         confirmed::{
-            ComplexAck, ComplexAckService, ConfirmedRequest, ConfirmedRequestService, SimpleAck,
+            Abort, AbortReason, ComplexAck, ComplexAckService, ConfirmedBacnetError,
+            ConfirmedRequest, ConfirmedRequestService, Reject, SimpleAck,
         },
+        primitives::data_value::ApplicationDataValue,
         services::{
+            acknowledge_alarm::AcknowledgeAlarm,
+            atomic_read_file::{AtomicReadFile, AtomicReadFileAck},
+            atomic_write_file::{AtomicWriteFile, AtomicWriteFileAck},
             change_of_value::{CovNotification, SubscribeCov},
+            create_object::CreateObject,
+            device_communication_control::DeviceCommunicationControl,
+            get_alarm_summary::GetAlarmSummaryAck,
+            get_event_information::{GetEventInformation, GetEventInformationAck},
             i_am::IAm,
-            read_property::{ReadProperty, ReadPropertyAck},
-            read_property_multiple::{ReadPropertyMultiple, ReadPropertyMultipleAck},
+            list_element::ListElementRequest,
+            read_property::{ReadProperty, ReadPropertyAck, ReadPropertyValue},
+            read_property_multiple::{
+                ReadPropertyMultiple, ReadPropertyMultipleAck, ReadPropertyMultipleObject,
+            },
             read_range::{ReadRange, ReadRangeAck},
-            time_synchronization::TimeSynchronization,
+            reinitialize_device::ReinitializeDevice,
+            time_synchronization::{TimeSynchronization, UtcTimeSynchronization},
             who_is::WhoIs,
             write_property::WriteProperty,
+            write_property_multiple::WritePropertyMultiple,
         },
         unconfirmed::UnconfirmedRequest,
     },
     common::{
         error::Error,
         io::{Reader, Writer},
+        object_id::ObjectId,
+        property_id::PropertyId,
+        spec::ErrorCode,
     },
     network_protocol::{
-        data_link::{DataLink, DataLinkFunction},
-        network_pdu::{DestinationAddress, MessagePriority, NetworkMessage, NetworkPdu},
+        data_link::{BvlcResultCode, DataLink, DataLinkFunction},
+        network_pdu::{MessagePriority, NetworkMessage, NetworkPdu},
     },
+    simple::device_info::{DeviceInfo, DEVICE_INFO_PROPERTIES},
+};
+
+#[cfg(feature = "alloc")]
+use {
+    crate::application_protocol::services::read_property_multiple::ObjectWithResults,
+    alloc::{vec, vec::Vec},
 };
 
 #[derive(Debug)]
@@ -77,6 +108,23 @@ where
     Io(T::Error),
     Codec(Error),
     InvokeId(InvokeIdError),
+    Abort(Abort),
+    Reject(Reject),               // the server could not parse the request
+    Server(ConfirmedBacnetError), // the server responded with an Error PDU
+    Bvlc(BvlcResultCode),         // the BBMD responded with a NAK
+}
+
+impl<T: NetworkIo> BacnetError<T> {
+    // the server could not fit its reply into a single apdu and does not support segmentation
+    pub fn is_segmentation_not_supported(&self) -> bool {
+        matches!(
+            self,
+            Self::Abort(Abort {
+                reason: AbortReason::SegmentationNotSupported,
+                ..
+            })
+        )
+    }
 }
 
 impl<T: NetworkIo> From<Error> for BacnetError<T> {
@@ -110,17 +158,14 @@ where
 
     #[maybe_async()]
     pub async fn who_is(&self, buf: &mut [u8]) -> Result<Option<IAm>, BacnetError<T>> {
-        let apdu = ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::WhoIs(WhoIs {}));
-        let dst = Some(DestinationAddress::new(0xffff, None));
-        let message = NetworkMessage::Apdu(apdu);
-        let npdu = NetworkPdu::new(None, dst, false, MessagePriority::Normal, message);
-        let data_link = DataLink::new(DataLinkFunction::OriginalBroadcastNpdu, Some(npdu));
+        let request = UnconfirmedRequest::WhoIs(WhoIs::new());
+        let data_link = DataLink::new_broadcast_unconfirmed_req(request);
 
         let mut writer = Writer::new(buf);
         data_link.encode(&mut writer);
 
         // send packet until we get a reply
-        let buffer = writer.to_bytes();
+        let buffer = writer.try_to_bytes()?;
 
         self.io.write(buffer).await.map_err(BacnetError::Io)?;
 
@@ -132,7 +177,7 @@ where
         let mut reader = Reader::default();
         let message = DataLink::decode(&mut reader, buf).map_err(BacnetError::Codec)?;
 
-        if let Some(npdu) = message.npdu {
+        if let Some(npdu) = message.into_npdu() {
             if let NetworkMessage::Apdu(ApplicationPdu::UnconfirmedRequest(
                 UnconfirmedRequest::IAm(iam),
             )) = npdu.network_message
@@ -144,6 +189,37 @@ where
         Ok(None)
     }
 
+    /// Registers with a BBMD as a foreign device so that broadcasts on its subnet are forwarded to us.
+    /// The registration must be renewed before `ttl_seconds` expires.
+    #[maybe_async()]
+    pub async fn register_foreign_device(
+        &self,
+        buf: &mut [u8],
+        ttl_seconds: u16,
+    ) -> Result<(), BacnetError<T>> {
+        let data_link = DataLink::new_register_foreign_device(ttl_seconds);
+        let mut writer = Writer::new(buf);
+        data_link.encode(&mut writer);
+        let buffer = writer.try_to_bytes()?;
+        self.io.write(buffer).await.map_err(BacnetError::Io)?;
+
+        // the BBMD replies with a BVLC-Result but other messages may arrive first
+        for _ in 0..MAX_UNRELATED_MESSAGES {
+            let n = self.io.read(buf).await.map_err(BacnetError::Io)?;
+            let mut reader = Reader::default();
+            let message = DataLink::decode(&mut reader, &buf[..n])?;
+            match message.result_code() {
+                Some(BvlcResultCode::SuccessfulCompletion) => return Ok(()),
+                Some(code) => return Err(BacnetError::Bvlc(code.clone())),
+                None => {}
+            }
+        }
+
+        Err(BacnetError::Codec(Error::ConvertDataLink(
+            "no BVLC-Result received for the foreign device registration",
+        )))
+    }
+
     #[maybe_async()]
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
     pub async fn read_property_multiple<'a>(
@@ -161,6 +237,65 @@ where
         }
     }
 
+    /// Same as `read_property_multiple` but falls back to smaller requests if the server aborts
+    /// with segmentation-not-supported. The request is first split into one request per object and,
+    /// if a single object is still too large, into one request per property. Results are merged
+    /// back together in the order they were requested.
+    #[cfg(feature = "alloc")]
+    #[maybe_async()]
+    pub async fn read_property_multiple_split(
+        &self,
+        buf: &mut [u8],
+        request: ReadPropertyMultiple<'_>,
+    ) -> Result<ReadPropertyMultipleAck<'static>, BacnetError<T>> {
+        let objects = request.objects.clone();
+        let abort = match self.read_property_multiple(buf, request).await {
+            Err(e) if e.is_segmentation_not_supported() => e,
+            result => return result,
+        };
+
+        // a single property of a single object cannot be split any further
+        if let [object] = objects.as_slice() {
+            if object.property_ids.len() <= 1 {
+                return Err(abort);
+            }
+        }
+
+        // no point resending the original request if it only contained one object
+        let split_objects = objects.len() > 1;
+
+        let mut objects_with_results = Vec::new();
+        for object in objects {
+            let object_id = object.object_id;
+            let property_ids = object.property_ids.clone();
+
+            if split_objects {
+                let request = ReadPropertyMultiple::new(vec![object]);
+                match self.read_property_multiple(buf, request).await {
+                    Ok(ack) => {
+                        objects_with_results.extend(ack.objects_with_results);
+                        continue;
+                    }
+                    Err(e) if e.is_segmentation_not_supported() && property_ids.len() > 1 => {}
+                    Err(e) => return Err(e),
+                }
+            }
+
+            let mut property_results = Vec::new();
+            for property_id in property_ids {
+                let object = ReadPropertyMultipleObject::new(object_id, vec![property_id]);
+                let request = ReadPropertyMultiple::new(vec![object]);
+                let ack = self.read_property_multiple(buf, request).await?;
+                for x in ack.objects_with_results {
+                    property_results.extend(x.property_results);
+                }
+            }
+            objects_with_results.push(ObjectWithResults::new(object_id, property_results));
+        }
+
+        Ok(ReadPropertyMultipleAck::new(objects_with_results))
+    }
+
     #[maybe_async()]
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
     pub async fn read_property<'a>(
@@ -178,6 +313,158 @@ where
         }
     }
 
+    /// Reads a single element of an array property (e.g. one slot of the priority array or one entry of the object list).
+    /// Index 0 returns the number of elements in the array.
+    #[maybe_async()]
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub async fn read_array_element<'a>(
+        &self,
+        buf: &'a mut [u8],
+        object_id: ObjectId,
+        property_id: PropertyId,
+        index: u32,
+    ) -> Result<ApplicationDataValue<'a>, BacnetError<T>> {
+        let mut request = ReadProperty::new(object_id, property_id);
+        request.array_index = index;
+        let ack = self.read_property(buf, request).await?;
+
+        if ack.array_index != index {
+            return Err(BacnetError::Codec(Error::InvalidValue(
+                "ReadPropertyAck array_index does not match the requested index",
+            )));
+        }
+        match ack.property_value {
+            ReadPropertyValue::ApplicationDataValue(value) => Ok(value),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "ReadPropertyAck value is not a single array element",
+            ))),
+        }
+    }
+
+    /// Reads the properties that identify a device (name, vendor, model, firmware etc.) in a single request
+    #[maybe_async()]
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub async fn read_device_info<'a>(
+        &self,
+        buf: &'a mut [u8],
+        device_id: ObjectId,
+    ) -> Result<DeviceInfo<'a>, BacnetError<T>> {
+        #[cfg(feature = "alloc")]
+        let request = ReadPropertyMultiple::new(vec![ReadPropertyMultipleObject::new(
+            device_id,
+            DEVICE_INFO_PROPERTIES.to_vec(),
+        )]);
+
+        #[cfg(not(feature = "alloc"))]
+        let objects = [ReadPropertyMultipleObject::new(
+            device_id,
+            &DEVICE_INFO_PROPERTIES,
+        )];
+        #[cfg(not(feature = "alloc"))]
+        let request = ReadPropertyMultiple::new(&objects);
+
+        let ack = self.read_property_multiple(buf, request).await?;
+        let info = DeviceInfo::from_ack(&ack)?;
+        Ok(info)
+    }
+
+    /// Reads the device info of many devices (e.g. the bindings collected from IAm replies).
+    /// Up to `DEVICE_SCAN_BATCH` requests are sent before waiting for any reply and replies are matched to devices
+    /// by invoke_id, so a device that does not reply only costs one read timeout per batch.
+    /// `select` is called before each request to point the io at the device (e.g. set the destination address)
+    /// and the io must then receive the replies of every device in the batch (e.g. an unconnected UDP socket).
+    /// A failure only affects the device it happened on, every device gets an entry in the result.
+    #[cfg(feature = "alloc")]
+    #[maybe_async()]
+    pub async fn read_device_infos<A>(
+        &self,
+        buf: &mut [u8],
+        devices: impl IntoIterator<Item = (ObjectId, A)>,
+        mut select: impl FnMut(&T, &A) -> Result<(), T::Error>,
+    ) -> Vec<(A, Result<DeviceInfo<'static>, BacnetError<T>>)> {
+        let mut results = Vec::new();
+        let mut devices = devices.into_iter().peekable();
+
+        while devices.peek().is_some() {
+            // (index into results, invoke_id) of the requests waiting for a reply
+            let mut pending = Vec::new();
+
+            for (device_id, addr) in devices.by_ref().take(DEVICE_SCAN_BATCH) {
+                let request = ReadPropertyMultiple::new(vec![ReadPropertyMultipleObject::new(
+                    device_id,
+                    DEVICE_INFO_PROPERTIES.to_vec(),
+                )]);
+                let service = ConfirmedRequestService::ReadPropertyMultiple(request);
+                let sent = match select(&self.io, &addr) {
+                    Ok(()) => self.send_confirmed(buf, service).await,
+                    Err(e) => Err(BacnetError::Io(e)),
+                };
+
+                match sent {
+                    Ok(invoke_id) => {
+                        pending.push((results.len(), invoke_id));
+                        results.push((addr, Err(no_reply_error())));
+                    }
+                    Err(e) => results.push((addr, Err(e))),
+                }
+            }
+
+            // a read error (e.g. a timeout) means no more replies are coming for this batch
+            for _ in 0..pending.len() * MAX_UNRELATED_MESSAGES {
+                if pending.is_empty() {
+                    break;
+                }
+                let Ok(n) = self.io.read(buf).await else {
+                    break;
+                };
+
+                let mut reader = Reader::default();
+                let Ok(message) = DataLink::decode(&mut reader, &buf[..n]) else {
+                    continue;
+                };
+                let Some(i) = reply_invoke_id(&message)
+                    .and_then(|invoke_id| pending.iter().position(|(_, x)| *x == invoke_id))
+                else {
+                    continue;
+                };
+
+                let (index, invoke_id) = pending.swap_remove(i);
+                results[index].1 = Self::decode_device_info(invoke_id, message);
+            }
+        }
+
+        results
+    }
+
+    #[cfg(feature = "alloc")]
+    fn decode_device_info(
+        invoke_id: u8,
+        message: DataLink<'static>,
+    ) -> Result<DeviceInfo<'static>, BacnetError<T>> {
+        Self::check_error_pdu(invoke_id, &message)?;
+        let ack: ComplexAck = message.try_into()?;
+        match ack.service {
+            ComplexAckService::ReadPropertyMultiple(ack) => Ok(DeviceInfo::from_ack(&ack)?),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService ReadPropertyMultipleAck",
+            ))),
+        }
+    }
+
+    /// Reads Object_Identifier and checks that the object reports the id that was requested.
+    /// A mismatch usually means the request was routed to the wrong device.
+    #[maybe_async()]
+    pub async fn verify_object_identifier(
+        &self,
+        buf: &mut [u8],
+        object_id: ObjectId,
+    ) -> Result<(), BacnetError<T>> {
+        let request = ReadProperty::new(object_id, PropertyId::PropObjectIdentifier);
+        let ack = self.read_property(buf, request).await?;
+        ack.verify_object_identifier(&object_id)?;
+        Ok(())
+    }
+
     #[maybe_async()]
     pub async fn subscribe_change_of_value(
         &self,
@@ -189,6 +476,27 @@ where
         Ok(())
     }
 
+    /// Cancels a subscription made with `subscribe_change_of_value`.
+    /// Cancelling a subscription that does not exist (e.g. it already expired) is not treated as an error.
+    #[maybe_async()]
+    pub async fn unsubscribe_change_of_value(
+        &self,
+        buf: &mut [u8],
+        process_id: u32,
+        object_id: ObjectId,
+    ) -> Result<(), BacnetError<T>> {
+        let service =
+            ConfirmedRequestService::SubscribeCov(SubscribeCov::cancel(process_id, object_id));
+        match self.send_and_receive_simple_ack(buf, service).await {
+            Ok(_) => Ok(()),
+            Err(BacnetError::Server(ConfirmedBacnetError {
+                error_code: ErrorCode::UnknownSubscription,
+                ..
+            })) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
     #[maybe_async()]
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
     pub async fn read_change_of_value<'a>(
@@ -199,7 +507,7 @@ where
         let mut reader = Reader::default();
         let message = DataLink::decode(&mut reader, &buf[..n])?;
 
-        if let Some(npdu) = message.npdu {
+        if let Some(npdu) = message.into_npdu() {
             if let NetworkMessage::Apdu(ApplicationPdu::UnconfirmedRequest(
                 UnconfirmedRequest::CovNotification(x),
             )) = npdu.network_message
@@ -228,8 +536,43 @@ where
         }
     }
 
+    /// Reads a chunk of a File object (call repeatedly until end_of_file is set).
+    #[maybe_async()]
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub async fn read_file<'a>(
+        &self,
+        buf: &'a mut [u8],
+        request: AtomicReadFile,
+    ) -> Result<AtomicReadFileAck<'a>, BacnetError<T>> {
+        let service = ConfirmedRequestService::AtomicReadFile(request);
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::AtomicReadFile(ack) => Ok(ack),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService AtomicReadFileAck",
+            ))),
+        }
+    }
+
+    /// Writes a chunk of a File object and returns where the data was written.
+    #[maybe_async()]
+    pub async fn write_file(
+        &self,
+        buf: &mut [u8],
+        request: AtomicWriteFile<'_>,
+    ) -> Result<AtomicWriteFileAck, BacnetError<T>> {
+        let service = ConfirmedRequestService::AtomicWriteFile(request);
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::AtomicWriteFile(ack) => Ok(ack),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService AtomicWriteFileAck",
+            ))),
+        }
+    }
+
     #[maybe_async()]
-    pub async fn write_property<'a>(
+    pub async fn write_property(
         &self,
         buf: &mut [u8],
         request: WriteProperty<'_>,
@@ -239,6 +582,135 @@ where
         Ok(())
     }
 
+    /// Writes several properties (possibly of several objects) in a single request.
+    /// The server either applies every write or reports the first one that failed.
+    #[maybe_async()]
+    pub async fn write_property_multiple(
+        &self,
+        buf: &mut [u8],
+        request: WritePropertyMultiple<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::WritePropertyMultiple(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
+
+    /// Creates an object and returns the id the server assigned to it.
+    #[maybe_async()]
+    pub async fn create_object(
+        &self,
+        buf: &mut [u8],
+        request: CreateObject<'_>,
+    ) -> Result<ObjectId, BacnetError<T>> {
+        let service = ConfirmedRequestService::CreateObject(request);
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::CreateObject(ack) => Ok(ack.object_id),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService CreateObjectAck",
+            ))),
+        }
+    }
+
+    /// Restarts the device or starts / ends a backup or restore procedure.
+    /// A wrong password is returned as a BacnetError::Server with ErrorCode::PasswordFailure.
+    #[maybe_async()]
+    pub async fn reinitialize_device(
+        &self,
+        buf: &mut [u8],
+        request: ReinitializeDevice<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::ReinitializeDevice(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
+
+    /// Enables or disables communication on the device (e.g. to silence a misbehaving device).
+    #[maybe_async()]
+    pub async fn device_communication_control(
+        &self,
+        buf: &mut [u8],
+        request: DeviceCommunicationControl<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::DeviceCommunicationControl(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
+
+    /// Reads the objects with active alarms or unacknowledged transitions.
+    /// If more_events is set in the ack then call this again with the last object id received.
+    #[maybe_async()]
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub async fn get_event_information<'a>(
+        &self,
+        buf: &'a mut [u8],
+        request: GetEventInformation,
+    ) -> Result<GetEventInformationAck<'a>, BacnetError<T>> {
+        let service = ConfirmedRequestService::GetEventInformation(request);
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::GetEventInformation(ack) => Ok(ack),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService GetEventInformationAck",
+            ))),
+        }
+    }
+
+    /// Reads the objects that are currently in alarm (superseded by get_event_information).
+    #[maybe_async()]
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub async fn get_alarm_summary<'a>(
+        &self,
+        buf: &'a mut [u8],
+    ) -> Result<GetAlarmSummaryAck<'a>, BacnetError<T>> {
+        let service = ConfirmedRequestService::GetAlarmSummary;
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::GetAlarmSummary(ack) => Ok(ack),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService GetAlarmSummaryAck",
+            ))),
+        }
+    }
+
+    /// Acknowledges an alarm previously reported by the device in an EventNotification.
+    #[maybe_async()]
+    pub async fn acknowledge_alarm(
+        &self,
+        buf: &mut [u8],
+        request: AcknowledgeAlarm<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::AcknowledgeAlarm(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
+
+    /// Adds elements to a list property.
+    /// A rejected element is returned as a BacnetError::Server with its index in first_failed_element.
+    #[maybe_async()]
+    pub async fn add_list_element(
+        &self,
+        buf: &mut [u8],
+        request: ListElementRequest<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::AddListElement(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
+
+    /// Removes elements from a list property.
+    /// A rejected element is returned as a BacnetError::Server with its index in first_failed_element.
+    #[maybe_async()]
+    pub async fn remove_list_element(
+        &self,
+        buf: &mut [u8],
+        request: ListElementRequest<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::RemoveListElement(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
+
     #[maybe_async()]
     pub async fn time_sync(
         &self,
@@ -249,6 +721,17 @@ where
         self.send_unconfirmed(buf, service).await
     }
 
+    /// Broadcasts the current UTC date and time, each device applies its own UTC offset.
+    #[maybe_async()]
+    pub async fn utc_time_sync(
+        &self,
+        buf: &mut [u8],
+        request: UtcTimeSynchronization,
+    ) -> Result<(), BacnetError<T>> {
+        let service = UnconfirmedRequest::UtcTimeSynchronization(request);
+        self.send_unconfirmed(buf, service).await
+    }
+
     #[maybe_async()]
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
     async fn send_and_receive_complex_ack<'a>(
@@ -259,14 +742,15 @@ where
         let invoke_id = self.send_confirmed(buf, service).await?;
 
         // receive reply
-        let n = self.io.read(buf).await.map_err(BacnetError::Io)?;
+        let n = self.read_reply(buf, invoke_id).await?;
         let buf = &buf[..n];
 
         // use the DataLink codec to decode the bytes
         let mut reader = Reader::default();
         let message = DataLink::decode(&mut reader, buf).map_err(BacnetError::Codec)?;
 
-        // TODO: return bacnet error if the server returns one
+        Self::check_error_pdu(invoke_id, &message)?;
+
         // return message is expected to be a ComplexAck
         let ack: ComplexAck = message.try_into().map_err(BacnetError::Codec)?;
 
@@ -277,7 +761,7 @@ where
     }
 
     #[maybe_async()]
-    async fn send_and_receive_simple_ack<'a>(
+    async fn send_and_receive_simple_ack(
         &self,
         buf: &mut [u8],
         service: ConfirmedRequestService<'_>,
@@ -285,14 +769,15 @@ where
         let invoke_id = self.send_confirmed(buf, service).await?;
 
         // receive reply
-        let n = self.io.read(buf).await.map_err(BacnetError::Io)?;
+        let n = self.read_reply(buf, invoke_id).await?;
         let buf = &buf[..n];
 
         // use the DataLink codec to decode the bytes
         let mut reader = Reader::default();
         let message = DataLink::decode(&mut reader, buf).map_err(BacnetError::Codec)?;
 
-        // TODO: return bacnet error if the server returns one
+        Self::check_error_pdu(invoke_id, &message)?;
+
         // return message is expected to be a ComplexAck
         let ack: SimpleAck = message.try_into().map_err(BacnetError::Codec)?;
 
@@ -302,6 +787,43 @@ where
         Ok(ack)
     }
 
+    // skips messages that are not a reply to the request (e.g. broadcasts, undecodable frames or
+    // late replies to an earlier request that timed out) and returns the length of the reply in buf
+    #[maybe_async()]
+    async fn read_reply(&self, buf: &mut [u8], invoke_id: u8) -> Result<usize, BacnetError<T>> {
+        let mut unrelated_reply = None;
+        let mut decode_error = None;
+
+        for _ in 0..MAX_UNRELATED_MESSAGES {
+            let n = self.io.read(buf).await.map_err(BacnetError::Io)?;
+            let mut reader = Reader::default();
+
+            // a frame we cannot decode is not our reply (e.g. an unsupported broadcast)
+            let message = match DataLink::decode(&mut reader, &buf[..n]) {
+                Ok(message) => message,
+                Err(e) => {
+                    decode_error = Some(e);
+                    continue;
+                }
+            };
+
+            match reply_invoke_id(&message) {
+                Some(actual) if actual == invoke_id => return Ok(n),
+                Some(actual) => unrelated_reply = Some(actual),
+                None => {}
+            }
+        }
+
+        match (unrelated_reply, decode_error) {
+            (Some(actual), _) => Err(BacnetError::InvokeId(InvokeIdError {
+                expected: invoke_id,
+                actual,
+            })),
+            (None, Some(e)) => Err(BacnetError::Codec(e)),
+            (None, None) => Err(no_reply_error()),
+        }
+    }
+
     #[maybe_async()]
     async fn send_unconfirmed(
         &self,
@@ -317,7 +839,7 @@ where
         data_link.encode(&mut writer);
 
         // send packet
-        let buffer = writer.to_bytes();
+        let buffer = writer.try_to_bytes()?;
         self.io.write(buffer).await.map_err(BacnetError::Io)?;
         Ok(())
     }
@@ -338,7 +860,7 @@ where
         data_link.encode(&mut writer);
 
         // send packet
-        let buffer = writer.to_bytes();
+        let buffer = writer.try_to_bytes()?;
         self.io.write(buffer).await.map_err(BacnetError::Io)?;
 
         Ok(invoke_id)
@@ -352,7 +874,661 @@ where
         }
     }
 
+    // the server may respond to a confirmed request with an Abort, Reject or Error instead of an ack
+    fn check_error_pdu(invoke_id: u8, message: &DataLink) -> Result<(), BacnetError<T>> {
+        if let Some(NetworkPdu {
+            network_message: NetworkMessage::Apdu(apdu),
+            ..
+        }) = message.npdu()
+        {
+            match apdu {
+                ApplicationPdu::Abort(abort) => {
+                    Self::check_invoke_id(invoke_id, abort.invoke_id)?;
+                    return Err(BacnetError::Abort(abort.clone()));
+                }
+                ApplicationPdu::Reject(reject) => {
+                    Self::check_invoke_id(invoke_id, reject.invoke_id)?;
+                    return Err(BacnetError::Reject(reject.clone()));
+                }
+                ApplicationPdu::Error(error) => {
+                    Self::check_invoke_id(invoke_id, error.invoke_id)?;
+                    return Err(BacnetError::Server(error.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
     fn get_then_inc_invoke_id(&self) -> u8 {
         self.invoke_id.fetch_add(1, Ordering::SeqCst)
     }
 }
+
+// the number of unrelated messages to skip while waiting for the reply to a confirmed request
+const MAX_UNRELATED_MESSAGES: usize = 8;
+
+// the number of device info requests read_device_infos sends before waiting for the replies
+pub const DEVICE_SCAN_BATCH: usize = 16;
+
+fn no_reply_error<T: NetworkIo>() -> BacnetError<T> {
+    BacnetError::Codec(Error::ConvertDataLink(
+        "no reply received for the confirmed request",
+    ))
+}
+
+// the invoke_id of a message sent in reply to a confirmed request (None for anything else)
+fn reply_invoke_id(message: &DataLink) -> Option<u8> {
+    match &message.npdu()?.network_message {
+        NetworkMessage::Apdu(apdu) => match apdu {
+            ApplicationPdu::ComplexAck(x) => Some(x.invoke_id),
+            ApplicationPdu::SimpleAck(x) => Some(x.invoke_id),
+            ApplicationPdu::Error(x) => Some(x.invoke_id),
+            ApplicationPdu::Segment(x) => Some(x.invoke_id),
+            ApplicationPdu::SegmentAck(x) => Some(x.invoke_id),
+            ApplicationPdu::Reject(x) => Some(x.invoke_id),
+            ApplicationPdu::Abort(x) => Some(x.invoke_id),
+            ApplicationPdu::ConfirmedRequest(_) | ApplicationPdu::UnconfirmedRequest(_) => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(all(test, feature = "alloc", not(feature = "is_sync")))]
+mod tests {
+    use core::cell::RefCell;
+
+    use alloc::{collections::VecDeque, vec, vec::Vec};
+
+    use super::*;
+    use crate::{
+        application_protocol::{
+            confirmed::RejectReason,
+            primitives::data_value::CharacterString,
+            services::{
+                atomic_read_file::{AtomicReadFileData, FileAccessMethod},
+                atomic_write_file::FileWriteData,
+                create_object::{CreateObjectAck, ObjectSpecifier},
+                read_property_multiple::{PropertyResult, PropertyValue},
+                reinitialize_device::ReinitializedState,
+            },
+        },
+        common::{object_id::ObjectType, spec::BACNET_ARRAY_ALL},
+    };
+
+    #[derive(Debug, Default)]
+    struct MockIo {
+        replies: RefCell<VecDeque<Vec<u8>>>,
+        requests: RefCell<usize>,
+    }
+
+    impl MockIo {
+        fn push(&self, apdu: ApplicationPdu) {
+            let message = NetworkMessage::Apdu(apdu);
+            let npdu = NetworkPdu::new(None, None, false, MessagePriority::Normal, message);
+            let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+            let mut buf = [0; 1024];
+            let mut writer = Writer::new(&mut buf);
+            data_link.encode(&mut writer);
+            self.replies
+                .borrow_mut()
+                .push_back(writer.try_to_bytes().unwrap().to_vec());
+        }
+
+        fn push_raw(&self, bytes: &[u8]) {
+            self.replies.borrow_mut().push_back(bytes.to_vec());
+        }
+
+        fn push_ack(&self, invoke_id: u8, objects_with_results: Vec<ObjectWithResults>) {
+            let ack = ReadPropertyMultipleAck::new(objects_with_results);
+            self.push(ApplicationPdu::ComplexAck(ComplexAck {
+                invoke_id,
+                service: ComplexAckService::ReadPropertyMultiple(ack),
+            }));
+        }
+    }
+
+    impl NetworkIo for MockIo {
+        type Error = ();
+
+        async fn read(&self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let reply = self.replies.borrow_mut().pop_front().ok_or(())?;
+            buf[..reply.len()].copy_from_slice(&reply);
+            Ok(reply.len())
+        }
+
+        async fn write(&self, buf: &[u8]) -> Result<usize, Self::Error> {
+            *self.requests.borrow_mut() += 1;
+            Ok(buf.len())
+        }
+    }
+
+    fn result(object_id: ObjectId, id: PropertyId, value: f32) -> ObjectWithResults<'static> {
+        let value = PropertyValue::PropValue(ApplicationDataValue::Real(value));
+        ObjectWithResults::new(object_id, vec![PropertyResult { id, value }])
+    }
+
+    #[tokio::test]
+    async fn read_property_multiple_split_after_segmentation_abort() {
+        let ai_1 = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let ai_2 = ObjectId::new(ObjectType::ObjectAnalogInput, 2);
+        let pv = PropertyId::PropPresentValue;
+        let cov = PropertyId::PropCovIncrement;
+
+        let io = MockIo::default();
+        let abort = |invoke_id| Abort {
+            server: true,
+            invoke_id,
+            reason: AbortReason::SegmentationNotSupported,
+        };
+
+        // whole request is too big, so is ai_1 on its own, ai_2 fits
+        io.push(ApplicationPdu::Abort(abort(0)));
+        io.push(ApplicationPdu::Abort(abort(1)));
+        io.push_ack(2, vec![result(ai_1, pv, 1.0)]);
+        io.push_ack(3, vec![result(ai_1, cov, 0.5)]);
+        io.push_ack(4, vec![result(ai_2, pv, 2.0)]);
+
+        let bacnet = Bacnet::new(io);
+        let request = ReadPropertyMultiple::from_pairs(&[(ai_1, pv), (ai_1, cov), (ai_2, pv)]);
+        let mut buf = [0; 1024];
+        let ack = bacnet
+            .read_property_multiple_split(&mut buf, request)
+            .await
+            .unwrap();
+
+        assert_eq!(*bacnet.io.requests.borrow(), 5);
+        assert!(bacnet.io.replies.borrow().is_empty());
+
+        let objects = ack.objects_with_results;
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0].object_id, ai_1);
+        let ids: Vec<_> = objects[0].property_results.iter().map(|x| x.id).collect();
+        assert_eq!(ids, vec![pv, cov]);
+        assert_eq!(objects[1].object_id, ai_2);
+        assert_eq!(objects[1].property_results.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn read_property_multiple_split_single_property_is_not_resent() {
+        let io = MockIo::default();
+        io.push(ApplicationPdu::Abort(Abort {
+            server: true,
+            invoke_id: 0,
+            reason: AbortReason::SegmentationNotSupported,
+        }));
+
+        let bacnet = Bacnet::new(io);
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let request =
+            ReadPropertyMultiple::from_pairs(&[(object_id, PropertyId::PropPresentValue)]);
+        let mut buf = [0; 1024];
+        let result = bacnet.read_property_multiple_split(&mut buf, request).await;
+
+        assert!(matches!(result, Err(e) if e.is_segmentation_not_supported()));
+        assert_eq!(*bacnet.io.requests.borrow(), 1);
+    }
+
+    #[tokio::test]
+    async fn abort_is_returned_as_error() {
+        let io = MockIo::default();
+        io.push(ApplicationPdu::Abort(Abort {
+            server: true,
+            invoke_id: 0,
+            reason: AbortReason::BufferOverflow,
+        }));
+
+        let bacnet = Bacnet::new(io);
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let request =
+            ReadPropertyMultiple::from_pairs(&[(object_id, PropertyId::PropPresentValue)]);
+        let mut buf = [0; 1024];
+        let result = bacnet.read_property_multiple_split(&mut buf, request).await;
+
+        match result {
+            Err(BacnetError::Abort(abort)) => assert_eq!(abort.reason, AbortReason::BufferOverflow),
+            x => panic!("expected abort, got {:?}", x),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_object_returns_object_id() {
+        let created = ObjectId::new(ObjectType::ObjectAnalogValue, 7);
+        let io = MockIo::default();
+        io.push(ApplicationPdu::ComplexAck(ComplexAck {
+            invoke_id: 0,
+            service: ComplexAckService::CreateObject(CreateObjectAck { object_id: created }),
+        }));
+
+        let bacnet = Bacnet::new(io);
+        let specifier = ObjectSpecifier::ObjectType(ObjectType::ObjectAnalogValue);
+        let request = CreateObject::new(specifier, vec![]);
+        let mut buf = [0; 1024];
+        let object_id = bacnet.create_object(&mut buf, request).await.unwrap();
+        assert_eq!(object_id, created);
+    }
+
+    #[tokio::test]
+    async fn read_file_stream() {
+        let io = MockIo::default();
+        // complex ack for invoke_id 0: end of file, 3 octets from position 0
+        io.push_raw(&[
+            0x81, 0x0a, 0x00, 0x12, 0x01, 0x00, 0x30, 0x00, 0x06, 0x11, 0x0e, 0x31, 0x00, 0x63,
+            b'a', b'b', b'c', 0x0f,
+        ]);
+
+        let bacnet = Bacnet::new(io);
+        let request = AtomicReadFile::new(
+            ObjectId::new(ObjectType::ObjectFile, 1),
+            FileAccessMethod::Stream {
+                file_start_position: 0,
+                requested_octet_count: 1024,
+            },
+        );
+        let mut buf = [0; 1024];
+        let ack = bacnet.read_file(&mut buf, request).await.unwrap();
+        assert!(ack.end_of_file);
+        match ack.data {
+            AtomicReadFileData::Stream(data) => assert_eq!(data.file_data, b"abc"),
+            x => panic!("expected stream data, got {:?}", x),
+        }
+    }
+
+    #[tokio::test]
+    async fn write_file_appends() {
+        let io = MockIo::default();
+        io.push(ApplicationPdu::ComplexAck(ComplexAck {
+            invoke_id: 0,
+            service: ComplexAckService::AtomicWriteFile(AtomicWriteFileAck::Stream {
+                file_start_position: 2048,
+            }),
+        }));
+
+        let bacnet = Bacnet::new(io);
+        let request = AtomicWriteFile::new(
+            ObjectId::new(ObjectType::ObjectFile, 1),
+            FileWriteData::Stream {
+                file_start_position: -1,
+                file_data: b"abc",
+            },
+        );
+        let mut buf = [0; 1024];
+        let ack = bacnet.write_file(&mut buf, request).await.unwrap();
+        assert_eq!(
+            ack,
+            AtomicWriteFileAck::Stream {
+                file_start_position: 2048
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_is_returned_as_error() {
+        let io = MockIo::default();
+        io.push(ApplicationPdu::Reject(Reject {
+            invoke_id: 0,
+            reason: RejectReason::UnrecognizedService,
+        }));
+
+        let bacnet = Bacnet::new(io);
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let request = ReadProperty::new(object_id, PropertyId::PropPresentValue);
+        let mut buf = [0; 1024];
+        let result = bacnet.read_property(&mut buf, request).await;
+
+        match result {
+            Err(BacnetError::Reject(reject)) => {
+                assert_eq!(reject.reason, RejectReason::UnrecognizedService)
+            }
+            x => panic!("expected reject, got {:?}", x),
+        }
+    }
+
+    #[tokio::test]
+    async fn register_foreign_device() {
+        let io = MockIo::default();
+        // an unrelated broadcast arrives before the result
+        io.push_raw(&[
+            0x81, 0x0b, 0x00, 0x0c, 0x01, 0x20, 0xff, 0xff, 0x00, 0xff, 0x10, 0x08,
+        ]);
+        io.push_raw(&[0x81, 0x00, 0x00, 0x06, 0x00, 0x00]);
+        io.push_raw(&[0x81, 0x00, 0x00, 0x06, 0x00, 0x30]);
+
+        let bacnet = Bacnet::new(io);
+        let mut buf = [0; 64];
+        bacnet.register_foreign_device(&mut buf, 300).await.unwrap();
+
+        let result = bacnet.register_foreign_device(&mut buf, 300).await;
+        assert!(matches!(
+            result,
+            Err(BacnetError::Bvlc(BvlcResultCode::RegisterForeignDeviceNak))
+        ));
+        assert_eq!(*bacnet.io.requests.borrow(), 2);
+    }
+
+    #[tokio::test]
+    async fn buffer_too_small_is_returned_as_error() {
+        let bacnet = Bacnet::new(MockIo::default());
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let request = ReadProperty::new(object_id, PropertyId::PropPresentValue);
+        let mut buf = [0; 8];
+        let result = bacnet.read_property(&mut buf, request).await;
+
+        assert!(matches!(result, Err(BacnetError::Codec(Error::Length(_)))));
+        assert_eq!(*bacnet.io.requests.borrow(), 0);
+    }
+
+    #[tokio::test]
+    async fn reinitialize_device_password_failure() {
+        let io = MockIo::default();
+        // Error PDU for ReinitializeDevice: security / password-failure
+        io.push_raw(&[
+            0x81, 0x0a, 0x00, 0x0d, 0x01, 0x00, 0x50, 0x00, 0x14, 0x91, 0x04, 0x91, 0x1a,
+        ]);
+
+        let bacnet = Bacnet::new(io);
+        let request = ReinitializeDevice::new(
+            ReinitializedState::WarmStart,
+            Some(CharacterString::new("wrong")),
+        );
+        let mut buf = [0; 1024];
+        let result = bacnet.reinitialize_device(&mut buf, request).await;
+
+        match result {
+            Err(BacnetError::Server(error)) => {
+                assert!(matches!(error.error_code, ErrorCode::PasswordFailure))
+            }
+            x => panic!("expected password failure, got {:?}", x),
+        }
+    }
+
+    #[tokio::test]
+    async fn add_list_element_first_failed_element() {
+        let io = MockIo::default();
+        // Error PDU for AddListElement: [0] { property / invalid-data-type }, [1] 2
+        io.push_raw(&[
+            0x81, 0x0a, 0x00, 0x11, 0x01, 0x00, 0x50, 0x00, 0x08, 0x0e, 0x91, 0x02, 0x91, 0x09,
+            0x0f, 0x19, 0x02,
+        ]);
+
+        let bacnet = Bacnet::new(io);
+        let request = ListElementRequest::new(
+            ObjectId::new(ObjectType::ObjectMultiStateInput, 1),
+            PropertyId::PropAlarmValues,
+            None,
+            vec![
+                ApplicationDataValue::UnsignedInt(3),
+                ApplicationDataValue::Real(4.0),
+            ],
+        );
+        let mut buf = [0; 1024];
+        let result = bacnet.add_list_element(&mut buf, request).await;
+
+        match result {
+            Err(BacnetError::Server(error)) => {
+                assert!(matches!(error.error_code, ErrorCode::InvalidDataType));
+                assert_eq!(error.first_failed_element, Some(2));
+            }
+            x => panic!("expected invalid data type, got {:?}", x),
+        }
+    }
+
+    #[tokio::test]
+    async fn verify_object_identifier_mismatch() {
+        let requested = ObjectId::new(ObjectType::ObjectDevice, 20088);
+        let echoed = ObjectId::new(ObjectType::ObjectDevice, 20089);
+
+        let io = MockIo::default();
+        io.push(ApplicationPdu::ComplexAck(ComplexAck {
+            invoke_id: 0,
+            service: ComplexAckService::ReadProperty(ReadPropertyAck {
+                object_id: requested,
+                property_id: PropertyId::PropObjectIdentifier,
+                array_index: BACNET_ARRAY_ALL,
+                property_value: ReadPropertyValue::ApplicationDataValue(
+                    ApplicationDataValue::ObjectId(echoed),
+                ),
+            }),
+        }));
+
+        let bacnet = Bacnet::new(io);
+        let mut buf = [0; 1024];
+        let result = bacnet.verify_object_identifier(&mut buf, requested).await;
+        assert!(matches!(
+            result,
+            Err(BacnetError::Codec(Error::InvalidValue(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn read_array_element_strips_index_echo() {
+        let device = ObjectId::new(ObjectType::ObjectDevice, 20088);
+        let io = MockIo::default();
+        // Object_List[1] = Device:20088
+        io.push_raw(&[
+            0x81, 0x0a, 0x00, 0x19, 0x01, 0x00, 0x30, 0x00, 0x0c, 0x0c, 0x02, 0x00, 0x4e, 0x78,
+            0x19, 0x4c, 0x29, 0x01, 0x3e, 0xc4, 0x02, 0x00, 0x4e, 0x78, 0x3f,
+        ]);
+
+        let bacnet = Bacnet::new(io);
+        let mut buf = [0; 1024];
+        let value = bacnet
+            .read_array_element(&mut buf, device, PropertyId::PropObjectList, 1)
+            .await
+            .unwrap();
+        assert!(matches!(value, ApplicationDataValue::ObjectId(x) if x == device));
+    }
+
+    #[tokio::test]
+    async fn read_array_element_relinquished_priority() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogOutput, 1);
+        let io = MockIo::default();
+        // Priority_Array[8] = Null
+        io.push_raw(&[
+            0x81, 0x0a, 0x00, 0x15, 0x01, 0x00, 0x30, 0x00, 0x0c, 0x0c, 0x00, 0x40, 0x00, 0x01,
+            0x19, 0x57, 0x29, 0x08, 0x3e, 0x00, 0x3f,
+        ]);
+
+        let bacnet = Bacnet::new(io);
+        let mut buf = [0; 1024];
+        let value = bacnet
+            .read_array_element(&mut buf, object_id, PropertyId::PropPriorityArray, 8)
+            .await
+            .unwrap();
+        assert!(matches!(value, ApplicationDataValue::Null));
+    }
+
+    #[tokio::test]
+    async fn write_property_multiple_simple_ack() {
+        use crate::application_protocol::{
+            confirmed::ConfirmedServiceChoice,
+            primitives::data_value::ApplicationDataValueWrite,
+            services::write_property_multiple::{WriteAccessSpecification, WritePropertyValue},
+        };
+
+        let io = MockIo::default();
+        io.push(ApplicationPdu::SimpleAck(SimpleAck {
+            invoke_id: 0,
+            service_choice: ConfirmedServiceChoice::WritePropMultiple,
+        }));
+
+        let bacnet = Bacnet::new(io);
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogValue, 1);
+        let value = WritePropertyValue::new(
+            PropertyId::PropPresentValue,
+            ApplicationDataValueWrite::Real(21.5),
+        );
+        let request =
+            WritePropertyMultiple::new(vec![WriteAccessSpecification::new(object_id, vec![value])]);
+        let mut buf = [0; 1024];
+        let result = bacnet.write_property_multiple(&mut buf, request).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn unrelated_messages_are_skipped() {
+        use crate::{
+            application_protocol::{application_pdu::MaxAdpu, confirmed::ConfirmedServiceChoice},
+            common::spec::Segmentation,
+        };
+
+        let simple_ack = |invoke_id| {
+            ApplicationPdu::SimpleAck(SimpleAck {
+                invoke_id,
+                service_choice: ConfirmedServiceChoice::ReinitializeDevice,
+            })
+        };
+
+        // a broadcast and a late reply to an earlier request arrive before the reply
+        let io = MockIo::default();
+        let device_id = ObjectId::new(ObjectType::ObjectDevice, 20);
+        let i_am = IAm::new(device_id, MaxAdpu::_1476, Segmentation::None, 260);
+        io.push(ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::IAm(
+            i_am,
+        )));
+        io.push(simple_ack(200));
+        // a truncated frame is skipped too
+        io.push_raw(&[0x81, 0x0a, 0x00, 0x08, 0x01]);
+        io.push(simple_ack(0));
+
+        let bacnet = Bacnet::new(io);
+        let request = ReinitializeDevice::new(ReinitializedState::WarmStart, None);
+        let mut buf = [0; 1024];
+        let result = bacnet.reinitialize_device(&mut buf, request).await;
+        assert!(result.is_ok());
+        assert!(bacnet.io.replies.borrow().is_empty());
+
+        // give up when the reply never arrives
+        let io = MockIo::default();
+        for _ in 0..MAX_UNRELATED_MESSAGES {
+            io.push(simple_ack(200));
+        }
+        let bacnet = Bacnet::new(io);
+        let request = ReinitializeDevice::new(ReinitializedState::WarmStart, None);
+        let result = bacnet.reinitialize_device(&mut buf, request).await;
+        assert!(matches!(
+            result,
+            Err(BacnetError::InvokeId(InvokeIdError {
+                expected: 0,
+                actual: 200
+            }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn read_device_infos_isolates_failures() {
+        let io = MockIo::default();
+        let vendor_id = |device_id, x| {
+            let value = PropertyValue::PropValue(ApplicationDataValue::UnsignedInt(x));
+            let id = PropertyId::PropVendorIdentifier;
+            vec![ObjectWithResults::new(
+                device_id,
+                vec![PropertyResult { id, value }],
+            )]
+        };
+
+        // device 10 replies, device 20 aborts and device 30 cannot be reached
+        let device = |x| ObjectId::new(ObjectType::ObjectDevice, x);
+        io.push_ack(0, vendor_id(device(10), 5));
+        io.push(ApplicationPdu::Abort(Abort {
+            server: true,
+            invoke_id: 1,
+            reason: AbortReason::Other,
+        }));
+
+        let bacnet = Bacnet::new(io);
+        let devices = [10u32, 20, 30].map(|x| (device(x), x));
+        let mut buf = [0; 1024];
+        let results = bacnet
+            .read_device_infos(&mut buf, devices, |_io, addr| match addr {
+                30 => Err(()),
+                _ => Ok(()),
+            })
+            .await;
+
+        assert_eq!(results.len(), 3);
+        let (addr, result) = &results[0];
+        assert_eq!(*addr, 10);
+        assert_eq!(result.as_ref().unwrap().vendor_identifier, Some(5));
+        let (addr, result) = &results[1];
+        assert_eq!(*addr, 20);
+        assert!(matches!(result, Err(BacnetError::Abort(_))));
+        let (addr, result) = &results[2];
+        assert_eq!(*addr, 30);
+        assert!(matches!(result, Err(BacnetError::Io(()))));
+        assert_eq!(*bacnet.io.requests.borrow(), 2);
+    }
+
+    #[tokio::test]
+    async fn read_device_infos_sends_batch_before_waiting() {
+        let io = MockIo::default();
+        let vendor_id = |device_id, x| {
+            let value = PropertyValue::PropValue(ApplicationDataValue::UnsignedInt(x));
+            let id = PropertyId::PropVendorIdentifier;
+            vec![ObjectWithResults::new(
+                device_id,
+                vec![PropertyResult { id, value }],
+            )]
+        };
+
+        // the replies arrive out of order and device 30 never replies
+        let device = |x| ObjectId::new(ObjectType::ObjectDevice, x);
+        io.push_ack(1, vendor_id(device(20), 6));
+        io.push(ApplicationPdu::UnconfirmedRequest(
+            UnconfirmedRequest::WhoIs(WhoIs::new()),
+        ));
+        io.push_ack(0, vendor_id(device(10), 5));
+
+        let bacnet = Bacnet::new(io);
+        let devices = [10u32, 20, 30].map(|x| (device(x), x));
+        let mut buf = [0; 1024];
+        let results = bacnet
+            .read_device_infos(&mut buf, devices, |_io, _addr| Ok(()))
+            .await;
+
+        assert_eq!(*bacnet.io.requests.borrow(), 3);
+        let vendor_ids: Vec<_> = results
+            .iter()
+            .map(|(addr, x)| (*addr, x.as_ref().ok().and_then(|x| x.vendor_identifier)))
+            .collect();
+        assert_eq!(vendor_ids, vec![(10, Some(5)), (20, Some(6)), (30, None)]);
+        assert!(matches!(
+            results[2].1,
+            Err(BacnetError::Codec(Error::ConvertDataLink(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn unsubscribe_unknown_subscription_is_ok() {
+        let io = MockIo::default();
+        // Error PDU for SubscribeCov: services / unknown-subscription
+        io.push_raw(&[
+            0x81, 0x0a, 0x00, 0x0d, 0x01, 0x00, 0x50, 0x00, 0x05, 0x91, 0x05, 0x91, 0x4f,
+        ]);
+        // Error PDU for SubscribeCov: object / unknown-object
+        io.push_raw(&[
+            0x81, 0x0a, 0x00, 0x0d, 0x01, 0x00, 0x50, 0x01, 0x05, 0x91, 0x01, 0x91, 0x1f,
+        ]);
+
+        let bacnet = Bacnet::new(io);
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let mut buf = [0; 1024];
+
+        let result = bacnet
+            .unsubscribe_change_of_value(&mut buf, 1, object_id)
+            .await;
+        assert!(result.is_ok());
+
+        // any other error is still reported
+        let result = bacnet
+            .unsubscribe_change_of_value(&mut buf, 1, object_id)
+            .await;
+        match result {
+            Err(BacnetError::Server(error)) => {
+                assert!(matches!(error.error_code, ErrorCode::UnknownObject))
+            }
+            x => panic!("expected a server error, got {:?}", x),
+        }
+    }
+}
@@ -0,0 +1,322 @@
+// A minimal async transport for no_std targets (requires the `transport` feature)
+// Unlike `NetworkIo` the transport takes `&mut self` so that it can own a socket from an embedded
+// network stack directly. `confirmed_request` does the framing for a single confirmed request.
+// The transport is not responsible for timeouts, wrap `confirmed_request` in one (e.g. embassy_time::with_timeout)
+// An embassy-net UDP implementation is provided with the `embassy-net` feature
+
+use core::fmt::Debug;
+
+use crate::{
+    application_protocol::{
+        application_pdu::ApplicationPdu,
+        confirmed::{
+            Abort, ComplexAck, ConfirmedBacnetError, ConfirmedRequest, ConfirmedRequestService,
+            Reject, SimpleAck,
+        },
+    },
+    common::{
+        error::Error,
+        io::{Reader, Writer},
+    },
+    network_protocol::{
+        data_link::{DataLink, DataLinkFunction},
+        network_pdu::{MessagePriority, NetworkMessage, NetworkPdu},
+    },
+    simple::{reply_invoke_id, InvokeIdError, MAX_UNRELATED_MESSAGES},
+};
+
+#[allow(async_fn_in_trait)]
+pub trait BacnetTransport {
+    type Error: Debug;
+
+    async fn send(&mut self, buf: &[u8]) -> Result<(), Self::Error>;
+    async fn recv(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TransportError<E> {
+    Io(E),
+    Codec(Error),
+    InvokeId(InvokeIdError),
+    Abort(Abort),
+    Reject(Reject),               // the server could not parse the request
+    Server(ConfirmedBacnetError), // the server responded with an Error PDU
+}
+
+impl<E> From<Error> for TransportError<E> {
+    fn from(value: Error) -> Self {
+        Self::Codec(value)
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ConfirmedReply<'a> {
+    SimpleAck(SimpleAck),
+    ComplexAck(ComplexAck<'a>),
+}
+
+// sends a confirmed request and waits for the ack with the same invoke_id
+// messages that are not a reply to the request (e.g. broadcasts) are skipped
+#[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+pub async fn confirmed_request<'a, T: BacnetTransport>(
+    transport: &mut T,
+    buf: &'a mut [u8],
+    invoke_id: u8,
+    service: ConfirmedRequestService<'_>,
+) -> Result<ConfirmedReply<'a>, TransportError<T::Error>> {
+    let apdu = ApplicationPdu::ConfirmedRequest(ConfirmedRequest::new(invoke_id, service));
+    let message = NetworkMessage::Apdu(apdu);
+    let npdu = NetworkPdu::new(None, None, true, MessagePriority::Normal, message);
+    let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+
+    let mut writer = Writer::new(buf);
+    data_link.encode(&mut writer);
+    let buffer = writer.try_to_bytes()?;
+    transport.send(buffer).await.map_err(TransportError::Io)?;
+
+    let n = read_reply(transport, buf, invoke_id).await?;
+
+    let mut reader = Reader::default();
+    let message = DataLink::decode(&mut reader, &buf[..n])?;
+    match message.into_npdu().map(|x| x.network_message) {
+        Some(NetworkMessage::Apdu(apdu)) => match apdu {
+            ApplicationPdu::SimpleAck(ack) => Ok(ConfirmedReply::SimpleAck(ack)),
+            ApplicationPdu::ComplexAck(ack) => Ok(ConfirmedReply::ComplexAck(ack)),
+            ApplicationPdu::Abort(abort) => Err(TransportError::Abort(abort)),
+            ApplicationPdu::Reject(reject) => Err(TransportError::Reject(reject)),
+            ApplicationPdu::Error(error) => Err(TransportError::Server(error)),
+            _ => Err(TransportError::Codec(Error::ConvertDataLink(
+                "apdu message is not a reply to a confirmed request",
+            ))),
+        },
+        _ => Err(TransportError::Codec(Error::ConvertDataLink(
+            "message is not an apdu",
+        ))),
+    }
+}
+
+// returns the length of the reply in buf
+async fn read_reply<T: BacnetTransport>(
+    transport: &mut T,
+    buf: &mut [u8],
+    invoke_id: u8,
+) -> Result<usize, TransportError<T::Error>> {
+    let mut unrelated_reply = None;
+
+    for _ in 0..MAX_UNRELATED_MESSAGES {
+        let n = transport.recv(buf).await.map_err(TransportError::Io)?;
+        let mut reader = Reader::default();
+        let Ok(message) = DataLink::decode(&mut reader, &buf[..n]) else {
+            continue;
+        };
+
+        match reply_invoke_id(&message) {
+            Some(actual) if actual == invoke_id => return Ok(n),
+            Some(actual) => unrelated_reply = Some(actual),
+            None => {}
+        }
+    }
+
+    match unrelated_reply {
+        Some(actual) => Err(TransportError::InvokeId(InvokeIdError {
+            expected: invoke_id,
+            actual,
+        })),
+        None => Err(TransportError::Codec(Error::ConvertDataLink(
+            "no reply received for the confirmed request",
+        ))),
+    }
+}
+
+#[cfg(feature = "embassy-net")]
+pub use self::embassy::{EmbassyUdpError, EmbassyUdpTransport};
+
+#[cfg(feature = "embassy-net")]
+mod embassy {
+    use embassy_net::{
+        udp::{RecvError, SendError, UdpSocket},
+        IpEndpoint,
+    };
+
+    use super::BacnetTransport;
+
+    #[derive(Debug)]
+    pub enum EmbassyUdpError {
+        Send(SendError),
+        Recv(RecvError),
+    }
+
+    // sends every request to a single device (e.g. 192.168.1.249:47808)
+    pub struct EmbassyUdpTransport<'a> {
+        socket: UdpSocket<'a>,
+        remote: IpEndpoint,
+    }
+
+    impl<'a> EmbassyUdpTransport<'a> {
+        // the socket must already be bound (usually to port 47808)
+        pub fn new(socket: UdpSocket<'a>, remote: IpEndpoint) -> Self {
+            Self { socket, remote }
+        }
+
+        pub fn into_inner(self) -> UdpSocket<'a> {
+            self.socket
+        }
+    }
+
+    impl BacnetTransport for EmbassyUdpTransport<'_> {
+        type Error = EmbassyUdpError;
+
+        async fn send(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+            self.socket
+                .send_to(buf, self.remote)
+                .await
+                .map_err(EmbassyUdpError::Send)
+        }
+
+        async fn recv(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let (n, _meta) = self
+                .socket
+                .recv_from(buf)
+                .await
+                .map_err(EmbassyUdpError::Recv)?;
+            Ok(n)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::{collections::VecDeque, vec::Vec};
+
+    use super::*;
+    use crate::{
+        application_protocol::{
+            confirmed::{AbortReason, ComplexAckService, ConfirmedServiceChoice},
+            primitives::data_value::ApplicationDataValue,
+            services::{
+                read_property::{ReadProperty, ReadPropertyAck, ReadPropertyValue},
+                who_is::WhoIs,
+            },
+            unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::BACNET_ARRAY_ALL,
+        },
+    };
+
+    #[derive(Debug, Default)]
+    struct MockTransport {
+        replies: VecDeque<Vec<u8>>,
+        sent: Vec<Vec<u8>>,
+    }
+
+    impl MockTransport {
+        fn push(&mut self, apdu: ApplicationPdu) {
+            let message = NetworkMessage::Apdu(apdu);
+            let npdu = NetworkPdu::new(None, None, false, MessagePriority::Normal, message);
+            let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+            let mut buf = [0; 1024];
+            let mut writer = Writer::new(&mut buf);
+            data_link.encode(&mut writer);
+            self.replies
+                .push_back(writer.try_to_bytes().unwrap().to_vec());
+        }
+    }
+
+    impl BacnetTransport for MockTransport {
+        type Error = ();
+
+        async fn send(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+            self.sent.push(buf.to_vec());
+            Ok(())
+        }
+
+        async fn recv(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let reply = self.replies.pop_front().ok_or(())?;
+            buf[..reply.len()].copy_from_slice(&reply);
+            Ok(reply.len())
+        }
+    }
+
+    fn read_present_value() -> ConfirmedRequestService<'static> {
+        ConfirmedRequestService::ReadProperty(ReadProperty::new(
+            ObjectId::new(ObjectType::ObjectAnalogValue, 1),
+            PropertyId::PropPresentValue,
+        ))
+    }
+
+    #[tokio::test]
+    async fn confirmed_request_skips_unrelated_messages() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogValue, 1);
+        let mut transport = MockTransport::default();
+        transport.push(ApplicationPdu::UnconfirmedRequest(
+            UnconfirmedRequest::WhoIs(WhoIs::new()),
+        ));
+        transport.push(ApplicationPdu::SimpleAck(SimpleAck {
+            invoke_id: 6,
+            service_choice: ConfirmedServiceChoice::WriteProperty,
+        }));
+        transport.push(ApplicationPdu::ComplexAck(ComplexAck {
+            invoke_id: 7,
+            service: ComplexAckService::ReadProperty(ReadPropertyAck {
+                object_id,
+                property_id: PropertyId::PropPresentValue,
+                array_index: BACNET_ARRAY_ALL,
+                property_value: ReadPropertyValue::ApplicationDataValue(
+                    ApplicationDataValue::Real(21.5),
+                ),
+            }),
+        }));
+
+        let mut buf = [0; 1024];
+        let reply = confirmed_request(&mut transport, &mut buf, 7, read_present_value())
+            .await
+            .unwrap();
+        let ConfirmedReply::ComplexAck(ComplexAck {
+            invoke_id: 7,
+            service: ComplexAckService::ReadProperty(ack),
+        }) = reply
+        else {
+            panic!("expected a ReadProperty ack");
+        };
+        assert!(matches!(
+            ack.property_value,
+            ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::Real(x)) if x == 21.5
+        ));
+
+        // the request went out once with the caller's invoke_id
+        assert_eq!(transport.sent.len(), 1);
+        let mut reader = Reader::default();
+        let request = DataLink::decode(&mut reader, &transport.sent[0]).unwrap();
+        let Some(NetworkMessage::Apdu(ApplicationPdu::ConfirmedRequest(request))) =
+            request.into_npdu().map(|x| x.network_message)
+        else {
+            panic!("expected a confirmed request");
+        };
+        assert_eq!(request.invoke_id, 7);
+    }
+
+    #[tokio::test]
+    async fn confirmed_request_returns_abort() {
+        let mut transport = MockTransport::default();
+        transport.push(ApplicationPdu::Abort(Abort {
+            server: true,
+            invoke_id: 3,
+            reason: AbortReason::SegmentationNotSupported,
+        }));
+
+        let mut buf = [0; 1024];
+        let result = confirmed_request(&mut transport, &mut buf, 3, read_present_value()).await;
+        assert!(matches!(
+            result,
+            Err(TransportError::Abort(Abort {
+                reason: AbortReason::SegmentationNotSupported,
+                ..
+            }))
+        ));
+    }
+}
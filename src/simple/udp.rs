@@ -0,0 +1,567 @@
+// A blocking BACnet IP transport built on the standard library UdpSocket (requires the `std` feature)
+// This is intended for desktop tools where discovering devices on the local subnet is the first step
+
+use core::sync::atomic::{AtomicU8, Ordering};
+use std::{
+    format,
+    io::ErrorKind,
+    net::{Ipv4Addr, SocketAddr, UdpSocket},
+    time::{Duration, Instant},
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    application_protocol::{
+        application_pdu::ApplicationPdu,
+        confirmed::{
+            Abort, ComplexAck, ComplexAckService, ConfirmedBacnetError, ConfirmedRequest,
+            ConfirmedRequestService, Reject, SimpleAck,
+        },
+        services::{
+            i_am::IAm,
+            read_property::{ReadProperty, ReadPropertyAck},
+            who_is::WhoIs,
+            write_property::WriteProperty,
+        },
+        unconfirmed::UnconfirmedRequest,
+    },
+    common::{
+        error::Error,
+        io::{Reader, Writer},
+    },
+    network_protocol::{
+        data_link::{DataLink, DataLinkFunction},
+        network_pdu::{MessagePriority, NetworkMessage, NetworkPdu},
+    },
+    simple::reply_invoke_id,
+};
+
+// the standard BACnet IP port (0xBAC0)
+pub const BACNET_IP_PORT: u16 = 47808;
+
+const MAX_PACKET_LEN: usize = 1500;
+
+// computes the directed broadcast address of a subnet e.g. 192.168.1.20/24 -> 192.168.1.255
+pub fn subnet_broadcast(ip: Ipv4Addr, prefix_len: u8) -> Ipv4Addr {
+    let mask = match prefix_len {
+        0 => 0,
+        x => u32::MAX << (32 - x.min(32) as u32),
+    };
+    Ipv4Addr::from(u32::from(ip) | !mask)
+}
+
+#[derive(Debug)]
+pub struct UdpTransport {
+    socket: UdpSocket,
+    broadcast_addr: SocketAddr,
+}
+
+impl UdpTransport {
+    // the socket has broadcast enabled so that it can send to broadcast_addr
+    pub fn new(socket: UdpSocket, broadcast_addr: SocketAddr) -> std::io::Result<Self> {
+        socket.set_broadcast(true)?;
+        Ok(Self {
+            socket,
+            broadcast_addr,
+        })
+    }
+
+    // binds to any local address on the given port and broadcasts on the subnet of local_ip
+    pub fn bind(local_ip: Ipv4Addr, prefix_len: u8, port: u16) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port))?;
+        let broadcast_ip = subnet_broadcast(local_ip, prefix_len);
+        Self::new(socket, SocketAddr::from((broadcast_ip, BACNET_IP_PORT)))
+    }
+
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    pub fn into_inner(self) -> UdpSocket {
+        self.socket
+    }
+
+    // broadcasts a WhoIs for device instances between low and high (inclusive) and collects
+    // every IAm received before the timeout elapses along with the address to send requests to
+    // packets that are not IAm replies (or that fail to decode) are ignored
+    pub fn who_is_broadcast(
+        &self,
+        low: u32,
+        high: u32,
+        timeout: Duration,
+    ) -> std::io::Result<Vec<(SocketAddr, IAm)>> {
+        let mut buf = vec![0; MAX_PACKET_LEN];
+
+        let who_is = WhoIs::for_range(low, high)
+            .map_err(|e| std::io::Error::new(ErrorKind::InvalidInput, format!("{:?}", e)))?;
+        let request = UnconfirmedRequest::WhoIs(who_is);
+        let data_link = DataLink::new_broadcast_unconfirmed_req(request);
+        let mut writer = Writer::new(&mut buf);
+        data_link.encode(&mut writer);
+        let bytes = writer
+            .try_to_bytes()
+            .map_err(|e| std::io::Error::new(ErrorKind::InvalidInput, format!("{:?}", e)))?;
+        self.socket.send_to(bytes, self.broadcast_addr)?;
+
+        let deadline = Instant::now() + timeout;
+        let mut devices = Vec::new();
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+
+            self.socket.set_read_timeout(Some(remaining))?;
+            let (n, peer) = match self.socket.recv_from(&mut buf) {
+                Ok(x) => x,
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => break,
+                Err(e) => return Err(e),
+            };
+
+            if let Some(device) = Self::decode_i_am(&buf[..n], peer) {
+                devices.push(device);
+            }
+        }
+
+        Ok(devices)
+    }
+
+    // an IAm forwarded by a BBMD was received from the BBMD so the device is at the original source
+    fn decode_i_am(buf: &[u8], peer: SocketAddr) -> Option<(SocketAddr, IAm)> {
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, buf).ok()?;
+        let addr = match message.original_source() {
+            Some(source) => SocketAddr::from((source.ip, source.port)),
+            None => peer,
+        };
+        match message.into_npdu()?.network_message {
+            NetworkMessage::Apdu(ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::IAm(
+                i_am,
+            ))) => Some((addr, i_am)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    Io(std::io::Error),
+    Codec(Error),
+    Timeout, // no reply with the invoke_id of the request arrived in time
+    Abort(Abort),
+    Reject(Reject),               // the server could not parse the request
+    Server(ConfirmedBacnetError), // the server responded with an Error PDU
+}
+
+impl From<Error> for ClientError {
+    fn from(value: Error) -> Self {
+        Self::Codec(value)
+    }
+}
+
+impl From<std::io::Error> for ClientError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+// A blocking client that sends one confirmed request at a time to a device (e.g. an address from who_is_broadcast)
+// Every request gets the next invoke_id and replies from other addresses or with other invoke_ids are skipped
+#[derive(Debug)]
+pub struct Client {
+    transport: UdpTransport,
+    timeout: Duration,
+    invoke_id: AtomicU8,
+}
+
+impl Client {
+    // timeout is how long to wait for the reply to each request
+    pub fn new(transport: UdpTransport, timeout: Duration) -> Self {
+        Self {
+            transport,
+            timeout,
+            invoke_id: AtomicU8::new(0),
+        }
+    }
+
+    pub fn transport(&self) -> &UdpTransport {
+        &self.transport
+    }
+
+    pub fn into_inner(self) -> UdpTransport {
+        self.transport
+    }
+
+    #[bacnet_macros::remove_lifetimes_from_fn_args]
+    pub fn read_property<'a>(
+        &self,
+        peer: SocketAddr,
+        buf: &'a mut [u8],
+        request: ReadProperty,
+    ) -> Result<ReadPropertyAck<'a>, ClientError> {
+        let service = ConfirmedRequestService::ReadProperty(request);
+        let ack = self.complex_request(peer, buf, service)?;
+        match ack.service {
+            ComplexAckService::ReadProperty(ack) => Ok(ack),
+            _ => Err(ClientError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService ReadPropertyAck",
+            ))),
+        }
+    }
+
+    pub fn write_property(
+        &self,
+        peer: SocketAddr,
+        buf: &mut [u8],
+        request: WriteProperty<'_>,
+    ) -> Result<(), ClientError> {
+        let service = ConfirmedRequestService::WriteProperty(request);
+        let _ack = self.simple_request(peer, buf, service)?;
+        Ok(())
+    }
+
+    // for services that are answered with a ComplexAck
+    #[bacnet_macros::remove_lifetimes_from_fn_args]
+    pub fn complex_request<'a>(
+        &self,
+        peer: SocketAddr,
+        buf: &'a mut [u8],
+        service: ConfirmedRequestService<'_>,
+    ) -> Result<ComplexAck<'a>, ClientError> {
+        let n = self.request(peer, buf, service)?;
+        match Self::decode_reply(&buf[..n])? {
+            ApplicationPdu::ComplexAck(ack) => Ok(ack),
+            _ => Err(ClientError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAck",
+            ))),
+        }
+    }
+
+    // for services that are answered with a SimpleAck
+    pub fn simple_request(
+        &self,
+        peer: SocketAddr,
+        buf: &mut [u8],
+        service: ConfirmedRequestService<'_>,
+    ) -> Result<SimpleAck, ClientError> {
+        let n = self.request(peer, buf, service)?;
+        match Self::decode_reply(&buf[..n])? {
+            ApplicationPdu::SimpleAck(ack) => Ok(ack),
+            _ => Err(ClientError::Codec(Error::ConvertDataLink(
+                "apdu message is not a SimpleAck",
+            ))),
+        }
+    }
+
+    // sends the request and returns the length of the reply in buf
+    fn request(
+        &self,
+        peer: SocketAddr,
+        buf: &mut [u8],
+        service: ConfirmedRequestService<'_>,
+    ) -> Result<usize, ClientError> {
+        let invoke_id = self.invoke_id.fetch_add(1, Ordering::SeqCst);
+        let apdu = ApplicationPdu::ConfirmedRequest(ConfirmedRequest::new(invoke_id, service));
+        let message = NetworkMessage::Apdu(apdu);
+        let npdu = NetworkPdu::new(None, None, true, MessagePriority::Normal, message);
+        let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+
+        let mut writer = Writer::new(buf);
+        data_link.encode(&mut writer);
+        let bytes = writer.try_to_bytes()?;
+        let socket = self.transport.socket();
+        socket.send_to(bytes, peer)?;
+
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(ClientError::Timeout);
+            }
+
+            socket.set_read_timeout(Some(remaining))?;
+            let (n, from) = match socket.recv_from(buf) {
+                Ok(x) => x,
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                    return Err(ClientError::Timeout)
+                }
+                Err(e) => return Err(ClientError::Io(e)),
+            };
+            if from != peer {
+                continue;
+            }
+
+            let mut reader = Reader::default();
+            let Ok(message) = DataLink::decode(&mut reader, &buf[..n]) else {
+                continue;
+            };
+            if reply_invoke_id(&message) == Some(invoke_id) {
+                return Ok(n);
+            }
+        }
+    }
+
+    // the server may respond to a confirmed request with an Abort, Reject or Error instead of an ack
+    #[bacnet_macros::remove_lifetimes_from_fn_args]
+    fn decode_reply<'a>(buf: &'a [u8]) -> Result<ApplicationPdu<'a>, ClientError> {
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, buf)?;
+        match message.into_npdu().map(|x| x.network_message) {
+            Some(NetworkMessage::Apdu(ApplicationPdu::Abort(abort))) => {
+                Err(ClientError::Abort(abort))
+            }
+            Some(NetworkMessage::Apdu(ApplicationPdu::Reject(reject))) => {
+                Err(ClientError::Reject(reject))
+            }
+            Some(NetworkMessage::Apdu(ApplicationPdu::Error(error))) => {
+                Err(ClientError::Server(error))
+            }
+            Some(NetworkMessage::Apdu(apdu)) => Ok(apdu),
+            _ => Err(ClientError::Codec(Error::ConvertDataLink(
+                "message is not an apdu",
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{Ipv4Addr, SocketAddr, UdpSocket},
+        thread,
+        time::Duration,
+        vec,
+        vec::Vec,
+    };
+
+    use crate::{
+        application_protocol::{
+            application_pdu::{ApplicationPdu, MaxAdpu},
+            confirmed::{
+                ComplexAck, ComplexAckService, ConfirmedBacnetError, ConfirmedRequestService,
+                ConfirmedServiceChoice, SimpleAck,
+            },
+            primitives::data_value::{ApplicationDataValue, ApplicationDataValueWrite},
+            services::{
+                i_am::IAm,
+                read_property::{ReadProperty, ReadPropertyAck, ReadPropertyValue},
+                write_property::WriteProperty,
+            },
+            unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{ErrorClass, ErrorCode, Segmentation, BACNET_ARRAY_ALL},
+        },
+        network_protocol::{
+            data_link::{DataLink, DataLinkFunction},
+            network_pdu::{MessagePriority, NetworkMessage, NetworkPdu},
+        },
+    };
+
+    use super::{subnet_broadcast, Client, ClientError, UdpTransport};
+
+    fn send_apdu(socket: &UdpSocket, peer: SocketAddr, apdu: ApplicationPdu) {
+        let message = NetworkMessage::Apdu(apdu);
+        let npdu = NetworkPdu::new(None, None, false, MessagePriority::Normal, message);
+        let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+        let mut out = vec![0; 1500];
+        let mut writer = Writer::new(&mut out);
+        data_link.encode(&mut writer);
+        socket
+            .send_to(writer.try_to_bytes().unwrap(), peer)
+            .unwrap();
+    }
+
+    // answers each ReadProperty with a late reply to an earlier request followed by the present value
+    // of the object (the invoke_id of the request) and then an Error PDU for any other request
+    fn spawn_device(requests: usize) -> SocketAddr {
+        let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = socket.local_addr().unwrap();
+
+        thread::spawn(move || {
+            let mut buf = vec![0; 1500];
+            for _ in 0..requests {
+                let (n, peer) = socket.recv_from(&mut buf).unwrap();
+                let mut reader = Reader::default();
+                let message = DataLink::decode(&mut reader, &buf[..n]).unwrap();
+                let request = match message.into_npdu().unwrap().network_message {
+                    NetworkMessage::Apdu(ApplicationPdu::ConfirmedRequest(x)) => x,
+                    x => panic!("expected a confirmed request: {:?}", x),
+                };
+                let invoke_id = request.invoke_id;
+
+                let late_reply = ApplicationPdu::SimpleAck(SimpleAck {
+                    invoke_id: invoke_id.wrapping_sub(1),
+                    service_choice: ConfirmedServiceChoice::WriteProperty,
+                });
+                send_apdu(&socket, peer, late_reply);
+
+                let reply = match request.service {
+                    ConfirmedRequestService::ReadProperty(x) => {
+                        ApplicationPdu::ComplexAck(ComplexAck {
+                            invoke_id,
+                            service: ComplexAckService::ReadProperty(ReadPropertyAck {
+                                object_id: x.object_id,
+                                property_id: x.property_id,
+                                array_index: BACNET_ARRAY_ALL,
+                                property_value: ReadPropertyValue::ApplicationDataValue(
+                                    ApplicationDataValue::UnsignedInt(invoke_id as u32),
+                                ),
+                            }),
+                        })
+                    }
+                    _ => ApplicationPdu::Error(ConfirmedBacnetError {
+                        invoke_id,
+                        service_choice: ConfirmedServiceChoice::WriteProperty,
+                        error_class: ErrorClass::Property,
+                        error_code: ErrorCode::WriteAccessDenied,
+                        first_failed_element: None,
+                        first_failed_write: None,
+                    }),
+                };
+                send_apdu(&socket, peer, reply);
+            }
+        });
+
+        addr
+    }
+
+    fn client(timeout: Duration) -> Client {
+        let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let broadcast_addr = socket.local_addr().unwrap();
+        Client::new(UdpTransport::new(socket, broadcast_addr).unwrap(), timeout)
+    }
+
+    #[test]
+    fn compute_subnet_broadcast() {
+        let ip = Ipv4Addr::new(192, 168, 1, 20);
+        assert_eq!(subnet_broadcast(ip, 24), Ipv4Addr::new(192, 168, 1, 255));
+        assert_eq!(subnet_broadcast(ip, 16), Ipv4Addr::new(192, 168, 255, 255));
+        assert_eq!(subnet_broadcast(ip, 32), ip);
+        assert_eq!(subnet_broadcast(ip, 0), Ipv4Addr::BROADCAST);
+    }
+
+    // answers every WhoIs it receives with an IAm for each device in range
+    fn spawn_responder(devices: &'static [u32]) -> SocketAddr {
+        let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = socket.local_addr().unwrap();
+
+        thread::spawn(move || {
+            let mut buf = vec![0; 1500];
+            let (n, peer) = socket.recv_from(&mut buf).unwrap();
+            let mut reader = Reader::default();
+            let message = DataLink::decode(&mut reader, &buf[..n]).unwrap();
+            let who_is = match message.into_npdu().unwrap().network_message {
+                NetworkMessage::Apdu(ApplicationPdu::UnconfirmedRequest(
+                    UnconfirmedRequest::WhoIs(who_is),
+                )) => who_is,
+                x => panic!("expected WhoIs: {:?}", x),
+            };
+
+            for instance in devices.iter().filter(|x| who_is.matches(**x)) {
+                let device_id = ObjectId::new(ObjectType::ObjectDevice, *instance);
+                let i_am = IAm::new(device_id, MaxAdpu::_1476, Segmentation::None, 260);
+                let apdu = ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::IAm(i_am));
+                let message = NetworkMessage::Apdu(apdu);
+                let npdu = NetworkPdu::new(None, None, false, MessagePriority::Normal, message);
+                let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+
+                let mut out = vec![0; 1500];
+                let mut writer = Writer::new(&mut out);
+                data_link.encode(&mut writer);
+                socket
+                    .send_to(writer.try_to_bytes().unwrap(), peer)
+                    .unwrap();
+            }
+        });
+
+        addr
+    }
+
+    #[test]
+    fn who_is_broadcast_collects_i_am_replies() {
+        let responder = spawn_responder(&[10, 20, 30]);
+        let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let transport = UdpTransport::new(socket, responder).unwrap();
+
+        let devices = transport
+            .who_is_broadcast(10, 20, Duration::from_millis(500))
+            .unwrap();
+
+        let mut instances: Vec<u32> = devices.iter().map(|(_, x)| x.device_id.id).collect();
+        instances.sort();
+        assert_eq!(instances, [10, 20]);
+        assert!(devices.iter().all(|(_, x)| x.vendor_id == 260));
+        assert!(devices.iter().all(|(addr, _)| *addr == responder));
+    }
+
+    #[test]
+    fn forwarded_i_am_uses_original_source() {
+        // an IAm from 192.168.2.20:47808 forwarded to us by the BBMD at 192.168.1.1
+        let bbmd = SocketAddr::from(([192, 168, 1, 1], 47808));
+        #[rustfmt::skip]
+        let buf = [
+            0x81, 0x04, 0x00, 0x1e, 192, 168, 2, 20, 0xba, 0xc0,
+            0x01, 0x20, 0xff, 0xff, 0x00, 0xff,
+            0x10, 0x00, 0xc4, 0x02, 0x00, 0x04, 0xd2, 0x22, 0x05, 0xc4, 0x91, 0x03, 0x21, 0x0f,
+        ];
+
+        let (addr, i_am) = UdpTransport::decode_i_am(&buf, bbmd).unwrap();
+        assert_eq!(addr, SocketAddr::from(([192, 168, 2, 20], 47808)));
+        assert_eq!(i_am.device_id.id, 1234);
+    }
+
+    #[test]
+    fn client_matches_replies_by_invoke_id() {
+        let device = spawn_device(3);
+        let client = client(Duration::from_secs(2));
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogValue, 1);
+        let mut buf = vec![0; 1500];
+
+        // each request gets the next invoke_id and the late reply before it is skipped
+        for expected in 0..2 {
+            let request = ReadProperty::new(object_id, PropertyId::PropPresentValue);
+            let ack = client.read_property(device, &mut buf, request).unwrap();
+            assert_eq!(ack.object_id, object_id);
+            assert!(matches!(
+                ack.property_value,
+                ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::UnsignedInt(x)) if x == expected
+            ));
+        }
+
+        let request = WriteProperty::new(
+            object_id,
+            PropertyId::PropPresentValue,
+            None,
+            None,
+            ApplicationDataValueWrite::Real(1.0),
+        );
+        let result = client.write_property(device, &mut buf, request);
+        match result {
+            Err(ClientError::Server(error)) => {
+                assert_eq!(error.error_code, ErrorCode::WriteAccessDenied)
+            }
+            x => panic!("expected an Error PDU, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn client_times_out() {
+        // bound but never replies
+        let device = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let client = client(Duration::from_millis(100));
+        let request = ReadProperty::new(
+            ObjectId::new(ObjectType::ObjectAnalogValue, 1),
+            PropertyId::PropPresentValue,
+        );
+        let mut buf = vec![0; 1500];
+
+        let result = client.read_property(device.local_addr().unwrap(), &mut buf, request);
+        assert!(matches!(result, Err(ClientError::Timeout)));
+    }
+}